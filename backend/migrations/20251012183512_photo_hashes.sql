@@ -0,0 +1,13 @@
+-- Add migration script here
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS plant_id UUID;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS content_hash VARCHAR;
+ALTER TABLE photos ADD COLUMN IF NOT EXISTS perceptual_hash BIGINT;
+
+-- Photos uploaded before this migration only referenced their plant through the photo event
+UPDATE photos SET plant_id = events.plant_id
+FROM events
+WHERE photos.plant_id IS NULL
+    AND events.event_type_id = '77271e34-e207-47cd-b360-f1db84db4f7e'
+    AND events.data::jsonb ->> 'String' = photos.file_location;
+
+CREATE INDEX IF NOT EXISTS photos_plant_content_hash ON photos (plant_id, content_hash);
@@ -0,0 +1,8 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS timelapses
+(
+    plant_id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    source_hash        VARCHAR        NOT NULL,
+    status        JSON        NOT NULL,
+    date_requested        timestamp        NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE plants ADD COLUMN IF NOT EXISTS cover_photo_id UUID;
+
+CREATE INDEX IF NOT EXISTS photos_photo_date ON photos (photo_date DESC, id DESC);
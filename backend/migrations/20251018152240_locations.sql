@@ -0,0 +1,16 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS locations
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    name        VARCHAR(250)        NOT NULL,
+    kind        JSON        NOT NULL,
+    parent_id        UUID,
+    light_level        JSON        NOT NULL,
+    direction        JSON        NOT NULL,
+    date_created        timestamp        NOT NULL
+);
+
+INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES
+    ('cf78cef1-d41f-431c-8c8f-6d8775400d1e', 'Moved', '"Location"', false, false, false)
+
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS extra_data_fields
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    name        VARCHAR(250) UNIQUE       NOT NULL,
+    kind        JSON        NOT NULL,
+    unit        VARCHAR(50),
+    date_created        timestamp        NOT NULL
+);
+
+-- Values are never overwritten, every change is a new row so the history of each field is kept
+CREATE TABLE IF NOT EXISTS plant_extra_data
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    plant_id        UUID        NOT NULL,
+    field_id        UUID        NOT NULL REFERENCES extra_data_fields (id),
+    data        JSON        NOT NULL,
+    date_changed        timestamp        NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS plant_extra_data_plant_idx ON plant_extra_data (plant_id, field_id, date_changed);
@@ -0,0 +1,22 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS notes
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    plant_id        UUID        NOT NULL,
+    event_id        UUID,
+    body        TEXT        NOT NULL,
+    date_created        timestamp        NOT NULL,
+    date_edited        timestamp        NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS notes_plant_idx ON notes (plant_id, date_created);
+
+CREATE TABLE IF NOT EXISTS note_revisions
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    note_id        UUID        NOT NULL REFERENCES notes (id) ON DELETE CASCADE,
+    body        TEXT        NOT NULL,
+    date_replaced        timestamp        NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS note_revisions_note_idx ON note_revisions (note_id, date_replaced);
@@ -0,0 +1,13 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS species
+(
+    id          UUID UNIQUE PRIMARY KEY NOT NULL,
+    scientific_name        VARCHAR(250) UNIQUE       NOT NULL,
+    common_names        JSON        NOT NULL,
+    family        VARCHAR(250)        NOT NULL,
+    care        JSON        NOT NULL,
+    user_defined        BOOLEAN        NOT NULL,
+    date_created        timestamp        NOT NULL
+);
+
+ALTER TABLE plants ADD COLUMN IF NOT EXISTS species_id UUID;
@@ -0,0 +1,9 @@
+-- Add migration script here
+ALTER TABLE plants ADD COLUMN IF NOT EXISTS parent_id UUID;
+
+CREATE INDEX IF NOT EXISTS plants_parent_idx ON plants (parent_id);
+
+INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES
+    ('1c650ce9-dba0-44c4-9a6b-a8881b5d7ad8', 'Propagated', '"Plant"', false, false, false)
+
+ON CONFLICT (id) DO NOTHING;
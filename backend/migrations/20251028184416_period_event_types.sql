@@ -0,0 +1,7 @@
+-- Add migration script here
+INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES
+    ('db8efed4-ee9d-4d9d-a1bd-136b96533903', 'Dormancy', '"Period"', false, false, false),
+    ('55703608-86fa-4808-89b7-8a9560d05430', 'Quarantine', '"Period"', false, false, false),
+    ('be66dbb6-5118-4314-bab7-15b9fcbd3548', 'Vacation Care', '"Period"', false, false, false)
+
+ON CONFLICT (id) DO NOTHING;
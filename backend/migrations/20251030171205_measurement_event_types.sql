@@ -0,0 +1,14 @@
+-- Add migration script here
+UPDATE event_types SET event_type = '{"Number":{"unit":"None","min":null,"max":null}}'
+WHERE event_type::jsonb = '"Number"'::jsonb;
+
+INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES
+    ('438a9f0e-5207-4f6d-8c5a-3672d8a773c3', 'Height', '{"Number":{"unit":"Centimeters","min":0,"max":null}}', false, false, false),
+    ('6376a8b2-ae2d-4a59-be46-d2ed2ab1f07f', 'Leaf Count', '{"Number":{"unit":"Leaves","min":0,"max":null}}', false, false, false),
+    ('41a75b1d-8a7b-4a73-a11b-97d70409ecd9', 'Soil Moisture', '{"Number":{"unit":"Percent","min":0,"max":100}}', false, false, false),
+    ('8a68fa47-eefa-4dab-860c-535cb37050c6', 'Soil pH', '{"Number":{"unit":"Ph","min":0,"max":14}}', false, false, false),
+    ('9d8ce1c4-4c73-4e88-a9e6-3bb446beefce', 'Light Level', '{"Number":{"unit":"Lux","min":0,"max":null}}', false, false, false)
+
+ON CONFLICT (id) DO NOTHING;
+
+CREATE INDEX IF NOT EXISTS events_plant_type_date ON events (plant_id, event_type_id, event_date);
@@ -0,0 +1,5 @@
+-- Add migration script here
+INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES
+    ('3e78fdcc-c44e-426d-80e7-afdbe5c43479', 'Symptoms', '{"MultiEnum":{"options":["Yellow Leaves","Brown Tips","Drooping","Leaf Drop","Spots","Pests","Root Rot"],"selected":[]}}', false, false, false)
+
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,15 @@
+-- Add migration script here
+UPDATE event_types SET event_type = '"Lifecycle"', modifiable = false
+WHERE id = '1a5c53bb-18c2-4789-8ba4-9bbfc4bc2371';
+
+-- Retired plants are treated as having died as it was never recorded what happened to them
+UPDATE events SET data = json_build_object('Lifecycle', json_build_object(
+    'from', NULL,
+    'to', CASE data::jsonb -> 'CustomEnum' -> 'options' ->> ((data::jsonb -> 'CustomEnum' ->> 'selected')::int)
+        WHEN 'Retired' THEN '{"Died":{"cause":null}}'::json
+        WHEN 'Gifted' THEN '{"Gifted":{"to":null}}'::json
+        ELSE '"Alive"'::json
+    END,
+    'note', NULL
+))
+WHERE event_type_id = '1a5c53bb-18c2-4789-8ba4-9bbfc4bc2371' AND data::jsonb ? 'CustomEnum';
@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS plant_tags
+(
+    plant_id          UUID    NOT NULL,
+    tag        VARCHAR(100)        NOT NULL,
+    PRIMARY KEY (plant_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS plant_tags_tag_idx ON plant_tags (tag);
+
+CREATE TABLE IF NOT EXISTS collections
+(
+    id          UUID PRIMARY KEY    NOT NULL,
+    name        VARCHAR(250)        NOT NULL,
+    filter        TEXT        NOT NULL,
+    date_created        timestamp        NOT NULL
+);
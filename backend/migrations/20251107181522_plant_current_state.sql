@@ -0,0 +1,13 @@
+-- Add migration script here
+-- The name of the state the latest state event of a plant moved it to. States with details are saved as objects keyed by their name
+-- and plants without a state event are Alive, the state every plant starts in
+CREATE OR REPLACE FUNCTION plant_current_state(plant UUID) RETURNS VARCHAR AS $$
+    SELECT COALESCE((
+        SELECT CASE jsonb_typeof(data::jsonb -> 'Lifecycle' -> 'to')
+            WHEN 'string' THEN data::jsonb -> 'Lifecycle' ->> 'to'
+            WHEN 'object' THEN (SELECT jsonb_object_keys(data::jsonb -> 'Lifecycle' -> 'to') LIMIT 1)
+        END
+        FROM events WHERE events.plant_id = plant AND events.event_type_id = '1a5c53bb-18c2-4789-8ba4-9bbfc4bc2371'
+        ORDER BY event_date DESC LIMIT 1
+    ), 'Alive')
+$$ LANGUAGE SQL STABLE;
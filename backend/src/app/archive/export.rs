@@ -0,0 +1,462 @@
+use std::io::Write;
+use std::path::Path;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{StatusCode, header},
+    response::Response,
+};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::archive::{
+    ARCHIVE_DATA_FILE, ARCHIVE_TOO_LARGE_HEADER, ARCHIVE_VERSION, Archive, ArchiveExtraData,
+    ArchiveExtraDataField, ArchiveLocation, ArchiveNoteRevision, ArchivePhoto, ArchivePlant,
+    ArchiveSpecies, ArchiveTag, MAX_ARCHIVE_SIZE,
+};
+use shared::collections::Collection;
+use shared::events::{EventDataKind, EventInstance, EventType};
+use shared::locations::{Direction, LightLevel, Location, LocationKind};
+use shared::notes::Note;
+use shared::plant::{ExtraDataField, ExtraDataKind, ExtraDataType};
+use shared::species::{CareProfile, Species};
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow, types::Json};
+use tokio::{fs, io::AsyncReadExt};
+use uuid::Uuid;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::app::archive::ArchiveError;
+use crate::app::events::EventInstanceRow;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveEventTypeRow {
+    pub id: Uuid,
+    pub name: String,
+    pub event_type: Json<EventDataKind>,
+    pub deletable: bool,
+    pub modifiable: bool,
+    pub is_unique: bool,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchivePlantRow {
+    pub id: Uuid,
+    pub date_created: NaiveDateTime,
+    pub species_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub cover_photo_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchivePhotoRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub photo_date: NaiveDateTime,
+    pub file_location: String,
+    pub content_hash: Option<String>,
+    pub perceptual_hash: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveSpeciesRow {
+    pub id: Uuid,
+    pub scientific_name: String,
+    pub common_names: Json<Vec<String>>,
+    pub family: String,
+    pub care: Json<CareProfile>,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveLocationRow {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: Json<LocationKind>,
+    pub parent_id: Option<Uuid>,
+    pub light_level: Json<Option<LightLevel>>,
+    pub direction: Json<Option<Direction>>,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveExtraDataFieldRow {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: Json<ExtraDataKind>,
+    pub unit: Option<String>,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveExtraDataRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub field_id: Uuid,
+    pub data: Json<ExtraDataType>,
+    pub date_changed: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveNoteRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub body: String,
+    pub date_created: NaiveDateTime,
+    pub date_edited: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveNoteRevisionRow {
+    pub id: Uuid,
+    pub note_id: Uuid,
+    pub body: String,
+    pub date_replaced: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveTagRow {
+    pub plant_id: Uuid,
+    pub tag: String,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ArchiveCollectionRow {
+    pub id: Uuid,
+    pub name: String,
+    pub filter: String,
+    pub date_created: NaiveDateTime,
+}
+
+/// Downloads every plant along with its events, photos, notes, extra data and tags, and the locations, species and collections
+/// they use, as a zip archive that can be restored with the import endpoint.
+///
+/// The archive is written to a temporary file and streamed from there, since it carries every photo. An archive larger than the
+/// import endpoint accepts is still sent, marked with the [`ARCHIVE_TOO_LARGE_HEADER`] header, and has to be restored with the
+/// import command instead
+pub async fn export_archive(State(pool): State<PgPool>) -> Response {
+    let path = std::env::temp_dir().join(format!("house-of-eden-export-{}.zip", Uuid::new_v4()));
+    let size = match build_archive(pool, &path).await {
+        Ok(result) => result,
+        Err(err) => {
+            let _ = fs::remove_file(&path).await;
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let mut file = match fs::File::open(&path).await {
+        Ok(result) => result,
+        Err(err) => {
+            let _ = fs::remove_file(&path).await;
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    // The open file can still be read once it is removed, so nothing is left behind however the download ends
+    let _ = fs::remove_file(&path).await;
+
+    let stream = async_stream::stream! {
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            match file.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(read) => yield Ok(Bytes::copy_from_slice(&buffer[..read])),
+                Err(err) => {
+                    yield Err(err);
+                    break;
+                }
+            }
+        }
+    };
+
+    let mut response = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(header::CONTENT_LENGTH, size)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"house-of-eden-{}.zip\"",
+                Utc::now().format("%Y-%m-%d")
+            ),
+        );
+    if size > MAX_ARCHIVE_SIZE as u64 {
+        tracing::warn!(
+            "Exported archive is {} bytes, more than the {} bytes the import endpoint accepts. It has to be restored with the import command",
+            size,
+            MAX_ARCHIVE_SIZE
+        );
+        response = response.header(ARCHIVE_TOO_LARGE_HEADER, "true");
+    }
+
+    response.body(Body::from_stream(stream)).unwrap()
+}
+
+/// Writes the whole instance into a zip archive at the given path and returns its size in bytes. The data goes into a single JSON
+/// file and the photos are stored next to it, one photo in memory at a time.
+///
+/// Everything is read in one read only transaction so the archive is a snapshot of a single moment. Only species added by the
+/// user are written since the bundled catalog is seeded on every instance
+pub async fn build_archive(pool: Pool<Postgres>, path: &Path) -> Result<u64, ArchiveError> {
+    let mut transaction = pool.begin().await?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await?;
+
+    let plants: Vec<ArchivePlantRow> = sqlx::query_as(
+        r#"SELECT id, date_created, species_id, parent_id, cover_photo_id FROM plants ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let event_types: Vec<ArchiveEventTypeRow> = sqlx::query_as(
+        r#"SELECT id, name, event_type, deletable, modifiable, is_unique FROM event_types"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let events: Vec<EventInstanceRow> = sqlx::query_as(
+        r#"SELECT id, event_type_id, plant_id, data, event_date FROM events ORDER BY event_date"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let unique_events: Vec<EventInstanceRow> = sqlx::query_as(
+        r#"SELECT id, event_type_id, plant_id, data, event_date FROM events_unique ORDER BY event_date"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let photo_rows: Vec<ArchivePhotoRow> = sqlx::query_as(
+        r#"SELECT id, plant_id, photo_date, file_location, content_hash, perceptual_hash
+        FROM photos WHERE plant_id IS NOT NULL ORDER BY photo_date"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let species: Vec<ArchiveSpeciesRow> = sqlx::query_as(
+        r#"SELECT id, scientific_name, common_names, family, care, date_created FROM species
+        WHERE user_defined ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let locations: Vec<ArchiveLocationRow> = sqlx::query_as(
+        r#"SELECT id, name, kind, parent_id, light_level, direction, date_created FROM locations ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let extra_data_fields: Vec<ArchiveExtraDataFieldRow> = sqlx::query_as(
+        r#"SELECT id, name, kind, unit, date_created FROM extra_data_fields ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let extra_data: Vec<ArchiveExtraDataRow> = sqlx::query_as(
+        r#"SELECT id, plant_id, field_id, data, date_changed FROM plant_extra_data ORDER BY date_changed"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let notes: Vec<ArchiveNoteRow> = sqlx::query_as(
+        r#"SELECT id, plant_id, event_id, body, date_created, date_edited FROM notes ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let note_revisions: Vec<ArchiveNoteRevisionRow> = sqlx::query_as(
+        r#"SELECT id, note_id, body, date_replaced FROM note_revisions ORDER BY date_replaced"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    let tags: Vec<ArchiveTagRow> =
+        sqlx::query_as(r#"SELECT plant_id, tag FROM plant_tags ORDER BY plant_id, tag"#)
+            .fetch_all(&mut *transaction)
+            .await?;
+
+    let collections: Vec<ArchiveCollectionRow> = sqlx::query_as(
+        r#"SELECT id, name, filter, date_created FROM collections ORDER BY date_created"#,
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+
+    // Nothing else is read from the database, the photo files are read while the archive is written
+    transaction.commit().await?;
+
+    let mut zip = ZipWriter::new(std::fs::File::create(path)?);
+    // Photos are already compressed so deflating them again only costs time
+    let photo_options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    let mut photos = Vec::new();
+    for row in photo_rows {
+        let file_name = match Path::new(&row.file_location).file_name() {
+            Some(file_name) => file_name.to_string_lossy().to_string(),
+            None => format!("{}", row.id),
+        };
+        // A photo whose file is gone can't be restored so it is left out instead of failing the whole export
+        let binary = match fs::read(&row.file_location).await {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::warn!(
+                    "Leaving photo {} out of the archive, its file can't be read: {}",
+                    row.id,
+                    err
+                );
+                continue;
+            }
+        };
+
+        let photo = ArchivePhoto {
+            id: row.id,
+            plant_id: row.plant_id,
+            photo_date: row.photo_date,
+            file_name,
+            content_hash: row.content_hash,
+            perceptual_hash: row.perceptual_hash,
+        };
+        zip.start_file(photo.archive_path(), photo_options)?;
+        zip.write_all(&binary)?;
+        photos.push(photo);
+    }
+
+    let archive = Archive {
+        version: ARCHIVE_VERSION,
+        exported: Utc::now().naive_utc(),
+        plants: plants
+            .into_iter()
+            .map(|row| ArchivePlant {
+                id: row.id,
+                date_created: row.date_created,
+                species_id: row.species_id,
+                parent_id: row.parent_id,
+                cover_photo_id: row.cover_photo_id,
+            })
+            .collect(),
+        event_types: event_types
+            .into_iter()
+            .map(|row| EventType {
+                id: row.id,
+                name: row.name,
+                kind: row.event_type.0,
+                deletable: row.deletable,
+                modifiable: row.modifiable,
+                is_unique: row.is_unique,
+            })
+            .collect(),
+        events: events.into_iter().map(into_event_instance).collect(),
+        unique_events: unique_events.into_iter().map(into_event_instance).collect(),
+        photos,
+        species: species
+            .into_iter()
+            .map(|row| ArchiveSpecies {
+                species: Species {
+                    id: row.id,
+                    scientific_name: row.scientific_name,
+                    common_names: row.common_names.0,
+                    family: row.family,
+                    care: row.care.0,
+                    user_defined: true,
+                },
+                date_created: row.date_created,
+            })
+            .collect(),
+        locations: locations
+            .into_iter()
+            .map(|row| ArchiveLocation {
+                location: Location {
+                    id: row.id,
+                    name: row.name,
+                    kind: row.kind.0,
+                    parent_id: row.parent_id,
+                    light_level: row.light_level.0,
+                    direction: row.direction.0,
+                },
+                date_created: row.date_created,
+            })
+            .collect(),
+        extra_data_fields: extra_data_fields
+            .into_iter()
+            .map(|row| ArchiveExtraDataField {
+                field: ExtraDataField {
+                    id: row.id,
+                    name: row.name,
+                    kind: row.kind.0,
+                    unit: row.unit,
+                },
+                date_created: row.date_created,
+            })
+            .collect(),
+        extra_data: extra_data
+            .into_iter()
+            .map(|row| ArchiveExtraData {
+                id: row.id,
+                plant_id: row.plant_id,
+                field_id: row.field_id,
+                data: row.data.0,
+                date_changed: row.date_changed,
+            })
+            .collect(),
+        notes: notes
+            .into_iter()
+            .map(|row| Note {
+                id: row.id,
+                plant_id: row.plant_id,
+                event_id: row.event_id,
+                body: row.body,
+                date_created: row.date_created,
+                date_edited: row.date_edited,
+            })
+            .collect(),
+        note_revisions: note_revisions
+            .into_iter()
+            .map(|row| ArchiveNoteRevision {
+                id: row.id,
+                note_id: row.note_id,
+                body: row.body,
+                date_replaced: row.date_replaced,
+            })
+            .collect(),
+        tags: tags
+            .into_iter()
+            .map(|row| ArchiveTag {
+                plant_id: row.plant_id,
+                tag: row.tag,
+            })
+            .collect(),
+        collections: collections
+            .into_iter()
+            .map(|row| Collection {
+                id: row.id,
+                name: row.name,
+                filter: row.filter,
+                date_created: row.date_created,
+            })
+            .collect(),
+    };
+
+    zip.start_file(
+        ARCHIVE_DATA_FILE,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )?;
+    serde_json::to_writer_pretty(&mut zip, &archive)?;
+
+    let file = zip.finish()?;
+    Ok(file.metadata()?.len())
+}
+
+fn into_event_instance(row: EventInstanceRow) -> EventInstance {
+    EventInstance {
+        id: row.id,
+        event_type_id: row.event_type_id,
+        plant_id: row.plant_id,
+        data: row.data.0,
+        event_date: row.event_date,
+    }
+}
@@ -0,0 +1,711 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+use axum::{body::Body, body::Bytes, extract::State, http::StatusCode, response::Response};
+use chrono::{NaiveDateTime, Utc};
+use image::ImageReader;
+use serde::{Deserialize, Serialize};
+use shared::{
+    CacheType, DirtyCache,
+    archive::{
+        ARCHIVE_DATA_FILE, ARCHIVE_VERSION, Archive, ImportReport, MAX_ARCHIVE_DATA_SIZE,
+        MAX_ARCHIVE_PHOTO_SIZE, RejectedEvent,
+    },
+    collections::{CollectionFilter, MAX_FILTER_LENGTH},
+    events::{EventData, EventDataKind, EventInstance},
+    plant::{ExtraDataKind, normalize_tag},
+};
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow, types::Json};
+use tokio::{fs, sync::mpsc::Sender};
+use uuid::Uuid;
+use zip::ZipArchive;
+
+use crate::app::{archive::ArchiveError, events::check_event_data};
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ExistingEventTypeRow {
+    pub id: Uuid,
+    pub name: String,
+    pub event_type: Json<EventDataKind>,
+    pub is_unique: bool,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ExistingSpeciesRow {
+    pub id: Uuid,
+    pub scientific_name: String,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ExistingExtraDataFieldRow {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: Json<ExtraDataKind>,
+}
+
+/// The event type an event of the archive ends up logged under on this instance
+struct ResolvedEventType {
+    id: Uuid,
+    kind: EventDataKind,
+    is_unique: bool,
+}
+
+/// Restores an archive made by the export endpoint. Safe to send the same archive more than once
+pub async fn import_archive(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    body: Bytes,
+) -> Response {
+    let (report, dirty_caches) = match restore_archive(Cursor::new(body), pool).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(err.status())
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    for cache in dirty_caches {
+        let _ = dirt_cache.send(DirtyCache { cache }).await;
+    }
+
+    let serialize = match serde_json::to_string(&report) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
+
+/// Restores an archive into this instance in one transaction and returns what was restored along with every cache that changed.
+///
+/// Ids are kept so anything that already exists is skipped rather than duplicated. Event types, species and extra data fields that
+/// don't exist by id are merged into an existing one with the same name, otherwise they are added. Every event is checked against
+/// the event type it ends up under and every location and plant it points at must be in the archive or on this instance. Events
+/// that don't fit are reported instead of restored, as is anything else that belongs to a plant that isn't there
+pub async fn restore_archive<R: Read + Seek + Send>(
+    reader: R,
+    pool: Pool<Postgres>,
+) -> Result<(ImportReport, Vec<CacheType>), ArchiveError> {
+    let mut zip = ZipArchive::new(reader)?;
+
+    let data = read_capped(
+        zip.by_name(ARCHIVE_DATA_FILE)?,
+        MAX_ARCHIVE_DATA_SIZE,
+        ARCHIVE_DATA_FILE,
+    )?;
+    let archive: Archive = serde_json::from_slice(&data)?;
+    drop(data);
+    if archive.version > ARCHIVE_VERSION {
+        return Err(ArchiveError::Invalid(format!(
+            "Archive version {} is newer than the newest supported version {}",
+            archive.version, ARCHIVE_VERSION
+        )));
+    }
+
+    // Photos are only read once they are restored so just one is held in memory at a time
+    for photo in &archive.photos {
+        if zip.index_for_name(&photo.archive_path()).is_none() {
+            return Err(ArchiveError::Invalid(format!(
+                "Photo {} is missing from the archive",
+                photo.file_name
+            )));
+        }
+    }
+
+    let mut report = ImportReport::default();
+    let mut dirty_caches: Vec<CacheType> = vec![];
+    let now = Utc::now().naive_utc();
+
+    let mut transaction = pool.begin().await?;
+
+    // Event types are resolved first since every event has to be checked against the event type it is restored under
+    let existing_types: Vec<ExistingEventTypeRow> =
+        sqlx::query_as(r#"SELECT id, name, event_type, is_unique FROM event_types"#)
+            .fetch_all(&mut *transaction)
+            .await?;
+    let mut event_types: HashMap<Uuid, ResolvedEventType> = existing_types
+        .iter()
+        .map(|row| {
+            (
+                row.id,
+                ResolvedEventType {
+                    id: row.id,
+                    kind: row.event_type.0.clone(),
+                    is_unique: row.is_unique,
+                },
+            )
+        })
+        .collect();
+
+    for event_type in &archive.event_types {
+        if event_types.contains_key(&event_type.id) {
+            report.event_types_merged += 1;
+            continue;
+        }
+
+        let same_type = existing_types.iter().find(|row| {
+            row.name.eq_ignore_ascii_case(&event_type.name)
+                && row.event_type.0 == event_type.kind
+                && row.is_unique == event_type.is_unique
+        });
+        if let Some(row) = same_type {
+            report.event_types_merged += 1;
+            event_types.insert(
+                event_type.id,
+                ResolvedEventType {
+                    id: row.id,
+                    kind: row.event_type.0.clone(),
+                    is_unique: row.is_unique,
+                },
+            );
+            continue;
+        }
+
+        sqlx::query(
+            r#"INSERT INTO event_types (id, name, event_type, deletable, modifiable, is_unique) VALUES ($1, $2, $3, $4, $5, $6)"#,
+        )
+        .bind(event_type.id)
+        .bind(&event_type.name)
+        .bind(Json(&event_type.kind))
+        .bind(event_type.deletable)
+        .bind(event_type.modifiable)
+        .bind(event_type.is_unique)
+        .execute(&mut *transaction)
+        .await?;
+        report.event_types_added += 1;
+        dirty_caches.push(CacheType::EventType(event_type.id));
+        event_types.insert(
+            event_type.id,
+            ResolvedEventType {
+                id: event_type.id,
+                kind: event_type.kind.clone(),
+                is_unique: event_type.is_unique,
+            },
+        );
+    }
+
+    // Species are matched by id, then by scientific name since the same species can be added on two instances
+    let existing_species: Vec<ExistingSpeciesRow> =
+        sqlx::query_as(r#"SELECT id, scientific_name FROM species"#)
+            .fetch_all(&mut *transaction)
+            .await?;
+    let mut species_ids: HashMap<Uuid, Uuid> = existing_species
+        .iter()
+        .map(|row| (row.id, row.id))
+        .collect();
+    for archive_species in &archive.species {
+        let species = &archive_species.species;
+        if species_ids.contains_key(&species.id) {
+            continue;
+        }
+        let same_species = existing_species.iter().find(|row| {
+            row.scientific_name
+                .eq_ignore_ascii_case(&species.scientific_name)
+        });
+        if let Some(row) = same_species {
+            species_ids.insert(species.id, row.id);
+            continue;
+        }
+
+        sqlx::query(
+            r#"INSERT INTO species (id, scientific_name, common_names, family, care, user_defined, date_created)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+        )
+        .bind(species.id)
+        .bind(&species.scientific_name)
+        .bind(Json(&species.common_names))
+        .bind(&species.family)
+        .bind(Json(&species.care))
+        .bind(true)
+        .bind(archive_species.date_created)
+        .execute(&mut *transaction)
+        .await?;
+        report.species_added += 1;
+        dirty_caches.push(CacheType::Species(species.id));
+        species_ids.insert(species.id, species.id);
+    }
+
+    for archive_location in &archive.locations {
+        let location = &archive_location.location;
+        let added = sqlx::query(
+            r#"INSERT INTO locations (id, name, kind, parent_id, light_level, direction, date_created)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(location.id)
+        .bind(&location.name)
+        .bind(Json(location.kind))
+        .bind(location.parent_id)
+        .bind(Json(location.light_level))
+        .bind(Json(location.direction))
+        .bind(archive_location.date_created)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if added {
+            report.locations_added += 1;
+            dirty_caches.push(CacheType::Location(location.id));
+        }
+    }
+    let existing_locations: HashSet<Uuid> = sqlx::query_scalar(r#"SELECT id FROM locations"#)
+        .fetch_all(&mut *transaction)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut changed_plants: HashSet<Uuid> = HashSet::new();
+
+    for plant in &archive.plants {
+        let species_id = match plant.species_id {
+            Some(species_id) => match species_ids.get(&species_id) {
+                Some(species_id) => Some(*species_id),
+                None => {
+                    report.problems.push(format!(
+                        "Plant {} is restored without its species {}, which is not in the archive or on this instance",
+                        plant.id, species_id
+                    ));
+                    None
+                }
+            },
+            None => None,
+        };
+        let added = sqlx::query(
+            r#"INSERT INTO plants (id, date_created, event_modified, cover_photo_id, species_id, parent_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(plant.id)
+        .bind(plant.date_created)
+        .bind(now)
+        .bind(plant.cover_photo_id)
+        .bind(species_id)
+        .bind(plant.parent_id)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        match added {
+            true => {
+                report.plants_added += 1;
+                changed_plants.insert(plant.id);
+            }
+            false => report.plants_existing += 1,
+        }
+    }
+
+    // Everything else belongs to a plant, which must be in the archive or already on this instance
+    let plant_ids: Vec<Uuid> = archive
+        .events
+        .iter()
+        .chain(archive.unique_events.iter())
+        .flat_map(|event| match event.data {
+            EventData::Plant(other_plant_id) => vec![event.plant_id, other_plant_id],
+            _ => vec![event.plant_id],
+        })
+        .chain(archive.photos.iter().map(|photo| photo.plant_id))
+        .chain(archive.extra_data.iter().map(|value| value.plant_id))
+        .chain(archive.notes.iter().map(|note| note.plant_id))
+        .chain(archive.tags.iter().map(|tag| tag.plant_id))
+        .collect();
+    let existing_plants: HashSet<Uuid> =
+        sqlx::query_scalar(r#"SELECT id FROM plants WHERE id = ANY($1)"#)
+            .bind(&plant_ids)
+            .fetch_all(&mut *transaction)
+            .await?
+            .into_iter()
+            .collect();
+
+    let unique_event_ids: Vec<Uuid> = archive.unique_events.iter().map(|event| event.id).collect();
+    let existing_unique_events: HashSet<Uuid> =
+        sqlx::query_scalar(r#"SELECT id FROM events_unique WHERE id = ANY($1)"#)
+            .bind(&unique_event_ids)
+            .fetch_all(&mut *transaction)
+            .await?
+            .into_iter()
+            .collect();
+
+    // Every restored event by its event type, so open timelines and calendars of the plants refresh
+    let mut logged_events: HashMap<Uuid, Vec<(Uuid, NaiveDateTime)>> = HashMap::new();
+    for event in archive.events.iter().chain(archive.unique_events.iter()) {
+        let Some(event_type) = event_types.get(&event.event_type_id) else {
+            report
+                .rejected_events
+                .push(rejected(event, "Event type is not in the archive"));
+            continue;
+        };
+        if !existing_plants.contains(&event.plant_id) {
+            report
+                .rejected_events
+                .push(rejected(event, "Plant is not in the archive"));
+            continue;
+        }
+        if let Some(problem) = check_event_data(&event.data, &event_type.kind) {
+            report.rejected_events.push(rejected(event, &problem));
+            continue;
+        }
+        match event.data {
+            EventData::Location(location_id) if !existing_locations.contains(&location_id) => {
+                report
+                    .rejected_events
+                    .push(rejected(event, "Location is not in the archive"));
+                continue;
+            }
+            EventData::Plant(other_plant_id)
+                if !existing_plants.contains(&other_plant_id)
+                    || other_plant_id == event.plant_id =>
+            {
+                report
+                    .rejected_events
+                    .push(rejected(event, "Other plant is not in the archive"));
+                continue;
+            }
+            _ => {}
+        }
+
+        // Unique events only replace the plants current one when they are newer
+        let added = match event_type.is_unique {
+            true if existing_unique_events.contains(&event.id) => false,
+            true => {
+                sqlx::query(
+                    r#"INSERT INTO events_unique (id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (event_type_id, plant_id) DO UPDATE SET id = EXCLUDED.id, event_date = EXCLUDED.event_date, data = EXCLUDED.data
+                    WHERE events_unique.event_date < EXCLUDED.event_date"#,
+                )
+                .bind(event.id)
+                .bind(event_type.id)
+                .bind(event.plant_id)
+                .bind(Json(&event.data))
+                .bind(event.event_date)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected()
+                    > 0
+            }
+            false => {
+                sqlx::query(
+                    r#"INSERT INTO events (id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (id) DO NOTHING"#,
+                )
+                .bind(event.id)
+                .bind(event_type.id)
+                .bind(event.plant_id)
+                .bind(Json(&event.data))
+                .bind(event.event_date)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected()
+                    > 0
+            }
+        };
+        match added {
+            true => {
+                report.events_added += 1;
+                changed_plants.insert(event.plant_id);
+                logged_events
+                    .entry(event_type.id)
+                    .or_default()
+                    .push((event.plant_id, event.event_date));
+            }
+            false => report.events_existing += 1,
+        }
+    }
+
+    for photo in &archive.photos {
+        if !existing_plants.contains(&photo.plant_id) {
+            report.problems.push(format!(
+                "Photo {} is left out, its plant {} is not in the archive",
+                photo.id, photo.plant_id
+            ));
+            continue;
+        }
+
+        // The file is named after the photo rather than trusting the name in the archive, photo events point at the same location
+        let extension = Path::new(&photo.file_name)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_string())
+            .unwrap_or("png".to_string());
+        let file_location = format!("./assets/photos/{}.{}", photo.id, extension);
+
+        let added = sqlx::query(
+            r#"INSERT INTO photos (id, file_location, photo_date, plant_id, content_hash, perceptual_hash) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(photo.id)
+        .bind(&file_location)
+        .bind(photo.photo_date)
+        .bind(photo.plant_id)
+        .bind(&photo.content_hash)
+        .bind(photo.perceptual_hash)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if !added {
+            report.photos_existing += 1;
+            continue;
+        }
+
+        let binary = read_capped(
+            zip.by_name(&photo.archive_path())?,
+            MAX_ARCHIVE_PHOTO_SIZE,
+            &photo.file_name,
+        )?;
+        // Files are written before the commit so a photo never exists without its file. A failed import leaves files behind
+        // that the next import of the same archive overwrites
+        let thumbnail = ImageReader::new(Cursor::new(&binary))
+            .with_guessed_format()?
+            .decode()?
+            .resize(200, 200, image::imageops::FilterType::Gaussian);
+        let mut buf: Vec<u8> = Vec::new();
+        thumbnail.write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)?;
+        fs::write(format!("./assets/photos/thumbs/{}.png", photo.id), buf).await?;
+        fs::write(&file_location, binary).await?;
+
+        report.photos_added += 1;
+        changed_plants.insert(photo.plant_id);
+    }
+
+    // Field names are unique so a field is matched by name when its id isn't known, but only if it holds the same kind of value
+    let existing_fields: Vec<ExistingExtraDataFieldRow> =
+        sqlx::query_as(r#"SELECT id, name, kind FROM extra_data_fields"#)
+            .fetch_all(&mut *transaction)
+            .await?;
+    let mut fields: HashMap<Uuid, (Uuid, ExtraDataKind)> = existing_fields
+        .iter()
+        .map(|row| (row.id, (row.id, row.kind.0)))
+        .collect();
+    for archive_field in &archive.extra_data_fields {
+        let field = &archive_field.field;
+        let existing = existing_fields
+            .iter()
+            .find(|row| row.id == field.id || row.name == field.name);
+        match existing {
+            Some(row) if row.kind.0 == field.kind => {
+                fields.insert(field.id, (row.id, row.kind.0));
+            }
+            Some(row) => {
+                fields.remove(&field.id);
+                report.problems.push(format!(
+                    "Extra data field {} is left out along with its values, the field {} on this instance holds a different kind of value",
+                    field.name, row.name
+                ));
+            }
+            None => {
+                sqlx::query(
+                    r#"INSERT INTO extra_data_fields (id, name, kind, unit, date_created) VALUES ($1, $2, $3, $4, $5)"#,
+                )
+                .bind(field.id)
+                .bind(&field.name)
+                .bind(Json(field.kind))
+                .bind(&field.unit)
+                .bind(archive_field.date_created)
+                .execute(&mut *transaction)
+                .await?;
+                dirty_caches.push(CacheType::ExtraDataField(field.id));
+                fields.insert(field.id, (field.id, field.kind));
+            }
+        }
+    }
+
+    for value in &archive.extra_data {
+        let Some((field_id, kind)) = fields.get(&value.field_id) else {
+            continue;
+        };
+        if !existing_plants.contains(&value.plant_id) || value.data.kind() != *kind {
+            report.problems.push(format!(
+                "Extra data value {} is left out, its plant is not in the archive or it doesn't fit its field",
+                value.id
+            ));
+            continue;
+        }
+        let added = sqlx::query(
+            r#"INSERT INTO plant_extra_data (id, plant_id, field_id, data, date_changed) VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(value.id)
+        .bind(value.plant_id)
+        .bind(field_id)
+        .bind(Json(&value.data))
+        .bind(value.date_changed)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if added {
+            report.extra_data_added += 1;
+            changed_plants.insert(value.plant_id);
+        }
+    }
+
+    // A note about an event that wasn't restored is kept as a note about the plant
+    let event_ids: Vec<Uuid> = archive
+        .notes
+        .iter()
+        .filter_map(|note| note.event_id)
+        .collect();
+    let existing_events: HashSet<Uuid> = sqlx::query_scalar(
+        r#"SELECT id FROM events WHERE id = ANY($1) UNION SELECT id FROM events_unique WHERE id = ANY($1)"#,
+    )
+    .bind(&event_ids)
+    .fetch_all(&mut *transaction)
+    .await?
+    .into_iter()
+    .collect();
+    for note in &archive.notes {
+        if !existing_plants.contains(&note.plant_id) {
+            report.problems.push(format!(
+                "Note {} is left out, its plant {} is not in the archive",
+                note.id, note.plant_id
+            ));
+            continue;
+        }
+        let added = sqlx::query(
+            r#"INSERT INTO notes (id, plant_id, event_id, body, date_created, date_edited) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(note.id)
+        .bind(note.plant_id)
+        .bind(note.event_id.filter(|event_id| existing_events.contains(event_id)))
+        .bind(&note.body)
+        .bind(note.date_created)
+        .bind(note.date_edited)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if added {
+            report.notes_added += 1;
+            dirty_caches.push(CacheType::Note(note.plant_id, note.id));
+        }
+    }
+
+    let note_ids: Vec<Uuid> = archive
+        .note_revisions
+        .iter()
+        .map(|revision| revision.note_id)
+        .collect();
+    let existing_notes: HashSet<Uuid> =
+        sqlx::query_scalar(r#"SELECT id FROM notes WHERE id = ANY($1)"#)
+            .bind(&note_ids)
+            .fetch_all(&mut *transaction)
+            .await?
+            .into_iter()
+            .collect();
+    for revision in &archive.note_revisions {
+        if !existing_notes.contains(&revision.note_id) {
+            continue;
+        }
+        sqlx::query(
+            r#"INSERT INTO note_revisions (id, note_id, body, date_replaced) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(revision.id)
+        .bind(revision.note_id)
+        .bind(&revision.body)
+        .bind(revision.date_replaced)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for tag in &archive.tags {
+        if !existing_plants.contains(&tag.plant_id) {
+            continue;
+        }
+        let Some(normalized) = normalize_tag(&tag.tag) else {
+            report.problems.push(format!(
+                "Tag {} is left out, it is not a valid tag",
+                tag.tag
+            ));
+            continue;
+        };
+        let added = sqlx::query(
+            r#"INSERT INTO plant_tags (plant_id, tag) VALUES ($1, $2) ON CONFLICT DO NOTHING"#,
+        )
+        .bind(tag.plant_id)
+        .bind(normalized)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if added {
+            report.tags_added += 1;
+            changed_plants.insert(tag.plant_id);
+        }
+    }
+
+    // Collection filters are checked the same way as a new collection so a bad archive can't save one that never loads
+    for collection in &archive.collections {
+        if collection.filter.chars().count() > MAX_FILTER_LENGTH
+            || CollectionFilter::parse(&collection.filter).is_err()
+        {
+            report.problems.push(format!(
+                "Collection {} is left out, its filter is not valid",
+                collection.name
+            ));
+            continue;
+        }
+        let added = sqlx::query(
+            r#"INSERT INTO collections (id, name, filter, date_created) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(collection.id)
+        .bind(&collection.name)
+        .bind(&collection.filter)
+        .bind(collection.date_created)
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected()
+            > 0;
+        if added {
+            report.collections_added += 1;
+            dirty_caches.push(CacheType::Collection(collection.id));
+        }
+    }
+
+    // The plant demographics are built from their latest events so let clients know they changed
+    let changed_plants: Vec<Uuid> = changed_plants.into_iter().collect();
+    sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = ANY($1)")
+        .bind(&changed_plants)
+        .bind(now)
+        .execute(&mut *transaction)
+        .await?;
+
+    transaction.commit().await?;
+    dirty_caches.extend(changed_plants.into_iter().map(CacheType::Plant));
+    dirty_caches.extend(
+        logged_events
+            .into_iter()
+            .map(|(event_type, events)| CacheType::Events(event_type, events)),
+    );
+    Ok((report, dirty_caches))
+}
+
+/// Reads a file of the archive, refusing files that decompress to more than the given size
+fn read_capped(file: impl Read, max_size: u64, name: &str) -> Result<Vec<u8>, ArchiveError> {
+    let mut binary = Vec::new();
+    file.take(max_size + 1).read_to_end(&mut binary)?;
+    if binary.len() as u64 > max_size {
+        return Err(ArchiveError::Invalid(format!(
+            "{} in the archive is larger than the limit of {} bytes",
+            name, max_size
+        )));
+    }
+    Ok(binary)
+}
+
+fn rejected(event: &EventInstance, reason: &str) -> RejectedEvent {
+    RejectedEvent {
+        event_id: event.id,
+        plant_id: event.plant_id,
+        reason: reason.to_string(),
+    }
+}
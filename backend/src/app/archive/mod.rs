@@ -0,0 +1,96 @@
+use std::fmt;
+
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    http::StatusCode,
+    routing::{get, post},
+};
+use shared::archive::MAX_ARCHIVE_SIZE;
+
+use crate::AppState;
+
+pub use export::build_archive;
+pub use import::restore_archive;
+
+/// Module for writing every plant along with everything that belongs to it into an archive
+mod export;
+/// Module for restoring an archive into this instance
+mod import;
+
+pub fn rout_archive() -> Router<AppState> {
+    Router::new()
+        .route("/export", get(export::export_archive))
+        // Archives carry every photo so they are far bigger than any other request
+        .route(
+            "/import",
+            post(import::import_archive).layer(DefaultBodyLimit::max(MAX_ARCHIVE_SIZE)),
+        )
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// The archive can be read but its contents can't be restored
+    Invalid(String),
+    Zip(zip::result::ZipError),
+    Json(serde_json::Error),
+    Image(image::ImageError),
+    Database(sqlx::Error),
+    Io(std::io::Error),
+}
+
+impl ArchiveError {
+    /// Problems with the archive itself are the fault of whoever sent it, the rest are problems with this instance
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ArchiveError::Invalid(_)
+            | ArchiveError::Zip(_)
+            | ArchiveError::Json(_)
+            | ArchiveError::Image(_) => StatusCode::BAD_REQUEST,
+            ArchiveError::Database(_) | ArchiveError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::Invalid(problem) => write!(f, "{}", problem),
+            ArchiveError::Zip(err) => write!(f, "Archive is not a readable zip file: {}", err),
+            ArchiveError::Json(err) => write!(f, "Archive data is malformed: {}", err),
+            ArchiveError::Image(err) => write!(f, "Archive photo can't be read: {}", err),
+            ArchiveError::Database(err) => write!(f, "{}", err),
+            ArchiveError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<zip::result::ZipError> for ArchiveError {
+    fn from(err: zip::result::ZipError) -> Self {
+        ArchiveError::Zip(err)
+    }
+}
+
+impl From<serde_json::Error> for ArchiveError {
+    fn from(err: serde_json::Error) -> Self {
+        ArchiveError::Json(err)
+    }
+}
+
+impl From<image::ImageError> for ArchiveError {
+    fn from(err: image::ImageError) -> Self {
+        ArchiveError::Image(err)
+    }
+}
+
+impl From<sqlx::Error> for ArchiveError {
+    fn from(err: sqlx::Error) -> Self {
+        ArchiveError::Database(err)
+    }
+}
+
+impl From<std::io::Error> for ArchiveError {
+    fn from(err: std::io::Error) -> Self {
+        ArchiveError::Io(err)
+    }
+}
@@ -0,0 +1,59 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Duration;
+use shared::calendar::calendar_http::{GetCalendar, GetCalendarResponse};
+use sqlx::PgPool;
+
+use crate::app::calendar::schedules::{get_care_schedules, get_events_between};
+
+/// The longest span of events that can be requested at once, enough for a month view with the surrounding weeks
+static MAX_SPAN_DAYS: i64 = 62;
+
+/// Returns the events logged in the span along with every care schedule
+pub async fn get_calendar(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetCalendar>,
+) -> Response {
+    if request.end <= request.start
+        || request.end.signed_duration_since(request.start) > Duration::days(MAX_SPAN_DAYS)
+    {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!(
+                "The calendar span must end after it starts and be at most {} days long",
+                MAX_SPAN_DAYS
+            )))
+            .unwrap();
+    }
+
+    let events = match get_events_between(request.start, request.end, pool.clone()).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let schedules = match get_care_schedules(pool.clone()).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let serialize = match serde_json::to_string(&GetCalendarResponse { events, schedules }) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,155 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::Response,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::Deserialize;
+use shared::{calendar::CareSchedule, events::events_http::ActivityItem};
+use sqlx::PgPool;
+
+use crate::app::calendar::schedules::{get_care_schedules, get_events_between};
+
+/// How many days of past events are included in the feed
+static HISTORY_DAYS: i64 = 90;
+
+/// Longest a line in an iCalendar file can be before it has to be folded onto the next line
+static MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Deserialize)]
+pub struct CalendarToken {
+    pub token: Option<String>,
+}
+
+/// iCalendar feed of recent events and every care schedule that calendar apps can subscribe to.
+///
+/// Only served when the `CALENDAR_TOKEN` environment variable is set, and the same token must be passed as the `token` query parameter
+pub async fn calendar_ics(
+    Query(query): Query<CalendarToken>,
+    State(pool): State<PgPool>,
+) -> Response {
+    let Ok(expected_token) = std::env::var("CALENDAR_TOKEN") else {
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from(
+                "The calendar feed is turned off, set CALENDAR_TOKEN to turn it on".to_string(),
+            ))
+            .unwrap();
+    };
+    if expected_token.is_empty() || query.token.as_deref() != Some(expected_token.as_str()) {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("Calendar token is missing or wrong".to_string()))
+            .unwrap();
+    }
+
+    let now = Utc::now().naive_utc();
+    let events = match get_events_between(
+        now - Duration::days(HISTORY_DAYS),
+        now + Duration::days(1),
+        pool.clone(),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let schedules = match get_care_schedules(pool.clone()).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .body(Body::from(to_ics(&events, &schedules, now)))
+        .unwrap()
+}
+
+/// Writes the events and schedules as an iCalendar file. Schedules repeat with a daily RRULE at their interval
+fn to_ics(events: &[ActivityItem], schedules: &[CareSchedule], now: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//House of Eden//Plant Care//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:House of Eden".to_string(),
+    ];
+
+    for item in events {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@house-of-eden", item.event.id),
+            format!("DTSTAMP:{}", ics_date_time(now)),
+            format!("DTSTART:{}", ics_date_time(item.event.event_date)),
+            format!("SUMMARY:{}", escape_text(&item.summary())),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+
+    // Care is due on a day rather than at a time so schedules are all day events
+    for schedule in schedules {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:schedule-{}-{}@house-of-eden",
+                schedule.plant_id, schedule.event_type_id
+            ),
+            format!("DTSTAMP:{}", ics_date_time(now)),
+            format!("DTSTART;VALUE=DATE:{}", schedule.next_due.format("%Y%m%d")),
+            format!("RRULE:FREQ=DAILY;INTERVAL={}", schedule.interval_days),
+            format!("SUMMARY:{}", escape_text(&schedule.summary())),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn ics_date_time(date: NaiveDateTime) -> String {
+    date.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes the characters that have a meaning in iCalendar text values. Every kind of line break is written as an escaped newline
+fn escape_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 octets, continuation lines start with a space. Never splits a character in half
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_octets = 0;
+    for character in line.chars() {
+        if line_octets + character.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the length of the continuation line
+            line_octets = 1;
+        }
+        folded.push(character);
+        line_octets += character.len_utf8();
+    }
+    folded
+}
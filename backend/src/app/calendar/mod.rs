@@ -0,0 +1,13 @@
+use axum::{Router, routing::post};
+
+use crate::AppState;
+
+pub use ics::calendar_ics;
+
+mod get_calendar;
+mod ics;
+mod schedules;
+
+pub fn rout_calendar() -> Router<AppState> {
+    Router::new().route("/events", post(get_calendar::get_calendar))
+}
@@ -0,0 +1,111 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use shared::{
+    calendar::CareSchedule,
+    events::{PLANT_NAME_EVENT_ID, WATERED_EVENT_ID, events_http::ActivityItem},
+    plant::PlantState,
+};
+use sqlx::{Pool, Postgres, prelude::FromRow};
+use uuid::{Uuid, uuid};
+
+use crate::app::events::ActivityRow;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ScheduleRow {
+    pub plant_id: Uuid,
+    pub plant_name: Option<String>,
+    pub state_name: Option<String>,
+    pub date_created: NaiveDateTime,
+    pub last_watered: Option<NaiveDateTime>,
+    pub interval_days: i32,
+    pub event_type_name: String,
+}
+
+impl ScheduleRow {
+    /// Retired plants aren't cared for anymore so they have no schedule
+    pub fn into_care_schedule(self) -> Option<CareSchedule> {
+        let retired = self
+            .state_name
+            .as_ref()
+            .and_then(|name| PlantState::from_name(name, String::new()))
+            .map(|state| state.is_retired())
+            .unwrap_or(false);
+        if retired {
+            return None;
+        }
+        let interval_days = self.interval_days.max(1) as i64;
+        Some(CareSchedule {
+            plant_id: self.plant_id,
+            plant_name: self.plant_name,
+            event_type_id: uuid!(WATERED_EVENT_ID),
+            event_type_name: self.event_type_name,
+            interval_days,
+            next_due: self.last_watered.unwrap_or(self.date_created)
+                + Duration::days(interval_days),
+        })
+    }
+}
+
+/// Gets the watering schedule of every plant in the users care whose species says how often it should be watered.
+/// Plants are due once the longest interval their species recommends has gone by
+pub async fn get_care_schedules(pool: Pool<Postgres>) -> Result<Vec<CareSchedule>, sqlx::Error> {
+    let rows: Vec<ScheduleRow> = sqlx::query_as(
+        r#"SELECT plants.id AS plant_id, plants.date_created,
+            (SELECT data->>'String' FROM events
+                WHERE events.plant_id = plants.id AND event_type_id = $1
+                ORDER BY event_date DESC LIMIT 1) AS plant_name,
+            plant_current_state(plants.id) AS state_name,
+            (SELECT max(event_date) FROM events
+                WHERE events.plant_id = plants.id AND event_type_id = $2) AS last_watered,
+            (species.care -> 'watering_interval' ->> 'max_days')::int AS interval_days,
+            (SELECT name FROM event_types WHERE id = $2) AS event_type_name
+        FROM plants
+        JOIN species ON species.id = plants.species_id
+        WHERE species.care -> 'watering_interval' ->> 'max_days' IS NOT NULL
+        ORDER BY plants.id"#,
+    )
+    .bind(uuid!(PLANT_NAME_EVENT_ID))
+    .bind(uuid!(WATERED_EVENT_ID))
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.into_care_schedule())
+        .collect())
+}
+
+/// Gets every event logged between the two dates across every plant, oldest first
+pub async fn get_events_between(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    pool: Pool<Postgres>,
+) -> Result<Vec<ActivityItem>, sqlx::Error> {
+    let rows: Vec<ActivityRow> = sqlx::query_as(
+        r#"WITH activity AS (
+            SELECT id, event_type_id, plant_id, data::jsonb AS data, event_date FROM events
+            UNION ALL
+            SELECT id, event_type_id, plant_id, data::jsonb, event_date FROM events_unique
+        )
+        SELECT activity.id, activity.event_type_id, activity.plant_id, activity.data, activity.event_date,
+            event_types.name AS event_type_name,
+            (SELECT data->>'String' FROM events
+                WHERE events.plant_id = activity.plant_id AND event_type_id = $1
+                ORDER BY event_date DESC LIMIT 1) AS plant_name
+        FROM activity
+        JOIN event_types ON event_types.id = activity.event_type_id
+        JOIN plants ON plants.id = activity.plant_id
+        WHERE activity.event_date >= $2 AND activity.event_date < $3
+        ORDER BY activity.event_date ASC, activity.id ASC"#,
+    )
+    .bind(uuid!(PLANT_NAME_EVENT_ID))
+    .bind(start)
+    .bind(end)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| row.into_activity_item())
+        .collect())
+}
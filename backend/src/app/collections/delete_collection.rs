@@ -0,0 +1,56 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use shared::DirtyCache;
+use sqlx::PgPool;
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+/// Deletes a saved collection. The plants in it are left untouched
+pub async fn delete_collection(
+    Path(collection_id): Path<String>,
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+) -> Response {
+    let collection_id = match Uuid::parse_str(&collection_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let result = match sqlx::query("DELETE FROM collections WHERE id = $1")
+        .bind(collection_id)
+        .execute(&pool)
+        .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if result.rows_affected() == 0 {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Collection sent does not exist".to_string()))
+            .unwrap();
+    }
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Collection(collection_id),
+        })
+        .await;
+
+    Response::new(Body::empty())
+}
@@ -0,0 +1,73 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::collections::Collection;
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct CollectionRow {
+    pub id: Uuid,
+    pub name: String,
+    pub filter: String,
+    pub date_created: NaiveDateTime,
+}
+
+impl CollectionRow {
+    pub fn into_collection(self) -> Collection {
+        Collection {
+            id: self.id,
+            name: self.name,
+            filter: self.filter,
+            date_created: self.date_created,
+        }
+    }
+}
+
+/// Gets every saved collection
+pub async fn get_collections(State(pool): State<PgPool>) -> Response {
+    let collections: Vec<CollectionRow> = match sqlx::query_as(
+        r#"SELECT id, name, filter, date_created FROM collections ORDER BY name"#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let collections: Vec<Collection> = collections
+        .into_iter()
+        .map(|collection| collection.into_collection())
+        .collect();
+
+    let serialize = match serde_json::to_string(&collections) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets a single collection if it exists
+pub async fn get_collection(
+    collection_id: Uuid,
+    pool: Pool<Postgres>,
+) -> Result<Option<Collection>, sqlx::Error> {
+    let collection: Option<CollectionRow> =
+        sqlx::query_as(r#"SELECT id, name, filter, date_created FROM collections WHERE id = $1"#)
+            .bind(collection_id)
+            .fetch_optional(&pool)
+            .await?;
+
+    Ok(collection.map(|collection| collection.into_collection()))
+}
@@ -0,0 +1,19 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::AppState;
+
+pub use get_collections::get_collection;
+
+mod delete_collection;
+mod get_collections;
+mod new_collection;
+
+pub fn rout_collection() -> Router<AppState> {
+    Router::new()
+        .route("/new", post(new_collection::new_collection))
+        .route("/get-all", get(get_collections::get_collections))
+        .route("/delete/{id}", get(delete_collection::delete_collection))
+}
@@ -0,0 +1,85 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    collections::{
+        Collection, CollectionFilter, MAX_FILTER_LENGTH, collections_http::NewCollection,
+    },
+};
+use sqlx::PgPool;
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+/// Saves a new smart collection and returns it to the client. The filter must parse and be at most [`MAX_FILTER_LENGTH`] characters
+pub async fn new_collection(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(new_collection): axum::Json<NewCollection>,
+) -> Response {
+    if new_collection.name.trim().is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Collection name must not be empty".to_string()))
+            .unwrap();
+    }
+
+    if new_collection.filter.chars().count() > MAX_FILTER_LENGTH {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!(
+                "Filter can be at most {} characters",
+                MAX_FILTER_LENGTH
+            )))
+            .unwrap();
+    }
+
+    if let Err(problem) = CollectionFilter::parse(&new_collection.filter) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(problem))
+            .unwrap();
+    }
+
+    let collection = Collection {
+        id: Uuid::new_v4(),
+        name: new_collection.name.trim().to_string(),
+        filter: new_collection.filter.trim().to_string(),
+        date_created: Utc::now().naive_utc(),
+    };
+
+    let _result = match sqlx::query(
+        "INSERT INTO collections(id, name, filter, date_created) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(collection.id)
+    .bind(&collection.name)
+    .bind(&collection.filter)
+    .bind(collection.date_created)
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Collection(collection.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&collection) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::{NaiveDateTime, Utc};
+use shared::{
+    DirtyCache,
+    csv_import::csv_import_http::{CsvImport, CsvImportResult, CsvRowProblem},
+    events::{EventData, PLANT_NAME_EVENT_ID, PLANT_STATE_ID, events_http::NewEvent},
+    plant::StateTransition,
+};
+use sqlx::{Acquire, PgConnection, PgPool, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::{Uuid, uuid};
+
+use crate::app::{
+    csv_import::plan::{PlantTarget, plan_import},
+    events::{check_event, insert_query},
+};
+
+/// The plants and dates of the logged events of each event type
+type LoggedEvents = HashMap<Uuid, Vec<(Uuid, NaiveDateTime)>>;
+
+/// Imports a CSV. Plants that don't exist yet are created and every row is checked the same way a new event is.
+///
+/// Rows are logged oldest first so each one is checked against the rows logged before it. Rows that fail are reported and skipped.
+/// The whole import is one transaction so it is either fully logged or not at all, and a new plant is only created once one of its
+/// rows passes its checks
+pub async fn commit_csv_import(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(request): axum::Json<CsvImport>,
+) -> Response {
+    let plan = match plan_import(&request, pool.clone()).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let mut result = CsvImportResult {
+        plants_created: vec![],
+        events_logged: 0,
+        problems: plan.preview.problems,
+    };
+
+    let changed: Result<(Vec<Uuid>, LoggedEvents), sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
+
+        // Existing plants are locked the same way a new event locks its plant, so another event can't change their state between
+        // the checks of a row and its insert. They are locked in order so two imports over the same plants can't deadlock
+        let existing_plants: Vec<Uuid> = plan
+            .events
+            .iter()
+            .filter_map(|event| match &event.plant {
+                PlantTarget::Existing(plant_id) => Some(*plant_id),
+                PlantTarget::New(_) => None,
+            })
+            .collect();
+        sqlx::query("SELECT id FROM plants WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+            .bind(&existing_plants)
+            .execute(&mut *transaction)
+            .await?;
+
+        let mut new_plants: HashMap<String, Uuid> = HashMap::new();
+        let mut changed_plants: HashSet<Uuid> = HashSet::new();
+        // Every logged event by its event type, so open timelines and calendars of the plants refresh
+        let mut logged_events: LoggedEvents = HashMap::new();
+
+        for event in plan.events {
+            let (plant_id, new_plant_name) = match event.plant {
+                PlantTarget::Existing(plant_id) => (plant_id, None),
+                PlantTarget::New(name) => match new_plants.get(&name) {
+                    Some(plant_id) => (*plant_id, None),
+                    None => (Uuid::new_v4(), Some(name)),
+                },
+            };
+            let new_event = NewEvent {
+                event_type: event.event_type.id,
+                plant_id,
+                event_data: event.event_data,
+                event_date: event.event_date,
+            };
+
+            // A new plant is created in a savepoint so it is rolled back along with its first row if that row fails. Rows are
+            // oldest first so the plant is created as of its oldest row that could be logged
+            let mut savepoint = transaction.begin().await?;
+            if let Some(name) = &new_plant_name {
+                create_plant(plant_id, name, new_event.event_date, &mut savepoint).await?;
+            }
+            if let Some(problem) =
+                check_event(&new_event, &event.event_type.kind, &mut savepoint).await?
+            {
+                savepoint.rollback().await?;
+                result.problems.push(CsvRowProblem {
+                    row: event.row,
+                    problem,
+                });
+                continue;
+            }
+
+            sqlx::query(insert_query(event.event_type.is_unique))
+                .bind(Uuid::new_v4())
+                .bind(new_event.event_type)
+                .bind(new_event.plant_id)
+                .bind(Json(new_event.event_data))
+                .bind(new_event.event_date)
+                .execute(&mut *savepoint)
+                .await?;
+            savepoint.commit().await?;
+
+            if let Some(name) = new_plant_name {
+                new_plants.insert(name, plant_id);
+                result.plants_created.push(plant_id);
+            }
+            result.events_logged += 1;
+            changed_plants.insert(plant_id);
+            logged_events
+                .entry(new_event.event_type)
+                .or_default()
+                .push((plant_id, new_event.event_date));
+        }
+
+        // The plant demographics are built from their latest events so let clients know they changed
+        let changed_plants: Vec<Uuid> = changed_plants.into_iter().collect();
+        sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = ANY($1)")
+            .bind(&changed_plants)
+            .bind(Utc::now().naive_utc())
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok((changed_plants, logged_events))
+    }
+    .await;
+
+    let (changed_plants, logged_events) = match changed {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    for plant_id in changed_plants {
+        let _ = dirt_cache
+            .send(DirtyCache {
+                cache: shared::CacheType::Plant(plant_id),
+            })
+            .await;
+    }
+    for (event_type, events) in logged_events {
+        let _ = dirt_cache
+            .send(DirtyCache {
+                cache: shared::CacheType::Events(event_type, events),
+            })
+            .await;
+    }
+
+    result.problems.sort_by_key(|problem| problem.row);
+    let serialize = match serde_json::to_string(&result) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
+
+/// Creates a plant with its name and starting state the same way a new plant is, dated to when the plant was created
+async fn create_plant(
+    plant_id: Uuid,
+    name: &str,
+    date_created: NaiveDateTime,
+    connection: &mut PgConnection,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO plants(id, date_created, event_modified) VALUES ($1, $2, $3)")
+        .bind(plant_id)
+        .bind(date_created)
+        .bind(Utc::now().naive_utc())
+        .execute(&mut *connection)
+        .await?;
+
+    for (event_type, event_data) in [
+        (
+            uuid!(PLANT_NAME_EVENT_ID),
+            EventData::String(name.to_string()),
+        ),
+        (
+            uuid!(PLANT_STATE_ID),
+            EventData::Lifecycle(StateTransition::initial()),
+        ),
+    ] {
+        sqlx::query(insert_query(false))
+            .bind(Uuid::new_v4())
+            .bind(event_type)
+            .bind(plant_id)
+            .bind(Json(event_data))
+            .bind(date_created)
+            .execute(&mut *connection)
+            .await?;
+    }
+
+    Ok(())
+}
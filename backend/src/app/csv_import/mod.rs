@@ -0,0 +1,15 @@
+use axum::{Router, routing::post};
+
+use crate::AppState;
+
+/// Module for saving a planned CSV import
+mod commit;
+/// Module for reading a CSV and matching its rows to plants and event types
+mod plan;
+mod preview;
+
+pub fn rout_csv_import() -> Router<AppState> {
+    Router::new()
+        .route("/preview", post(preview::preview_csv_import))
+        .route("/commit", post(commit::commit_csv_import))
+}
@@ -0,0 +1,255 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{NaiveDateTime, Utc};
+use shared::{
+    csv_import::{
+        CsvColumns, CsvTable,
+        csv_import_http::{CsvImport, CsvPreview, CsvRowProblem, MAX_CSV_ROWS, ProbableDuplicate},
+        parse_date,
+    },
+    events::{EventData, EventDataKind, EventType},
+    plant::plant_http::PlantCandidate,
+};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::app::{
+    events::{GetDatabaseEventTypes, check_event_data, get_event_types_custom},
+    plants::lookup::{FUZZY_THRESHOLD, PlantNameRow, get_plant_names, similarity},
+};
+
+/// The plant a row of the CSV logs to
+#[derive(Clone, PartialEq, Debug)]
+pub enum PlantTarget {
+    Existing(Uuid),
+    /// A plant that will be created, by the name first written in the CSV
+    New(String),
+}
+
+/// A row of the CSV that passed every check that doesn't depend on the events before it
+pub struct PlannedEvent {
+    pub row: usize,
+    pub plant: PlantTarget,
+    pub event_type: EventType,
+    pub event_data: EventData,
+    pub event_date: NaiveDateTime,
+}
+
+pub struct ImportPlan {
+    pub preview: CsvPreview,
+    /// Oldest first so events that depend on earlier ones, like period ends, come after them
+    pub events: Vec<PlannedEvent>,
+}
+
+/// Reads the CSV and works out what importing it would do without changing anything.
+///
+/// Returns the problem with the request when the CSV itself can't be used
+pub async fn plan_import(
+    request: &CsvImport,
+    pool: Pool<Postgres>,
+) -> Result<Result<ImportPlan, String>, sqlx::Error> {
+    let table = match CsvTable::parse(&request.csv) {
+        Ok(result) => result,
+        Err(problem) => return Ok(Err(problem)),
+    };
+    if table.rows.len() > MAX_CSV_ROWS {
+        return Ok(Err(format!(
+            "A CSV import can have at most {} rows",
+            MAX_CSV_ROWS
+        )));
+    }
+
+    let (plant_column, event_type_column, date_column, value_column) =
+        match find_columns(&table, &request.columns) {
+            Ok(result) => result,
+            Err(problem) => return Ok(Err(problem)),
+        };
+
+    let event_types =
+        get_event_types_custom(GetDatabaseEventTypes::All(Utc::now()), pool.clone()).await?;
+    let plant_names = get_plant_names(None, pool).await?;
+
+    let mut preview = CsvPreview {
+        rows: table.rows.len(),
+        events_ready: 0,
+        matched_plants: vec![],
+        new_plants: vec![],
+        probable_duplicates: vec![],
+        unknown_event_types: vec![],
+        problems: vec![],
+    };
+    let mut unknown_event_types: BTreeSet<String> = BTreeSet::new();
+    // Plant names are matched once each, keyed by the name ignoring case and surrounding space
+    let mut plants: HashMap<String, Result<PlantTarget, String>> = HashMap::new();
+    let mut events = vec![];
+
+    for (index, fields) in table.rows.iter().enumerate() {
+        let row = table.row_number(index);
+        let field = |column: usize| fields.get(column).map(|field| field.trim()).unwrap_or("");
+
+        let plant_name = field(plant_column);
+        if plant_name.is_empty() {
+            preview.problems.push(CsvRowProblem {
+                row,
+                problem: "Plant name is empty".to_string(),
+            });
+            continue;
+        }
+        let plant = plants
+            .entry(plant_name.to_lowercase())
+            .or_insert_with(|| {
+                match_plant(
+                    plant_name,
+                    &request.plant_overrides,
+                    &plant_names,
+                    &mut preview,
+                )
+            })
+            .clone();
+        let plant = match plant {
+            Ok(plant) => plant,
+            Err(problem) => {
+                preview.problems.push(CsvRowProblem { row, problem });
+                continue;
+            }
+        };
+
+        let event_type_name = field(event_type_column);
+        let Some(event_type) = event_types
+            .iter()
+            .find(|event_type| event_type.name.eq_ignore_ascii_case(event_type_name))
+        else {
+            unknown_event_types.insert(event_type_name.to_string());
+            preview.problems.push(CsvRowProblem {
+                row,
+                problem: format!("Unknown event type {}", event_type_name),
+            });
+            continue;
+        };
+
+        let Some(event_date) = parse_date(field(date_column), request.date_format.as_deref())
+        else {
+            preview.problems.push(CsvRowProblem {
+                row,
+                problem: format!("Can't read the date {}", field(date_column)),
+            });
+            continue;
+        };
+
+        let value = value_column.map(field).unwrap_or("");
+        let event_data = match parse_value(&event_type.kind, value) {
+            Ok(result) => result,
+            Err(problem) => {
+                preview.problems.push(CsvRowProblem { row, problem });
+                continue;
+            }
+        };
+
+        preview.events_ready += 1;
+        events.push(PlannedEvent {
+            row,
+            plant,
+            event_type: event_type.clone(),
+            event_data,
+            event_date,
+        });
+    }
+
+    preview.unknown_event_types = unknown_event_types.into_iter().collect();
+    events.sort_by_key(|event| event.event_date);
+    Ok(Ok(ImportPlan { preview, events }))
+}
+
+/// The position of the plant name, event type, date and value columns in the CSV
+fn find_columns(
+    table: &CsvTable,
+    columns: &CsvColumns,
+) -> Result<(usize, usize, usize, Option<usize>), String> {
+    let column = |name: &str| {
+        table
+            .column(name)
+            .ok_or(format!("Column {} is not in the CSV header", name))
+    };
+    Ok((
+        column(&columns.plant_name)?,
+        column(&columns.event_type)?,
+        column(&columns.date)?,
+        columns.value.as_deref().map(column).transpose()?,
+    ))
+}
+
+/// Reads the value of a row as data of the event type, with the same checks as a new event
+fn parse_value(kind: &EventDataKind, value: &str) -> Result<EventData, String> {
+    let event_data = kind.parse_value(value)?;
+    match check_event_data(&event_data, kind) {
+        Some(problem) => Err(problem),
+        None => Ok(event_data),
+    }
+}
+
+/// Finds the plant a name from the CSV belongs to. Overrides win, then a single exact match, otherwise a new plant is made.
+/// Close but not exact names are added to the preview as probable duplicates
+fn match_plant(
+    name: &str,
+    overrides: &HashMap<String, Uuid>,
+    plant_names: &[PlantNameRow],
+    preview: &mut CsvPreview,
+) -> Result<PlantTarget, String> {
+    let lowercase = name.to_lowercase();
+    let plant_override = overrides
+        .iter()
+        .find(|(override_name, _)| override_name.trim().to_lowercase() == lowercase);
+    if let Some((_, plant_id)) = plant_override {
+        if !plant_names.iter().any(|plant| plant.plant_id == *plant_id) {
+            return Err(format!("Plant picked for {} does not exist", name));
+        }
+        preview.matched_plants.push(name.to_string());
+        return Ok(PlantTarget::Existing(*plant_id));
+    }
+
+    let mut candidates: Vec<PlantCandidate> = plant_names
+        .iter()
+        .filter_map(|plant| {
+            let score = similarity(&lowercase, &plant.name.as_ref()?.trim().to_lowercase());
+            Some(PlantCandidate {
+                plant_id: plant.plant_id,
+                name: plant.name.clone(),
+                score,
+            })
+        })
+        .filter(|candidate| candidate.score >= FUZZY_THRESHOLD)
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let exact = candidates
+        .iter()
+        .take_while(|candidate| candidate.score >= 1.0)
+        .count();
+    match exact {
+        1 => {
+            preview.matched_plants.push(name.to_string());
+            Ok(PlantTarget::Existing(candidates[0].plant_id))
+        }
+        0 => {
+            if !candidates.is_empty() {
+                preview.probable_duplicates.push(ProbableDuplicate {
+                    name: name.to_string(),
+                    candidates,
+                });
+            }
+            preview.new_plants.push(name.to_string());
+            Ok(PlantTarget::New(name.to_string()))
+        }
+        _ => {
+            candidates.truncate(exact);
+            preview.probable_duplicates.push(ProbableDuplicate {
+                name: name.to_string(),
+                candidates,
+            });
+            Err(format!(
+                "{} matches more than one plant, pick one with an override",
+                name
+            ))
+        }
+    }
+}
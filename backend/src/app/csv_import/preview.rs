@@ -0,0 +1,40 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use shared::csv_import::csv_import_http::CsvImport;
+use sqlx::PgPool;
+
+use crate::app::csv_import::plan::plan_import;
+
+/// Reports what importing a CSV would do without changing anything, including unknown event types,
+/// dates that can't be read and plant names that are probably already in the collection
+pub async fn preview_csv_import(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<CsvImport>,
+) -> Response {
+    let plan = match plan_import(&request, pool).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let serialize = match serde_json::to_string(&plan.preview) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,129 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::events::{
+    EventData, EventInstance, PLANT_NAME_EVENT_ID,
+    events_http::{ActivityCursor, ActivityItem, ExportedEvent, GetActivity, GetActivityResponse},
+};
+use sqlx::{PgPool, prelude::FromRow, types::Json};
+use uuid::{Uuid, uuid};
+
+/// The most events that will be returned in a single page
+static MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ActivityRow {
+    pub id: Uuid,
+    pub event_type_id: Uuid,
+    pub plant_id: Uuid,
+    pub data: Json<EventData>,
+    pub event_date: NaiveDateTime,
+    pub event_type_name: String,
+    pub plant_name: Option<String>,
+}
+
+impl ActivityRow {
+    pub fn into_activity_item(self) -> ActivityItem {
+        ActivityItem {
+            event: EventInstance {
+                id: self.id,
+                event_type_id: self.event_type_id,
+                plant_id: self.plant_id,
+                data: self.data.0,
+                event_date: self.event_date,
+            },
+            event_type_name: self.event_type_name,
+            plant_name: self.plant_name,
+            actor: None,
+        }
+    }
+
+    pub fn into_exported_event(self) -> ExportedEvent {
+        ExportedEvent {
+            id: self.id,
+            event_date: self.event_date,
+            plant_id: self.plant_id,
+            plant_name: self.plant_name,
+            event_type_id: self.event_type_id,
+            event_type_name: self.event_type_name,
+            value: self.data.0.value_text(),
+            data: self.data.0,
+        }
+    }
+}
+
+/// Returns a page of the latest events across every plant, newest first
+pub async fn get_activity(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetActivity>,
+) -> Response {
+    let limit = request.limit.clamp(1, MAX_PAGE_SIZE);
+    let (cursor_date, cursor_id) = match &request.cursor {
+        Some(cursor) => (Some(cursor.event_date), Some(cursor.id)),
+        None => (None, None),
+    };
+
+    // Events of deleted plants are left out since there is nothing left to link them to
+    let rows: Vec<ActivityRow> = match sqlx::query_as(
+        r#"WITH activity AS (
+            SELECT id, event_type_id, plant_id, data::jsonb AS data, event_date FROM events
+            UNION ALL
+            SELECT id, event_type_id, plant_id, data::jsonb, event_date FROM events_unique
+        )
+        SELECT activity.id, activity.event_type_id, activity.plant_id, activity.data, activity.event_date,
+            event_types.name AS event_type_name,
+            (SELECT data->>'String' FROM events
+                WHERE events.plant_id = activity.plant_id AND event_type_id = $1
+                ORDER BY event_date DESC LIMIT 1) AS plant_name
+        FROM activity
+        JOIN event_types ON event_types.id = activity.event_type_id
+        JOIN plants ON plants.id = activity.plant_id
+        WHERE ($2::timestamp IS NULL OR (activity.event_date, activity.id) < ($2, $3::uuid))
+        ORDER BY activity.event_date DESC, activity.id DESC
+        LIMIT $4"#,
+    )
+    .bind(uuid!(PLANT_NAME_EVENT_ID))
+    .bind(cursor_date)
+    .bind(cursor_id)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // A short page means there is nothing left to request
+    let next_cursor = match rows.len() as i64 == limit {
+        true => rows.last().map(|row| ActivityCursor {
+            event_date: row.event_date,
+            id: row.id,
+        }),
+        false => None,
+    };
+
+    let response = GetActivityResponse {
+        items: rows
+            .into_iter()
+            .map(|row| row.into_activity_item())
+            .collect(),
+        next_cursor,
+    };
+
+    let serialize = match serde_json::to_string(&response) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
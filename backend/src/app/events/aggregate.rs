@@ -0,0 +1,115 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::events::{
+    EventDataKind,
+    events_http::{AggregateBucket, AggregateEvents, AggregatePoint},
+};
+use sqlx::{PgPool, prelude::FromRow};
+
+use crate::app::events::get_event_types::get_event_type_single;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct AggregatePointRow {
+    bucket_start: NaiveDateTime,
+    min: f64,
+    max: f64,
+    avg: f64,
+    count: i64,
+}
+
+impl AggregatePointRow {
+    fn into_point(self) -> AggregatePoint {
+        AggregatePoint {
+            bucket_start: self.bucket_start,
+            min: self.min,
+            max: self.max,
+            avg: self.avg,
+            count: self.count,
+        }
+    }
+}
+
+/// Summarises the measurements of a numeric event type per day, week or month
+pub async fn aggregate_events(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<AggregateEvents>,
+) -> Response {
+    let event_type = match get_event_type_single(request.event_type, pool.clone()).await {
+        Ok(result) => result,
+        Err(sqlx::Error::RowNotFound) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("Event type sent does not exist".to_string()))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if !matches!(event_type.kind, EventDataKind::Number(_)) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Only numeric event types can be aggregated".to_string(),
+            ))
+            .unwrap();
+    }
+
+    let bucket = match request.bucket {
+        AggregateBucket::Day => "day",
+        AggregateBucket::Week => "week",
+        AggregateBucket::Month => "month",
+    };
+    let (start, end) = request.span.unzip();
+
+    let query_string = format!(
+        r#"SELECT date_trunc($3, event_date) AS bucket_start,
+        MIN((data->>'Number')::float8) AS min,
+        MAX((data->>'Number')::float8) AS max,
+        AVG((data->>'Number')::float8) AS avg,
+        COUNT(*) AS count
+        FROM {}
+        WHERE plant_id = $1 AND event_type_id = $2
+        AND ($4::timestamp IS NULL OR event_date >= $4)
+        AND ($5::timestamp IS NULL OR event_date <= $5)
+        GROUP BY bucket_start
+        ORDER BY bucket_start"#,
+        event_type.table_name()
+    );
+
+    let points: Vec<AggregatePointRow> = match sqlx::query_as(&query_string)
+        .bind(request.plant_id)
+        .bind(request.event_type)
+        .bind(bucket)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&pool)
+        .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let points: Vec<AggregatePoint> = points.into_iter().map(|point| point.into_point()).collect();
+
+    let serialize = match serde_json::to_string(&points) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
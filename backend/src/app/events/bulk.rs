@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::{NaiveDateTime, Utc};
+use shared::{
+    DirtyCache,
+    events::{
+        EventInstance, PROPAGATED_EVENT_TYPE_ID,
+        events_http::{BulkEventResult, MAX_BULK_PLANTS, NewBulkEvent, NewEvent},
+    },
+};
+use sqlx::{PgPool, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::{Uuid, uuid};
+
+use crate::app::events::{
+    EventInstanceRow,
+    new_event::{EventTypesRow, check_event, insert_query},
+};
+
+/// Logs the same event type for many plants in one transaction.
+///
+/// Every plant is checked the same way a single event is. Plants that fail are reported in the response and skipped, the rest are logged together
+pub async fn new_bulk_event(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(bulk_event): axum::Json<NewBulkEvent>,
+) -> Response {
+    if bulk_event.plants.is_empty() || bulk_event.plants.len() > MAX_BULK_PLANTS {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!(
+                "A bulk event must be logged for between 1 and {} plants",
+                MAX_BULK_PLANTS
+            )))
+            .unwrap();
+    }
+
+    let event_type: EventTypesRow =
+        match sqlx::query_as(r#"SELECT id, event_type, is_unique FROM event_types where id = $1"#)
+            .bind(bulk_event.event_type)
+            .fetch_one(&pool)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(err.to_string()))
+                    .unwrap();
+            }
+        };
+
+    // Propagated events set the parent of the propagated plant, which can only have one
+    if event_type.id == uuid!(PROPAGATED_EVENT_TYPE_ID) {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Propagated events can only be logged for one plant at a time".to_string(),
+            ))
+            .unwrap();
+    }
+
+    let plant_ids: Vec<Uuid> = bulk_event
+        .plants
+        .iter()
+        .map(|plant| plant.plant_id)
+        .collect();
+    let result: Result<Vec<BulkEventResult>, sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
+
+        // Locking the plants keeps another event from changing their state between their checks and their insert. They are
+        // locked in order so two bulk events over the same plants can't deadlock
+        let existing_plants: Vec<Uuid> = sqlx::query_scalar(
+            r#"SELECT id FROM plants WHERE id = ANY($1) ORDER BY id FOR UPDATE"#,
+        )
+        .bind(&plant_ids)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        // Every plant is checked on its own so one bad plant doesn't stop the rest
+        let mut seen_plants = HashSet::new();
+        let mut results = Vec::new();
+        let mut logged_plants = Vec::new();
+        for plant in bulk_event.plants {
+            let event_data = plant.event_data.or(bulk_event.event_data.clone());
+            let checked_event = if !seen_plants.insert(plant.plant_id) {
+                Err("Plant is listed more than once".to_string())
+            } else if !existing_plants.contains(&plant.plant_id) {
+                Err("Plant does not exist".to_string())
+            } else {
+                event_data
+                    .map(|event_data| NewEvent {
+                        event_type: bulk_event.event_type,
+                        plant_id: plant.plant_id,
+                        event_data,
+                        event_date: bulk_event.event_date,
+                    })
+                    .ok_or("No event data was sent for the plant".to_string())
+            };
+            let new_event = match checked_event {
+                Ok(new_event) => new_event,
+                Err(problem) => {
+                    results.push(BulkEventResult {
+                        plant_id: plant.plant_id,
+                        result: Err(problem),
+                    });
+                    continue;
+                }
+            };
+            if let Some(problem) =
+                check_event(&new_event, &event_type.event_type.0, &mut transaction).await?
+            {
+                results.push(BulkEventResult {
+                    plant_id: plant.plant_id,
+                    result: Err(problem),
+                });
+                continue;
+            }
+
+            let row: EventInstanceRow = sqlx::query_as(insert_query(event_type.is_unique))
+                .bind(Uuid::new_v4())
+                .bind(new_event.event_type)
+                .bind(new_event.plant_id)
+                .bind(Json(new_event.event_data))
+                .bind(new_event.event_date)
+                .fetch_one(&mut *transaction)
+                .await?;
+
+            logged_plants.push(plant.plant_id);
+            results.push(BulkEventResult {
+                plant_id: plant.plant_id,
+                result: Ok(EventInstance {
+                    id: row.id,
+                    event_type_id: row.event_type_id,
+                    plant_id: row.plant_id,
+                    data: row.data.0,
+                    event_date: row.event_date,
+                }),
+            });
+        }
+
+        // The plant demographics are built from their latest events so let clients know they changed
+        sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = ANY($1)")
+            .bind(&logged_plants)
+            .bind(Utc::now().naive_utc())
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(results)
+    }
+    .await;
+
+    let results = match result {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let logged_events: Vec<(Uuid, NaiveDateTime)> = results
+        .iter()
+        .filter_map(|result| match &result.result {
+            Ok(event) => Some((event.plant_id, event.event_date)),
+            Err(_) => None,
+        })
+        .collect();
+    if !logged_events.is_empty() {
+        let _ = dirt_cache
+            .send(DirtyCache {
+                cache: shared::CacheType::Events(event_type.id, logged_events),
+            })
+            .await;
+    }
+
+    let serialize = match serde_json::to_string(&results) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
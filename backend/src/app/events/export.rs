@@ -0,0 +1,112 @@
+use axum::{
+    BoxError,
+    body::Body,
+    extract::State,
+    http::{StatusCode, header},
+    response::Response,
+};
+use futures_util::StreamExt;
+use shared::events::{
+    PLANT_NAME_EVENT_ID,
+    events_http::{ExportEvents, ExportFormat, ExportedEvent, GetEventType},
+};
+use sqlx::PgPool;
+use uuid::uuid;
+
+use crate::app::events::ActivityRow;
+
+/// Exports events for analysis as CSV or NDJSON, oldest first.
+///
+/// Rows are streamed from the database as they are read so large histories are never held in memory. A database error part way
+/// through ends the response early since the headers have already been sent
+pub async fn export_events(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<ExportEvents>,
+) -> Response {
+    let (start, end) = match request.span {
+        GetEventType::Span(start, end) if start <= end => (Some(start), Some(end)),
+        GetEventType::Span(_, _) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "The start of the span must come before its end".to_string(),
+                ))
+                .unwrap();
+        }
+        GetEventType::All => (None, None),
+        GetEventType::LastNth(_, _) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "Events can only be exported by span or all at once".to_string(),
+                ))
+                .unwrap();
+        }
+    };
+
+    let format = request.format;
+    let stream = async_stream::stream! {
+        if format == ExportFormat::Csv {
+            yield Ok::<String, BoxError>(ExportedEvent::csv_header());
+        }
+
+        let mut rows = sqlx::query_as::<_, ActivityRow>(
+            r#"WITH all_events AS (
+                SELECT id, event_type_id, plant_id, data::jsonb AS data, event_date FROM events
+                UNION ALL
+                SELECT id, event_type_id, plant_id, data::jsonb, event_date FROM events_unique
+            )
+            SELECT all_events.id, all_events.event_type_id, all_events.plant_id, all_events.data, all_events.event_date,
+                event_types.name AS event_type_name, names.name AS plant_name
+            FROM all_events
+            JOIN event_types ON event_types.id = all_events.event_type_id
+            LEFT JOIN (
+                SELECT DISTINCT ON (plant_id) plant_id, data->>'String' AS name FROM events
+                WHERE event_type_id = $1
+                ORDER BY plant_id, event_date DESC
+            ) names ON names.plant_id = all_events.plant_id
+            WHERE (cardinality($2::uuid[]) = 0 OR all_events.plant_id = ANY($2))
+                AND (cardinality($3::uuid[]) = 0 OR all_events.event_type_id = ANY($3))
+                AND ($4::timestamp IS NULL OR all_events.event_date >= $4)
+                AND ($5::timestamp IS NULL OR all_events.event_date <= $5)
+            ORDER BY all_events.event_date, all_events.id"#,
+        )
+        .bind(uuid!(PLANT_NAME_EVENT_ID))
+        .bind(&request.plant_ids)
+        .bind(&request.event_types)
+        .bind(start)
+        .bind(end)
+        .fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            let event = match row {
+                Ok(row) => row.into_exported_event(),
+                Err(err) => {
+                    tracing::error!("Failed to export events: {}", err);
+                    yield Err(err.into());
+                    break;
+                }
+            };
+            yield match format {
+                ExportFormat::Csv => Ok(event.to_csv_row()),
+                ExportFormat::Ndjson => serde_json::to_string(&event)
+                    .map(|line| line + "\n")
+                    .map_err(|err| err.into()),
+            };
+        }
+    };
+
+    let (content_type, extension) = match format {
+        ExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+        ExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+    };
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"events.{}\"", extension),
+        )
+        .body(Body::from_stream(stream))
+        .unwrap()
+}
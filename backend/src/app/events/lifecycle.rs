@@ -0,0 +1,30 @@
+use shared::events::{EventData, events_http::NewEvent};
+use sqlx::{PgConnection, types::Json};
+
+/// Checks that a new lifecycle event starts from the plants current state and is an allowed transition. Returns a description of the problem if it isn't
+pub async fn check_transition(
+    new_event: &NewEvent,
+    connection: &mut PgConnection,
+) -> Result<Option<String>, sqlx::Error> {
+    let EventData::Lifecycle(transition) = &new_event.event_data else {
+        return Ok(None);
+    };
+
+    let previous: Option<Json<EventData>> = sqlx::query_scalar(
+        r#"SELECT data FROM (
+            SELECT data::jsonb AS data, event_date FROM events WHERE plant_id = $1 AND event_type_id = $2
+            UNION ALL
+            SELECT data::jsonb, event_date FROM events_unique WHERE plant_id = $1 AND event_type_id = $2
+        ) latest ORDER BY event_date DESC LIMIT 1"#,
+    )
+    .bind(new_event.plant_id)
+    .bind(new_event.event_type)
+    .fetch_optional(connection)
+    .await?;
+    let current = match previous.map(|data| data.0) {
+        Some(EventData::Lifecycle(previous)) => Some(previous.to),
+        _ => None,
+    };
+
+    Ok(transition.check(current.as_ref()))
+}
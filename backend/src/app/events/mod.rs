@@ -5,22 +5,34 @@ use axum::{
 
 use crate::AppState;
 
+pub use activity::ActivityRow;
 pub use get_event_types::{get_event_types_custom, GetDatabaseEventTypes};
-pub use get_events::get_last_event;
-pub use new_event::new_event;
+pub use get_events::{get_last_event, EventInstanceRow};
+pub use new_event::{check_event, check_event_data, insert_query, new_event};
 
+mod activity;
+mod aggregate;
+mod bulk;
+mod export;
 mod get_event_types;
 mod get_events;
+mod lifecycle;
 mod new_event;
 mod new_event_type;
+mod periods;
 
 pub fn rout_event() -> Router<AppState> {
     Router::new()
         .route("/new", post(new_event::new_event))
+        .route("/bulk", post(bulk::new_bulk_event))
         .route("/new-type", post(new_event_type::new_event_type))
         .route(
             "/get-types/{timestamp}",
             get(get_event_types::get_event_types),
         )
         .route("/get-events", post(get_events::get_events))
+        .route("/periods", post(periods::get_periods))
+        .route("/aggregate", post(aggregate::aggregate_events))
+        .route("/activity", post(activity::get_activity))
+        .route("/export", post(export::export_events))
 }
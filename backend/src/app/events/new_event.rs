@@ -1,19 +1,26 @@
 use axum::{body::Body, extract::State, http::StatusCode, response::Response};
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use shared::{
-    events::{events_http::NewEvent, EventData, EventDataKind, EventInstance},
     DirtyCache,
+    events::{
+        EventData, EventDataKind, EventInstance, PROPAGATED_EVENT_TYPE_ID, events_http::NewEvent,
+    },
 };
-use sqlx::{prelude::FromRow, types::Json, PgPool};
+use sqlx::{PgConnection, PgPool, prelude::FromRow, types::Json};
 use tokio::sync::mpsc::Sender;
-use uuid::Uuid;
+use uuid::{Uuid, uuid};
+
+use crate::app::{
+    events::{lifecycle::check_transition, periods::check_period},
+    locations::location_exists,
+};
 
 #[derive(Serialize, Deserialize, FromRow)]
 pub struct EventTypesRow {
-    event_type: Json<EventDataKind>,
-    is_unique: bool,
-    id: Uuid,
+    pub event_type: Json<EventDataKind>,
+    pub is_unique: bool,
+    pub id: Uuid,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, FromRow)]
@@ -46,34 +53,56 @@ pub async fn new_event(
             }
         };
 
-    if !new_event.event_data.equals_kind(event_type.event_type.0) {
-        return Response::builder()
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Body::from(
-                "Event Type sent does not match event type of event".to_string(),
-            ))
-            .unwrap();
-    };
-    
-    let query_string = match event_type.is_unique {
-        true => format!(
-            r#"INSERT INTO events_unique(id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (event_type_id, plant_id) DO UPDATE SET event_date = EXCLUDED.event_date, data = EXCLUDED.data RETURNING *"#
-        ),
-        false => format!(
-            r#"INSERT INTO events(id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5) RETURNING *"#
-        ),
-    };
+    // The event is checked and logged in one transaction so it is checked against every event logged before it
+    let result: Result<Result<EventInstanceRow, String>, sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
 
-    let result: EventInstanceRow = match sqlx::query_as(&query_string)
-        .bind(Uuid::new_v4())
-        .bind(new_event.event_type)
-        .bind(new_event.plant_id)
-        .bind(Json(new_event.event_data))
-        .bind(new_event.event_date)
-        .fetch_one(&pool)
-        .await
-    {
-        Ok(result) => result,
+        // Locking the plant keeps two events from both passing the checks against its current state, such as its lifecycle
+        let plant_exists: Option<Uuid> =
+            sqlx::query_scalar(r#"SELECT id FROM plants WHERE id = $1 FOR UPDATE"#)
+                .bind(new_event.plant_id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+        if plant_exists.is_none() {
+            return Ok(Err("Plant sent does not exist".to_string()));
+        }
+
+        if let Some(problem) =
+            check_event(&new_event, &event_type.event_type.0, &mut transaction).await?
+        {
+            return Ok(Err(problem));
+        }
+
+        let result: EventInstanceRow = sqlx::query_as(insert_query(event_type.is_unique))
+            .bind(Uuid::new_v4())
+            .bind(new_event.event_type)
+            .bind(new_event.plant_id)
+            .bind(Json(new_event.event_data.clone()))
+            .bind(new_event.event_date)
+            .fetch_one(&mut *transaction)
+            .await?;
+        record_propagation(&new_event, &mut transaction).await?;
+
+        // The plant demographic is built from its latest events so let clients know it changed
+        sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = $1")
+            .bind(new_event.plant_id)
+            .bind(Utc::now().naive_utc())
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(Ok(result))
+    }
+    .await;
+
+    let result = match result {
+        Ok(Ok(result)) => result,
+        Ok(Err(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
         Err(err) => {
             return Response::builder()
                 .status(StatusCode::INTERNAL_SERVER_ERROR)
@@ -95,6 +124,13 @@ pub async fn new_event(
             cache: shared::CacheType::Event(new_event.plant_id, event_type.id, result.event_date),
         })
         .await;
+    if let Some(child_id) = propagated_plant(&new_event) {
+        let _ = dirt_cache
+            .send(DirtyCache {
+                cache: shared::CacheType::Plant(child_id),
+            })
+            .await;
+    }
 
     let serialize = match serde_json::to_string(&new_event_instance) {
         Ok(result) => result,
@@ -110,3 +146,134 @@ pub async fn new_event(
         .body(Body::from(serialize))
         .unwrap();
 }
+
+/// The query that inserts an event, unique event types replace the previous event of the plant
+pub fn insert_query(is_unique: bool) -> &'static str {
+    match is_unique {
+        true => {
+            r#"INSERT INTO events_unique(id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (event_type_id, plant_id) DO UPDATE SET event_date = EXCLUDED.event_date, data = EXCLUDED.data RETURNING *"#
+        }
+        false => {
+            r#"INSERT INTO events(id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5) RETURNING *"#
+        }
+    }
+}
+
+/// Checks that the event data fits its event type on its own, without looking at any other events or plants
+pub fn check_event_data(event_data: &EventData, event_type: &EventDataKind) -> Option<String> {
+    if let Err(err) = event_data.check_options(event_type) {
+        return Some(err.to_string());
+    }
+
+    if !event_data.equals_kind(event_type.clone()) {
+        return Some("Event Type sent does not match event type of event".to_string());
+    };
+
+    if let (EventData::Number(value), EventDataKind::Number(format)) = (event_data, event_type) {
+        if let Some(problem) = format.check(*value) {
+            return Some(problem);
+        }
+    }
+
+    None
+}
+
+/// Checks an event can be logged against its event type. Returns the problem if it can't.
+///
+/// Run inside of a transaction the event is checked against the events written earlier in that transaction
+pub async fn check_event(
+    new_event: &NewEvent,
+    event_type: &EventDataKind,
+    connection: &mut PgConnection,
+) -> Result<Option<String>, sqlx::Error> {
+    if let Some(problem) = check_event_data(&new_event.event_data, event_type) {
+        return Ok(Some(problem));
+    }
+
+    if let Some(problem) = check_period(new_event, &mut *connection).await? {
+        return Ok(Some(problem));
+    }
+
+    if let Some(problem) = check_transition(new_event, &mut *connection).await? {
+        return Ok(Some(problem));
+    }
+
+    if let EventData::Plant(other_plant_id) = new_event.event_data {
+        let other_plant_exists: bool =
+            sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM plants WHERE id = $1)"#)
+                .bind(other_plant_id)
+                .fetch_one(&mut *connection)
+                .await?;
+        if !other_plant_exists || other_plant_id == new_event.plant_id {
+            return Ok(Some(
+                "Plant sent must be another existing plant".to_string(),
+            ));
+        }
+    }
+
+    if let EventData::Location(location_id) = new_event.event_data {
+        if !location_exists(location_id, &mut *connection).await? {
+            return Ok(Some("Location sent does not exist".to_string()));
+        }
+    }
+
+    if let Some(child_id) = propagated_plant(new_event) {
+        let parent_id: Option<Uuid> =
+            sqlx::query_scalar(r#"SELECT parent_id FROM plants WHERE id = $1"#)
+                .bind(child_id)
+                .fetch_one(&mut *connection)
+                .await?;
+        if parent_id.is_some_and(|parent_id| parent_id != new_event.plant_id) {
+            return Ok(Some(
+                "Plant sent was already propagated from another plant".to_string(),
+            ));
+        }
+
+        let is_ancestor: bool = sqlx::query_scalar(
+            r#"WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id FROM plants WHERE id = $1
+                UNION
+                SELECT plants.id, plants.parent_id FROM plants JOIN ancestors ON plants.id = ancestors.parent_id
+            )
+            SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2)"#,
+        )
+        .bind(new_event.plant_id)
+        .bind(child_id)
+        .fetch_one(&mut *connection)
+        .await?;
+        if is_ancestor {
+            return Ok(Some(
+                "A plant can't be propagated from one of its descendants".to_string(),
+            ));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The plant a Propagated event says was propagated from the plant it was logged for
+pub fn propagated_plant(new_event: &NewEvent) -> Option<Uuid> {
+    match new_event.event_data {
+        EventData::Plant(child_id) if new_event.event_type == uuid!(PROPAGATED_EVENT_TYPE_ID) => {
+            Some(child_id)
+        }
+        _ => None,
+    }
+}
+
+/// The lineage of a plant is read from its parent, so logging a Propagated event sets the parent of the propagated plant
+pub async fn record_propagation(
+    new_event: &NewEvent,
+    connection: &mut PgConnection,
+) -> Result<(), sqlx::Error> {
+    let Some(child_id) = propagated_plant(new_event) else {
+        return Ok(());
+    };
+    sqlx::query(r#"UPDATE plants SET parent_id = $1, event_modified = $3 WHERE id = $2"#)
+        .bind(new_event.plant_id)
+        .bind(child_id)
+        .bind(Utc::now().naive_utc())
+        .execute(connection)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use shared::events::{
+    EventData, EventInstance, Period, PeriodSpan,
+    events_http::{GetPeriods, NewEvent},
+};
+use sqlx::{PgConnection, PgPool, types::Json};
+
+use crate::app::events::get_events::EventInstanceRow;
+
+/// Gets the period spans of the requested plants and event types
+pub async fn get_periods(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetPeriods>,
+) -> Response {
+    let events: Vec<EventInstanceRow> = match sqlx::query_as(
+        r#"SELECT events.id, events.event_type_id, events.plant_id, events.data, events.event_date FROM events
+        JOIN event_types ON event_types.id = events.event_type_id
+        WHERE event_types.event_type::jsonb = '"Period"'::jsonb
+        AND ($1::uuid IS NULL OR events.plant_id = $1)
+        AND ($2::uuid IS NULL OR events.event_type_id = $2)
+        ORDER BY events.event_date"#,
+    )
+    .bind(request.plant_id)
+    .bind(request.event_type)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let events: Vec<EventInstance> = events
+        .into_iter()
+        .map(|event| EventInstance {
+            id: event.id,
+            event_type_id: event.event_type_id,
+            plant_id: event.plant_id,
+            data: event.data.0,
+            event_date: event.event_date,
+        })
+        .collect();
+
+    let mut spans = PeriodSpan::pair_periods(events);
+    if request.open_only {
+        spans.retain(|span| span.is_open());
+    }
+
+    let serialize = match serde_json::to_string(&spans) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Checks that a new period event keeps the periods of its plant paired. Returns a description of the problem if it doesn't
+///
+/// Starts and ends must alternate, so a period can only be started when there is no open period and can only be ended when there is one
+/// Both event tables are checked since a period event type can be unique
+pub async fn check_period(
+    new_event: &NewEvent,
+    connection: &mut PgConnection,
+) -> Result<Option<String>, sqlx::Error> {
+    let EventData::Period(period) = &new_event.event_data else {
+        return Ok(None);
+    };
+
+    let previous: Option<Json<EventData>> = sqlx::query_scalar(
+        r#"SELECT data FROM (
+            SELECT data::jsonb AS data, event_date FROM events WHERE plant_id = $1 AND event_type_id = $2
+            UNION ALL
+            SELECT data::jsonb, event_date FROM events_unique WHERE plant_id = $1 AND event_type_id = $2
+        ) periods WHERE event_date <= $3 ORDER BY event_date DESC LIMIT 1"#,
+    )
+    .bind(new_event.plant_id)
+    .bind(new_event.event_type)
+    .bind(new_event.event_date)
+    .fetch_optional(&mut *connection)
+    .await?;
+
+    let next: Option<Json<EventData>> = sqlx::query_scalar(
+        r#"SELECT data FROM (
+            SELECT data::jsonb AS data, event_date FROM events WHERE plant_id = $1 AND event_type_id = $2
+            UNION ALL
+            SELECT data::jsonb, event_date FROM events_unique WHERE plant_id = $1 AND event_type_id = $2
+        ) periods WHERE event_date > $3 ORDER BY event_date ASC LIMIT 1"#,
+    )
+    .bind(new_event.plant_id)
+    .bind(new_event.event_type)
+    .bind(new_event.event_date)
+    .fetch_optional(&mut *connection)
+    .await?;
+
+    let previous = previous.map(|data| data.0);
+    let next = next.map(|data| data.0);
+    let start = Some(EventData::Period(Period::PeriodStart));
+    let end = Some(EventData::Period(Period::PeriodEnd));
+
+    Ok(match period {
+        Period::PeriodStart if previous == start => {
+            Some("A period is already open, end it before starting a new one".to_string())
+        }
+        Period::PeriodStart if next.is_some() => {
+            Some("A period can't be started before a later period".to_string())
+        }
+        Period::PeriodEnd if previous != start => {
+            Some("There is no open period to end".to_string())
+        }
+        Period::PeriodEnd if next == end => {
+            Some("The open period has already been ended".to_string())
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,89 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use serde::{Deserialize, Serialize};
+use shared::locations::{Direction, LightLevel, Location, LocationKind};
+use sqlx::{PgConnection, PgPool, Pool, Postgres, prelude::FromRow, types::Json};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct LocationRow {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: Json<LocationKind>,
+    pub parent_id: Option<Uuid>,
+    pub light_level: Json<Option<LightLevel>>,
+    pub direction: Json<Option<Direction>>,
+}
+
+impl LocationRow {
+    pub fn into_location(self) -> Location {
+        Location {
+            id: self.id,
+            name: self.name,
+            kind: self.kind.0,
+            parent_id: self.parent_id,
+            light_level: self.light_level.0,
+            direction: self.direction.0,
+        }
+    }
+}
+
+/// Gets every saved location
+pub async fn get_locations(State(pool): State<PgPool>) -> Response {
+    let locations: Vec<LocationRow> = match sqlx::query_as(
+        r#"SELECT id, name, kind, parent_id, light_level, direction FROM locations ORDER BY name"#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let locations: Vec<Location> = locations
+        .into_iter()
+        .map(|location| location.into_location())
+        .collect();
+
+    let serialize = match serde_json::to_string(&locations) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
+
+/// Gets a single location
+pub async fn get_location(
+    location_id: Uuid,
+    pool: Pool<Postgres>,
+) -> Result<Option<Location>, sqlx::Error> {
+    let location: Option<LocationRow> = sqlx::query_as(
+        r#"SELECT id, name, kind, parent_id, light_level, direction FROM locations WHERE id = $1"#,
+    )
+    .bind(location_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(location.map(|location| location.into_location()))
+}
+
+/// Returns true if a location with the given id has been saved
+pub async fn location_exists(
+    location_id: Uuid,
+    connection: &mut PgConnection,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM locations WHERE id = $1)"#)
+        .bind(location_id)
+        .fetch_one(connection)
+        .await
+}
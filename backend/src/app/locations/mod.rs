@@ -0,0 +1,17 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::AppState;
+
+pub use get_locations::location_exists;
+
+mod get_locations;
+mod new_location;
+
+pub fn rout_location() -> Router<AppState> {
+    Router::new()
+        .route("/new", post(new_location::new_location))
+        .route("/get-all", get(get_locations::get_locations))
+}
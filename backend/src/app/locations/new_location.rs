@@ -0,0 +1,100 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    locations::{Location, locations_http::NewLocation},
+};
+use sqlx::{PgPool, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::app::locations::get_locations::get_location;
+
+/// Creates a new location and returns it to the client
+pub async fn new_location(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(new_location): axum::Json<NewLocation>,
+) -> Response {
+    if new_location.name.trim().is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Location name must not be empty".to_string()))
+            .unwrap();
+    }
+
+    // Homes hold rooms and rooms hold spots, so the parent must be exactly one level up
+    let parent = match new_location.parent_id {
+        Some(parent_id) => match get_location(parent_id, pool.clone()).await {
+            Ok(result) => result,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(err.to_string()))
+                    .unwrap();
+            }
+        },
+        None => None,
+    };
+    if parent.map(|parent| parent.kind) != new_location.kind.parent_kind() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!(
+                "A {:?} must be inside of a {:?}",
+                new_location.kind,
+                new_location.kind.parent_kind()
+            )))
+            .unwrap();
+    }
+
+    let location = Location {
+        id: Uuid::new_v4(),
+        name: new_location.name.trim().to_string(),
+        kind: new_location.kind,
+        parent_id: new_location.parent_id,
+        light_level: new_location.light_level,
+        direction: new_location.direction,
+    };
+
+    let _result = match sqlx::query(
+        "INSERT INTO locations(id, name, kind, parent_id, light_level, direction, date_created) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(location.id)
+    .bind(&location.name)
+    .bind(Json(location.kind))
+    .bind(location.parent_id)
+    .bind(Json(location.light_level))
+    .bind(Json(location.direction))
+    .bind(Utc::now().naive_utc())
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Location(location.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&location) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::builder()
+        .status(StatusCode::OK)
+        .body(Body::from(serialize))
+        .unwrap()
+}
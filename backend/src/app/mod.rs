@@ -1,6 +1,14 @@
+mod archive;
+mod calendar;
+mod collections;
+mod csv_import;
 mod events;
+mod locations;
+mod notes;
 mod photos;
 mod plants;
+mod species;
+mod stats;
 
 use std::{convert::Infallible, sync::Arc};
 
@@ -11,13 +19,21 @@ use axum::{
         Sse,
     },
 };
+pub use archive::{build_archive, restore_archive, rout_archive};
+pub use calendar::{calendar_ics, rout_calendar};
+pub use collections::rout_collection;
+pub use csv_import::rout_csv_import;
 pub use events::rout_event;
+pub use locations::rout_location;
+pub use notes::rout_note;
 use futures_util::{lock::Mutex, stream::Stream};
-pub use photos::route_photos;
+pub use photos::{get_cover_photo, route_photos};
 use shared::DirtyCache;
 use tokio::sync::mpsc::Receiver;
 
 pub use plants::rout_plant;
+pub use species::{rout_species, seed_species};
+pub use stats::rout_stats;
 
 pub async fn dirty_cache_sse_handler(
     State(receiver): State<Arc<Mutex<Receiver<DirtyCache>>>>,
@@ -0,0 +1,97 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    notes::{Note, notes_http::EditNote},
+};
+use sqlx::PgPool;
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::app::notes::{get_notes::NoteRow, new_note::check_note};
+
+/// Changes the body of a note, saving the previous body as a revision
+pub async fn edit_note(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(edit_note): axum::Json<EditNote>,
+) -> Response {
+    let now = Utc::now().naive_utc();
+    // The note is locked while it is edited so two edits at once each save the body the other replaced as a revision
+    let result: Result<Result<NoteRow, String>, sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
+        let note: Option<NoteRow> = sqlx::query_as(
+            r#"SELECT id, plant_id, event_id, body, date_created, date_edited FROM notes WHERE id = $1 FOR UPDATE"#,
+        )
+        .bind(edit_note.note_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let Some(note) = note else {
+            return Ok(Err("Note sent does not exist".to_string()));
+        };
+
+        if let Some(problem) =
+            check_note(note.plant_id, note.event_id, &edit_note.body, pool.clone()).await?
+        {
+            return Ok(Err(problem));
+        }
+
+        sqlx::query(
+            "INSERT INTO note_revisions(id, note_id, body, date_replaced) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(note.id)
+        .bind(&note.body)
+        .bind(now)
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query("UPDATE notes SET body = $2, date_edited = $3 WHERE id = $1")
+            .bind(note.id)
+            .bind(&edit_note.body)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(Ok(note))
+    }
+    .await;
+
+    let note = match result {
+        Ok(Ok(result)) => result,
+        Ok(Err(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let note = Note {
+        body: edit_note.body,
+        date_edited: now,
+        ..note.into_note()
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Note(note.plant_id, note.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&note) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
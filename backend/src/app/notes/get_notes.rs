@@ -0,0 +1,132 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::notes::{Note, NoteRevision};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct NoteRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub body: String,
+    pub date_created: NaiveDateTime,
+    pub date_edited: NaiveDateTime,
+}
+
+impl NoteRow {
+    pub fn into_note(self) -> Note {
+        Note {
+            id: self.id,
+            plant_id: self.plant_id,
+            event_id: self.event_id,
+            body: self.body,
+            date_created: self.date_created,
+            date_edited: self.date_edited,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct NoteRevisionRow {
+    pub note_id: Uuid,
+    pub body: String,
+    pub date_replaced: NaiveDateTime,
+}
+
+/// Gets every note of a plant, newest first
+pub async fn get_plant_notes(Path(plant_id): Path<String>, State(pool): State<PgPool>) -> Response {
+    let plant_id = match Uuid::parse_str(&plant_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let notes: Vec<NoteRow> = match sqlx::query_as(
+        r#"SELECT id, plant_id, event_id, body, date_created, date_edited FROM notes WHERE plant_id = $1 ORDER BY date_created DESC"#,
+    )
+    .bind(plant_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let notes: Vec<Note> = notes.into_iter().map(|note| note.into_note()).collect();
+
+    let serialize = match serde_json::to_string(&notes) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets every previous body of a note, newest first
+pub async fn get_note_history(Path(note_id): Path<String>, State(pool): State<PgPool>) -> Response {
+    let note_id = match Uuid::parse_str(&note_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let revisions: Vec<NoteRevisionRow> = match sqlx::query_as(
+        r#"SELECT note_id, body, date_replaced FROM note_revisions WHERE note_id = $1 ORDER BY date_replaced DESC"#,
+    )
+    .bind(note_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let revisions: Vec<NoteRevision> = revisions
+        .into_iter()
+        .map(|revision| NoteRevision {
+            note_id: revision.note_id,
+            body: revision.body,
+            date_replaced: revision.date_replaced,
+        })
+        .collect();
+
+    let serialize = match serde_json::to_string(&revisions) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
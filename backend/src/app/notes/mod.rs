@@ -0,0 +1,18 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::AppState;
+
+mod edit_note;
+mod get_notes;
+mod new_note;
+
+pub fn rout_note() -> Router<AppState> {
+    Router::new()
+        .route("/new", post(new_note::new_note))
+        .route("/edit", post(edit_note::edit_note))
+        .route("/plant/{id}", get(get_notes::get_plant_notes))
+        .route("/history/{id}", get(get_notes::get_note_history))
+}
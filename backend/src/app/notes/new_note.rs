@@ -0,0 +1,144 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    notes::{Note, notes_http::NewNote},
+};
+use sqlx::{PgPool, Pool, Postgres};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+/// Writes a new note about a plant and returns it to the client
+pub async fn new_note(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(new_note): axum::Json<NewNote>,
+) -> Response {
+    match check_note(
+        new_note.plant_id,
+        new_note.event_id,
+        &new_note.body,
+        pool.clone(),
+    )
+    .await
+    {
+        Ok(None) => {}
+        Ok(Some(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    }
+
+    let now = Utc::now().naive_utc();
+    let note = Note {
+        id: Uuid::new_v4(),
+        plant_id: new_note.plant_id,
+        event_id: new_note.event_id,
+        body: new_note.body,
+        date_created: now,
+        date_edited: now,
+    };
+
+    let _result = match sqlx::query(
+        "INSERT INTO notes(id, plant_id, event_id, body, date_created, date_edited) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(note.id)
+    .bind(note.plant_id)
+    .bind(note.event_id)
+    .bind(&note.body)
+    .bind(note.date_created)
+    .bind(note.date_edited)
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Note(note.plant_id, note.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&note) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Checks that a note can be saved. Returns a description of the problem if it can't
+///
+/// The plant must exist, the event must belong to the plant and every photo referenced in the body must be a photo of the plant
+pub(super) async fn check_note(
+    plant_id: Uuid,
+    event_id: Option<Uuid>,
+    body: &str,
+    pool: Pool<Postgres>,
+) -> Result<Option<String>, sqlx::Error> {
+    if body.trim().is_empty() {
+        return Ok(Some("Note must not be empty".to_string()));
+    }
+
+    let plant_exists: bool =
+        sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM plants WHERE id = $1)"#)
+            .bind(plant_id)
+            .fetch_one(&pool)
+            .await?;
+    if !plant_exists {
+        return Ok(Some("Plant sent does not exist".to_string()));
+    }
+
+    if let Some(event_id) = event_id {
+        let event_exists: bool = sqlx::query_scalar(
+            r#"SELECT EXISTS (SELECT 1 FROM events WHERE id = $1 AND plant_id = $2) OR EXISTS (SELECT 1 FROM events_unique WHERE id = $1 AND plant_id = $2)"#,
+        )
+        .bind(event_id)
+        .bind(plant_id)
+        .fetch_one(&pool)
+        .await?;
+        if !event_exists {
+            return Ok(Some("Event sent does not belong to the plant".to_string()));
+        }
+    }
+
+    let photo_references = Note::photo_references(body);
+    if !photo_references.is_empty() {
+        let found_photos: i64 = sqlx::query_scalar(
+            r#"SELECT COUNT(*) FROM photos WHERE plant_id = $1 AND id = ANY($2)"#,
+        )
+        .bind(plant_id)
+        .bind(&photo_references)
+        .fetch_one(&pool)
+        .await?;
+        let mut unique_references = photo_references.clone();
+        unique_references.sort();
+        unique_references.dedup();
+        if found_photos != unique_references.len() as i64 {
+            return Ok(Some(
+                "Note references a photo that is not a photo of the plant".to_string(),
+            ));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,71 @@
+use image::{Rgba, RgbaImage};
+
+/// 3x5 pixel glyphs for the characters used in dates. Each row is 3 bits, most significant bit on the left
+static DIGIT_GLYPHS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+static DASH_GLYPH: [u8; 5] = [0b000, 0b000, 0b111, 0b000, 0b000];
+
+/// Draws the caption in white on a darkened box in the bottom left corner of the frame.
+///
+/// Only digits and dashes are drawn, any other character is left as a gap
+pub fn draw_caption(frame: &mut RgbaImage, caption: &str) {
+    let scale = (frame.width() / 128).max(1);
+    let margin = scale * 2;
+    let char_width = 4 * scale;
+    let box_width = caption.chars().count() as u32 * char_width + margin * 2;
+    let box_height = 5 * scale + margin * 2;
+
+    if box_width > frame.width() || box_height > frame.height() {
+        return;
+    }
+    let box_x = 0;
+    let box_y = frame.height() - box_height;
+
+    for y in box_y..box_y + box_height {
+        for x in box_x..box_x + box_width {
+            let pixel = frame.get_pixel_mut(x, y);
+            for channel in pixel.0.iter_mut().take(3) {
+                *channel /= 3;
+            }
+        }
+    }
+
+    for (index, character) in caption.chars().enumerate() {
+        let glyph = match character {
+            '-' => &DASH_GLYPH,
+            digit => match digit.to_digit(10) {
+                Some(digit) => &DIGIT_GLYPHS[digit as usize],
+                None => continue,
+            },
+        };
+        let glyph_x = box_x + margin + index as u32 * char_width;
+        let glyph_y = box_y + margin;
+
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for y in 0..scale {
+                    for x in 0..scale {
+                        frame.put_pixel(
+                            glyph_x + column * scale + x,
+                            glyph_y + row as u32 * scale + y,
+                            Rgba([255, 255, 255, 255]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
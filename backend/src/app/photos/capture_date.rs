@@ -0,0 +1,44 @@
+use std::io::Cursor;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use exif::{In, Reader, Tag, Value};
+
+/// Reads the EXIF `DateTimeOriginal` of the photo, the moment the picture was actually taken.
+///
+/// If the photo also records `OffsetTimeOriginal` the date is converted to UTC, otherwise the camera's local time is used as is.
+/// Returns None if the photo has no EXIF data or the date could not be parsed
+pub fn exif_capture_date(photo_binary: &[u8]) -> Option<NaiveDateTime> {
+    let exif = Reader::new()
+        .read_from_container(&mut Cursor::new(photo_binary))
+        .ok()?;
+
+    let field = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)?;
+    let Value::Ascii(ref ascii) = field.value else {
+        return None;
+    };
+    let mut date_time = exif::DateTime::from_ascii(ascii.first()?).ok()?;
+
+    if let Some(offset) = exif.get_field(Tag::OffsetTimeOriginal, In::PRIMARY) {
+        if let Value::Ascii(ref ascii) = offset.value {
+            if let Some(offset) = ascii.first() {
+                let _ = date_time.parse_offset(offset);
+            }
+        }
+    }
+
+    let date = NaiveDate::from_ymd_opt(
+        date_time.year as i32,
+        date_time.month as u32,
+        date_time.day as u32,
+    )?
+    .and_hms_opt(
+        date_time.hour as u32,
+        date_time.minute as u32,
+        date_time.second as u32,
+    )?;
+
+    Some(match date_time.offset {
+        Some(offset) => date - Duration::minutes(offset as i64),
+        None => date,
+    })
+}
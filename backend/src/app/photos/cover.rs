@@ -0,0 +1,79 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    photos::{Photo, SetCoverPhoto},
+};
+use sqlx::{PgPool, Pool, Postgres};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+use crate::app::photos::get_photos::PhotoRow;
+
+/// Returns the photo that represents the plant. That is the explicitly chosen cover photo, or else the plants latest photo
+pub async fn get_cover_photo(
+    plant_id: Uuid,
+    cover_photo_id: Option<Uuid>,
+    pool: Pool<Postgres>,
+) -> Result<Option<Photo>, sqlx::Error> {
+    let photo: Option<PhotoRow> = sqlx::query_as(
+        r#"SELECT id, plant_id, photo_date, file_location FROM photos WHERE plant_id = $1 ORDER BY (id = $2) IS TRUE DESC, photo_date DESC LIMIT 1"#,
+    )
+    .bind(plant_id)
+    .bind(cover_photo_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(photo.map(|photo| Photo {
+        id: photo.id,
+        plant_id: photo.plant_id,
+        photo_date: photo.photo_date,
+        thumbnail_location: Photo::thumbnail_location(photo.id),
+        file_location: photo.file_location,
+    }))
+}
+
+/// Sets the photo that represents the plant on cards and in its demographic
+pub async fn set_cover_photo(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(request): axum::Json<SetCoverPhoto>,
+) -> Response {
+    let result = match sqlx::query(
+        r#"UPDATE plants SET cover_photo_id = $2, event_modified = $3 WHERE id = $1 AND EXISTS (SELECT 1 FROM photos WHERE photos.id = $2 AND photos.plant_id = $1)"#,
+    )
+    .bind(request.plant_id)
+    .bind(request.photo_id)
+    .bind(Utc::now().naive_utc())
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if result.rows_affected() == 0 {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Photo does not exist or does not belong to the plant".to_string(),
+            ))
+            .unwrap();
+    }
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Plant(request.plant_id),
+        })
+        .await;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .body(Body::from("".to_string()))
+        .unwrap()
+}
@@ -0,0 +1,98 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::photos::{GetPhotos, GetPhotosResponse, Photo, PhotoCursor};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::Uuid;
+
+/// The most photos that will be returned in a single page
+static MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PhotoRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub photo_date: NaiveDateTime,
+    pub file_location: String,
+}
+
+/// Returns a page of photos across all plants, newest first, matching the requested filters
+pub async fn get_photos(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetPhotos>,
+) -> Response {
+    let limit = request.limit.clamp(1, MAX_PAGE_SIZE);
+    let (span_start, span_end) = match request.span {
+        Some((start, end)) => (Some(start), Some(end)),
+        None => (None, None),
+    };
+    let (cursor_date, cursor_id) = match &request.cursor {
+        Some(cursor) => (Some(cursor.photo_date), Some(cursor.id)),
+        None => (None, None),
+    };
+
+    let photos: Vec<PhotoRow> = match sqlx::query_as(
+        r#"SELECT id, plant_id, photo_date, file_location FROM photos
+        WHERE plant_id IS NOT NULL
+            AND ($1::uuid IS NULL OR plant_id = $1)
+            AND ($2::timestamp IS NULL OR photo_date >= $2)
+            AND ($3::timestamp IS NULL OR photo_date <= $3)
+            AND ($4::timestamp IS NULL OR (photo_date, id) < ($4, $5::uuid))
+            AND ($6::varchar IS NULL OR plant_current_state(plant_id) = $6)
+        ORDER BY photo_date DESC, id DESC
+        LIMIT $7"#,
+    )
+    .bind(request.plant_id)
+    .bind(span_start)
+    .bind(span_end)
+    .bind(cursor_date)
+    .bind(cursor_id)
+    .bind(&request.plant_state)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // A short page means there is nothing left to request
+    let next_cursor = match photos.len() as i64 == limit {
+        true => photos.last().map(|photo| PhotoCursor {
+            photo_date: photo.photo_date,
+            id: photo.id,
+        }),
+        false => None,
+    };
+
+    let response = GetPhotosResponse {
+        photos: photos
+            .into_iter()
+            .map(|photo| Photo {
+                id: photo.id,
+                plant_id: photo.plant_id,
+                photo_date: photo.photo_date,
+                thumbnail_location: Photo::thumbnail_location(photo.id),
+                file_location: photo.file_location,
+            })
+            .collect(),
+        next_cursor,
+    };
+
+    let serialize = match serde_json::to_string(&response) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,34 @@
+use image::{DynamicImage, imageops::FilterType};
+use sha2::{Digest, Sha256};
+
+/// The max number of differing bits between two perceptual hashes for the photos to be reported as possible duplicates
+pub static PERCEPTUAL_DUPLICATE_DISTANCE: u32 = 6;
+
+/// Returns the hex encoded sha256 hash of the uploaded photo bytes. Identical uploads always share the same hash
+pub fn content_hash(photo_binary: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(photo_binary))
+}
+
+/// Returns a difference hash of the image.
+///
+/// The image is shrunk to 9x8 grayscale pixels and each bit records whether a pixel is brighter than its right neighbour,
+/// so resized or recompressed copies of the same picture end up with the same or very close hashes
+pub fn perceptual_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash: u64 = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y).0[0] > small.get_pixel(x + 1, y).0[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Number of bits that differ between two perceptual hashes
+pub fn hash_distance(left: u64, right: u64) -> u32 {
+    (left ^ right).count_ones()
+}
@@ -1,16 +1,48 @@
 use std::fs;
 
-use axum::{Router, routing::post};
+use axum::{
+    Router,
+    routing::{get, post},
+};
 
-use crate::{AppState, app::photos::new_photo::new_photo};
+pub use cover::get_cover_photo;
 
+use crate::{
+    AppState,
+    app::photos::{
+        cover::set_cover_photo,
+        get_photos::get_photos,
+        new_photo::new_photo,
+        possible_duplicates::get_possible_duplicates,
+        timelapse::{get_timelapse, new_timelapse},
+    },
+};
+
+/// Module for drawing date captions onto frames
+mod caption;
+/// Module for reading when a photo was taken from its EXIF data
+mod capture_date;
+/// Module for choosing and finding the photo that represents a plant
+mod cover;
 mod get_photo;
+mod get_photos;
+/// Module for content and perceptual hashing of uploaded photos
+mod hashing;
 mod new_photo;
+mod possible_duplicates;
+/// Module for rendering timelapses out of a plants photo history
+mod timelapse;
 
 pub fn route_photos() -> Router<AppState> {
     let _ = fs::create_dir_all("/assets/photos/thumbs");
 
-    Router::new().route("/new", post(new_photo))
+    Router::new()
+        .route("/new", post(new_photo))
+        .route("/get-photos", post(get_photos))
+        .route("/set-cover", post(set_cover_photo))
+        .route("/possible-duplicates", get(get_possible_duplicates))
+        .route("/timelapse/new", post(new_timelapse))
+        .route("/timelapse/{plant_id}", get(get_timelapse))
     //.route("/get/{id}", get(request_plant))
     //.route("/delete/{id}", get(delete_plant))
 }
@@ -1,41 +1,73 @@
 use std::io::Cursor;
 
-use crate::app::events::new_event;
+use crate::app::{
+    events::{EventInstanceRow, new_event},
+    photos::{
+        capture_date::exif_capture_date,
+        hashing::{content_hash, perceptual_hash},
+    },
+};
 use axum::{Json, body::Body, extract::State, http::StatusCode, response::Response};
 use chrono::Utc;
-use image::{DynamicImage, ImageDecoder, ImageReader, codecs::png::PngDecoder};
+use image::ImageReader;
+use serde::{Deserialize, Serialize};
 use shared::{
     DirtyCache,
-    events::{PHOTO_EVENT_TYPE_ID, events_http::NewEvent},
+    events::{EventData, EventInstance, PHOTO_EVENT_TYPE_ID, events_http::NewEvent},
     photos::NewPhoto,
 };
-use sqlx::PgPool;
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow};
 use tokio::{fs, sync::mpsc::Sender};
 use tracing::debug;
 use uuid::Uuid;
 use uuid::uuid;
 
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PhotoRow {
+    pub id: Uuid,
+    pub file_location: String,
+}
+
 /// Creates a new plant on the server and returns a basic plant demographic to the client
 pub async fn new_photo(
     State(pool): State<PgPool>,
     State(dirt_cache): State<Sender<DirtyCache>>,
     axum::Json(new_photo): axum::Json<NewPhoto>,
 ) -> Response {
-    let photo_id = Uuid::new_v4();
-    let file_location = format!("./assets/photos/{}.png", photo_id);
-    let thumbs_file_location = format!("./assets/photos/thumbs/{}.png", photo_id);
+    let hash = content_hash(&new_photo.photo_binary);
 
-    let thumbnail =
-        match ImageReader::new(Cursor::new(new_photo.photo_binary.clone())).with_guessed_format() {
-            Ok(ok) => match ok.decode() {
-                Ok(ok) => ok,
+    // If this exact photo was already uploaded for this plant then hand back the existing photo event instead of storing it twice
+    match get_duplicate_photo_event(new_photo.plant_id, &hash, pool.clone()).await {
+        Ok(Some(existing_event)) => {
+            let serialize = match serde_json::to_string(&existing_event) {
+                Ok(result) => result,
                 Err(err) => {
                     return Response::builder()
                         .status(StatusCode::INTERNAL_SERVER_ERROR)
                         .body(Body::from(err.to_string()))
                         .unwrap();
                 }
-            },
+            };
+            return Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::from(serialize))
+                .unwrap();
+        }
+        Ok(None) => {}
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    }
+
+    let photo_id = Uuid::new_v4();
+    let thumbs_file_location = format!("./assets/photos/thumbs/{}.png", photo_id);
+
+    let reader =
+        match ImageReader::new(Cursor::new(new_photo.photo_binary.clone())).with_guessed_format() {
+            Ok(ok) => ok,
             Err(err) => {
                 return Response::builder()
                     .status(StatusCode::INTERNAL_SERVER_ERROR)
@@ -44,6 +76,30 @@ pub async fn new_photo(
             }
         };
 
+    // The original upload is saved untouched so it keeps its EXIF data, which means it keeps its original format too
+    let extension = reader
+        .format()
+        .and_then(|format| format.extensions_str().first().copied())
+        .unwrap_or("png");
+    let file_location = format!("./assets/photos/{}.{}", photo_id, extension);
+
+    let thumbnail = match reader.decode() {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // Prefer when the photo was actually taken, then when the client says it was taken, and only then the upload time
+    let photo_date = exif_capture_date(&new_photo.photo_binary)
+        .or(new_photo.photo_date)
+        .unwrap_or(Utc::now().naive_utc());
+
+    let perceptual_hash = perceptual_hash(&thumbnail);
+
     let thumbnail = thumbnail.resize(200, 200, image::imageops::FilterType::Gaussian);
 
     let mut buf: Vec<u8> = Vec::new();
@@ -71,22 +127,26 @@ pub async fn new_photo(
         }
     };
 
-    let _result =
-        match sqlx::query("INSERT INTO photos(id, file_location, photo_date) VALUES ($1, $2, $3)")
-            .bind(photo_id.clone())
-            .bind(&file_location)
-            .bind(Utc::now().naive_utc())
-            .execute(&pool)
-            .await
-        {
-            Ok(ok) => ok,
-            Err(err) => {
-                return Response::builder()
-                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(Body::from(err.to_string()))
-                    .unwrap();
-            }
-        };
+    let _result = match sqlx::query(
+        "INSERT INTO photos(id, file_location, photo_date, plant_id, content_hash, perceptual_hash) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(photo_id.clone())
+    .bind(&file_location)
+    .bind(photo_date)
+    .bind(new_photo.plant_id)
+    .bind(&hash)
+    .bind(perceptual_hash as i64)
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
 
     let new_photo_event_row = new_event(
         State(pool.clone()),
@@ -95,7 +155,7 @@ pub async fn new_photo(
             event_type: uuid!(PHOTO_EVENT_TYPE_ID),
             plant_id: new_photo.plant_id,
             event_data: shared::events::EventData::String(file_location),
-            event_date: Utc::now().naive_utc(),
+            event_date: photo_date,
         }),
     )
     .await;
@@ -109,3 +169,39 @@ pub async fn new_photo(
 
     new_photo_event_row
 }
+
+/// Returns the photo event of an already uploaded photo of the plant with the same content hash, if there is one
+async fn get_duplicate_photo_event(
+    plant_id: Uuid,
+    content_hash: &str,
+    pool: Pool<Postgres>,
+) -> Result<Option<EventInstance>, sqlx::Error> {
+    let existing_photo: Option<PhotoRow> = sqlx::query_as(
+        r#"SELECT id, file_location FROM photos WHERE plant_id = $1 AND content_hash = $2 LIMIT 1"#,
+    )
+    .bind(plant_id)
+    .bind(content_hash)
+    .fetch_optional(&pool)
+    .await?;
+
+    let Some(existing_photo) = existing_photo else {
+        return Ok(None);
+    };
+
+    let event: Option<EventInstanceRow> = sqlx::query_as(
+        r#"SELECT id, event_type_id, plant_id, data, event_date FROM events WHERE plant_id = $1 AND event_type_id = $2 AND data::jsonb = $3 LIMIT 1"#,
+    )
+    .bind(plant_id)
+    .bind(uuid!(PHOTO_EVENT_TYPE_ID))
+    .bind(sqlx::types::Json(EventData::String(existing_photo.file_location)))
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(event.map(|event| EventInstance {
+        id: event.id,
+        event_type_id: event.event_type_id,
+        plant_id: event.plant_id,
+        data: event.data.0,
+        event_date: event.event_date,
+    }))
+}
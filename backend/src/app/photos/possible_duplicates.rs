@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use serde::{Deserialize, Serialize};
+use shared::photos::PossibleDuplicate;
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::Uuid;
+
+use crate::app::photos::hashing::{PERCEPTUAL_DUPLICATE_DISTANCE, hash_distance};
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PhotoHashRow {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub perceptual_hash: i64,
+}
+
+/// Reports photos of the same plant that look nearly identical to each other based on their perceptual hashes
+pub async fn get_possible_duplicates(State(pool): State<PgPool>) -> Response {
+    let photos: Vec<PhotoHashRow> = match sqlx::query_as(
+        r#"SELECT id, plant_id, perceptual_hash FROM photos WHERE plant_id IS NOT NULL AND perceptual_hash IS NOT NULL ORDER BY photo_date"#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let mut plants: HashMap<Uuid, Vec<&PhotoHashRow>> = HashMap::new();
+    for photo in photos.iter() {
+        plants.entry(photo.plant_id).or_default().push(photo);
+    }
+
+    let mut possible_duplicates: Vec<PossibleDuplicate> = vec![];
+    for (plant_id, plant_photos) in plants.iter() {
+        for (index, photo) in plant_photos.iter().enumerate() {
+            // Photos are ordered oldest first so later photos are always reported as the duplicate of the earlier one
+            for original in plant_photos[..index].iter() {
+                let distance = hash_distance(
+                    photo.perceptual_hash as u64,
+                    original.perceptual_hash as u64,
+                );
+                if distance <= PERCEPTUAL_DUPLICATE_DISTANCE {
+                    possible_duplicates.push(PossibleDuplicate {
+                        plant_id: *plant_id,
+                        photo_id: photo.id,
+                        duplicate_of: original.id,
+                        distance,
+                    });
+                }
+            }
+        }
+    }
+
+    let serialize = match serde_json::to_string(&possible_duplicates) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,301 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use image::{
+    Delay, Frame, ImageError,
+    codecs::gif::{GifEncoder, Repeat},
+    imageops::FilterType,
+};
+use serde::{Deserialize, Serialize};
+use shared::{
+    DirtyCache,
+    photos::{NewTimelapse, TimelapseStatus},
+};
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow, types::Json};
+use tokio::{fs, sync::mpsc::Sender};
+use uuid::Uuid;
+
+use crate::app::photos::{caption::draw_caption, hashing::content_hash};
+
+/// How long a timelapse can be generating before it is treated as abandoned, such as when the server restarted mid render
+static TIMELAPSE_RENDER_TIMEOUT_MINUTES: i64 = 15;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct TimelapsePhotoRow {
+    pub id: Uuid,
+    pub file_location: String,
+    pub photo_date: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct TimelapseRow {
+    pub source_hash: String,
+    pub status: Json<TimelapseStatus>,
+    pub date_requested: NaiveDateTime,
+}
+
+/// Starts rendering a timelapse of every photo of the plant in the background.
+///
+/// If a timelapse with the same photos and settings was already requested then its current status is returned instead of rendering it again,
+/// unless it has been generating for longer than the render could take. A timelapse of older photos or settings is deleted
+pub async fn new_timelapse(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(request): axum::Json<NewTimelapse>,
+) -> Response {
+    let photos: Vec<TimelapsePhotoRow> = match sqlx::query_as(
+        r#"SELECT id, file_location, photo_date FROM photos WHERE plant_id = $1 ORDER BY photo_date"#,
+    )
+    .bind(request.plant_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if photos.is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Plant has no photos to make a timelapse from".to_string(),
+            ))
+            .unwrap();
+    }
+
+    // Any new, deleted, or redated photo or a change in settings changes the hash and invalidates the cached timelapse
+    let photo_dates: Vec<(Uuid, NaiveDateTime)> = photos
+        .iter()
+        .map(|photo| (photo.id, photo.photo_date))
+        .collect();
+    let source_hash = content_hash(
+        format!(
+            "{:?}{}{}{}",
+            photo_dates, request.frame_size, request.frame_delay_ms, request.date_caption
+        )
+        .as_bytes(),
+    );
+
+    let existing: Option<TimelapseRow> = match sqlx::query_as(
+        r#"SELECT source_hash, status, date_requested FROM timelapses WHERE plant_id = $1"#,
+    )
+    .bind(request.plant_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let now = Utc::now().naive_utc();
+    if let Some(existing) = &existing {
+        let abandoned =
+            existing.date_requested < now - Duration::minutes(TIMELAPSE_RENDER_TIMEOUT_MINUTES);
+        let current = match &existing.status.0 {
+            TimelapseStatus::Generating => !abandoned,
+            TimelapseStatus::Ready(_) => true,
+            _ => false,
+        };
+        if existing.source_hash == source_hash && current {
+            return timelapse_status_response(existing.status.0.clone());
+        }
+    }
+
+    let status = TimelapseStatus::Generating;
+    let _result = match sqlx::query(
+        r#"INSERT INTO timelapses(plant_id, source_hash, status, date_requested) VALUES ($1, $2, $3, $4) ON CONFLICT (plant_id) DO UPDATE SET source_hash = EXCLUDED.source_hash, status = EXCLUDED.status, date_requested = EXCLUDED.date_requested"#,
+    )
+    .bind(request.plant_id)
+    .bind(&source_hash)
+    .bind(Json(status.clone()))
+    .bind(now)
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // The new timelapse is saved under its own hash so the superseded one is no longer reachable
+    if let Some(TimelapseRow {
+        source_hash: previous_hash,
+        status: Json(TimelapseStatus::Ready(previous_location)),
+        ..
+    }) = existing
+    {
+        if previous_hash != source_hash {
+            if let Err(err) = fs::remove_file(&previous_location).await {
+                tracing::warn!(
+                    "Failed to delete superseded timelapse {}: {}",
+                    previous_location,
+                    err
+                );
+            }
+        }
+    }
+
+    tokio::spawn(generate_timelapse(
+        pool,
+        dirt_cache,
+        request,
+        photos,
+        source_hash,
+    ));
+
+    timelapse_status_response(status)
+}
+
+/// Returns the status of the latest timelapse requested for the plant
+pub async fn get_timelapse(Path(plant_id): Path<String>, State(pool): State<PgPool>) -> Response {
+    let plant_id = match Uuid::parse_str(&plant_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let existing: Option<TimelapseRow> = match sqlx::query_as(
+        r#"SELECT source_hash, status, date_requested FROM timelapses WHERE plant_id = $1"#,
+    )
+    .bind(plant_id)
+    .fetch_optional(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    timelapse_status_response(match existing {
+        Some(existing) => existing.status.0,
+        None => TimelapseStatus::NotGenerated,
+    })
+}
+
+fn timelapse_status_response(status: TimelapseStatus) -> Response {
+    let serialize = match serde_json::to_string(&status) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
+
+/// Background job that renders the timelapse, saves the result and tells clients that the plant changed
+async fn generate_timelapse(
+    pool: Pool<Postgres>,
+    dirt_cache: Sender<DirtyCache>,
+    request: NewTimelapse,
+    photos: Vec<TimelapsePhotoRow>,
+    source_hash: String,
+) {
+    let plant_id = request.plant_id;
+    let file_location = format!(
+        "./assets/photos/timelapses/{}-{}.gif",
+        plant_id,
+        &source_hash[..12]
+    );
+    let _ = fs::create_dir_all("./assets/photos/timelapses").await;
+
+    let render_location = file_location.clone();
+    let status = match tokio::task::spawn_blocking(move || {
+        render_timelapse(photos, request, &render_location)
+    })
+    .await
+    {
+        Ok(Ok(())) => TimelapseStatus::Ready(file_location.clone()),
+        Ok(Err(err)) => TimelapseStatus::Failed(err.to_string()),
+        Err(err) => TimelapseStatus::Failed(err.to_string()),
+    };
+
+    // Only overwrite the status if a newer timelapse wasn't requested while this one was rendering, otherwise this render is
+    // already superseded and its file is deleted
+    let rendered = matches!(status, TimelapseStatus::Ready(_));
+    match sqlx::query(
+        r#"UPDATE timelapses SET status = $2 WHERE plant_id = $1 AND source_hash = $3"#,
+    )
+    .bind(plant_id)
+    .bind(Json(status))
+    .bind(&source_hash)
+    .execute(&pool)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 && rendered => {
+            let _ = fs::remove_file(&file_location).await;
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::error!("Failed to save timelapse of plant {}: {}", plant_id, err)
+        }
+    }
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Plant(plant_id),
+        })
+        .await;
+}
+
+/// Renders the photos in order into an endlessly looping animated GIF.
+///
+/// Every photo is cropped to a square around its center and scaled to the requested frame size so that the frames line up
+fn render_timelapse(
+    photos: Vec<TimelapsePhotoRow>,
+    request: NewTimelapse,
+    file_location: &str,
+) -> Result<(), ImageError> {
+    let frame_size = request.frame_size.clamp(64, 1024);
+    let file = std::fs::File::create(file_location)?;
+
+    let mut encoder = GifEncoder::new_with_speed(file, 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    for photo in photos {
+        let mut frame = image::open(&photo.file_location)?
+            .resize_to_fill(frame_size, frame_size, FilterType::Triangle)
+            .to_rgba8();
+
+        if request.date_caption {
+            draw_caption(&mut frame, &photo.photo_date.format("%Y-%m-%d").to_string());
+        }
+
+        encoder.encode_frame(Frame::from_parts(
+            frame,
+            0,
+            0,
+            Delay::from_numer_denom_ms(request.frame_delay_ms, 1),
+        ))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{
+    DirtyCache,
+    plant::{
+        ExtraData, ExtraDataField, ExtraDataKind, ExtraDataType,
+        plant_http::{NewExtraDataField, SetExtraData},
+    },
+};
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ExtraDataFieldRow {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: Json<ExtraDataKind>,
+    pub unit: Option<String>,
+}
+
+impl ExtraDataFieldRow {
+    pub fn into_field(self) -> ExtraDataField {
+        ExtraDataField {
+            id: self.id,
+            name: self.name,
+            kind: self.kind.0,
+            unit: self.unit,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct ExtraDataRow {
+    pub field_id: Uuid,
+    pub data: Json<ExtraDataType>,
+    pub date_changed: NaiveDateTime,
+}
+
+/// Defines a new field that every plant can have a value for
+pub async fn new_extra_data_field(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(new_field): axum::Json<NewExtraDataField>,
+) -> Response {
+    if new_field.name.trim().is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Field name must not be empty".to_string()))
+            .unwrap();
+    }
+
+    let field = ExtraDataField {
+        id: Uuid::new_v4(),
+        name: new_field.name.trim().to_string(),
+        kind: new_field.kind,
+        unit: new_field
+            .unit
+            .map(|unit| unit.trim().to_string())
+            .filter(|unit| !unit.is_empty()),
+    };
+
+    let _result = match sqlx::query(
+        "INSERT INTO extra_data_fields(id, name, kind, unit, date_created) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(field.id)
+    .bind(&field.name)
+    .bind(Json(field.kind))
+    .bind(&field.unit)
+    .bind(Utc::now().naive_utc())
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::ExtraDataField(field.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&field) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets every user defined field
+pub async fn get_extra_data_fields(State(pool): State<PgPool>) -> Response {
+    let fields: Vec<ExtraDataFieldRow> =
+        match sqlx::query_as(r#"SELECT id, name, kind, unit FROM extra_data_fields ORDER BY name"#)
+            .fetch_all(&pool)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(err.to_string()))
+                    .unwrap();
+            }
+        };
+
+    let fields: Vec<ExtraDataField> = fields.into_iter().map(|field| field.into_field()).collect();
+
+    let serialize = match serde_json::to_string(&fields) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Sets a new value for a field on a plant, keeping the old value as history
+pub async fn set_extra_data(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(set_extra_data): axum::Json<SetExtraData>,
+) -> Response {
+    let field: Option<ExtraDataFieldRow> =
+        match sqlx::query_as(r#"SELECT id, name, kind, unit FROM extra_data_fields WHERE id = $1"#)
+            .bind(set_extra_data.field_id)
+            .fetch_optional(&pool)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(err.to_string()))
+                    .unwrap();
+            }
+        };
+
+    let Some(field) = field else {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Field sent does not exist".to_string()))
+            .unwrap();
+    };
+
+    if field.kind.0 != set_extra_data.data.kind() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Data sent does not match the kind of the field".to_string(),
+            ))
+            .unwrap();
+    }
+
+    let now = Utc::now().naive_utc();
+    let updated = match sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = $1")
+        .bind(set_extra_data.plant_id)
+        .bind(now)
+        .execute(&pool)
+        .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if updated.rows_affected() == 0 {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Plant sent does not exist".to_string()))
+            .unwrap();
+    }
+
+    let _result = match sqlx::query(
+        "INSERT INTO plant_extra_data(id, plant_id, field_id, data, date_changed) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(set_extra_data.plant_id)
+    .bind(set_extra_data.field_id)
+    .bind(Json(&set_extra_data.data))
+    .bind(now)
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Plant(set_extra_data.plant_id),
+        })
+        .await;
+
+    let extra_data = match get_plant_extra_data(set_extra_data.plant_id, pool).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let serialize = match serde_json::to_string(&extra_data) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets the current value and the history of every field set on the plant
+pub async fn get_plant_extra_data(
+    plant_id: Uuid,
+    pool: Pool<Postgres>,
+) -> Result<Vec<ExtraData>, sqlx::Error> {
+    let fields: Vec<ExtraDataFieldRow> = sqlx::query_as(
+        r#"SELECT id, name, kind, unit FROM extra_data_fields WHERE id IN (SELECT field_id FROM plant_extra_data WHERE plant_id = $1) ORDER BY name"#,
+    )
+    .bind(plant_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let values: Vec<ExtraDataRow> = sqlx::query_as(
+        r#"SELECT field_id, data, date_changed FROM plant_extra_data WHERE plant_id = $1 ORDER BY date_changed DESC"#,
+    )
+    .bind(plant_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut extra_data = vec![];
+    for field in fields {
+        let field_id = field.id;
+        let mut history = values
+            .iter()
+            .filter(|value| value.field_id == field_id)
+            .map(|value| (value.date_changed, value.data.0.clone()));
+        // Values are ordered newest first so the first one is the current value
+        let Some((date_changed, data)) = history.next() else {
+            continue;
+        };
+        extra_data.push(ExtraData {
+            field: field.into_field(),
+            data,
+            date_changed,
+            history: history.collect(),
+        });
+    }
+
+    Ok(extra_data)
+}
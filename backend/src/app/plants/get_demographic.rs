@@ -7,13 +7,13 @@ use axum::{
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use shared::{
-    events::{CustomEnum, EventData, PLANT_NAME_EVENT_ID, PLANT_STATE_ID},
-    plant::{Plant, PlantDemographic},
+    events::{EventData, MOVED_EVENT_TYPE_ID, PLANT_NAME_EVENT_ID, PLANT_STATE_ID},
+    plant::{Plant, PlantDemographic, PlantState},
 };
 use sqlx::{prelude::FromRow, PgPool};
 use uuid::{uuid, Uuid};
 
-use crate::app::events::get_last_event;
+use crate::app::{events::get_last_event, get_cover_photo, plants::tags::get_plant_tags};
 
 /// Struct which represents an entire plant
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -21,6 +21,8 @@ pub struct PlantDatabase {
     pub id: Uuid,
     pub date_created: NaiveDateTime,
     pub event_modified: NaiveDateTime,
+    pub cover_photo_id: Option<Uuid>,
+    pub species_id: Option<Uuid>,
 }
 
 pub async fn request_plant_demographic(
@@ -37,7 +39,7 @@ pub async fn request_plant_demographic(
         }
     };
     let result: PlantDatabase = match sqlx::query_as(&format!(
-        "SELECT id, date_created, event_modified FROM plants where id ='{}'",
+        "SELECT id, date_created, event_modified, cover_photo_id, species_id FROM plants where id ='{}'",
         plant_id
     ))
     .fetch_one(&pool)
@@ -61,7 +63,37 @@ pub async fn request_plant_demographic(
                 .unwrap()
         }
     };
-    let state_event = match get_last_event(uuid!(PLANT_STATE_ID), plant_id, pool).await {
+    let state_event = match get_last_event(uuid!(PLANT_STATE_ID), plant_id, pool.clone()).await {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap()
+        }
+    };
+
+    let moved_event = match get_last_event(uuid!(MOVED_EVENT_TYPE_ID), plant_id, pool.clone()).await {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap()
+        }
+    };
+
+    let tags = match get_plant_tags(plant_id, pool.clone()).await {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap()
+        }
+    };
+
+    let cover_photo = match get_cover_photo(plant_id, result.cover_photo_id, pool).await {
         Ok(ok) => ok,
         Err(err) => {
             return Response::builder()
@@ -88,17 +120,32 @@ pub async fn request_plant_demographic(
 
     let state = match state_event {
         Some(event) => {
-            let EventData::CustomEnum(state) = event.data else {
+            let EventData::Lifecycle(transition) = event.data else {
                 return Response::builder()
                     .status(StatusCode::INTERNAL_SERVER_ERROR)
                     .body(Body::from(
-                        "Name Event Instance is the wrong type".to_string(),
+                        "State Event Instance is the wrong type".to_string(),
                     ))
                     .unwrap();
             };
-            state
+            transition.to
         }
-        None => CustomEnum::plant_state(),
+        None => PlantState::default(),
+    };
+
+    let current_location = match moved_event {
+        Some(event) => {
+            let EventData::Location(location_id) = event.data else {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(
+                        "Moved Event Instance is the wrong type".to_string(),
+                    ))
+                    .unwrap();
+            };
+            Some(location_id)
+        }
+        None => None,
     };
 
     let plant: PlantDemographic = PlantDemographic::from_plant(
@@ -106,9 +153,15 @@ pub async fn request_plant_demographic(
             id: result.id,
             date_created: result.date_created,
             event_modified: result.event_modified,
+            // Demographics don't include extra data so there's no need to request it
+            extra_data: vec![],
         },
         name,
         state,
+        cover_photo,
+        current_location,
+        result.species_id,
+        tags,
     );
     Response::new(serde_json::ser::to_string(&plant).unwrap().into())
 }
@@ -8,6 +8,8 @@ use shared::plant::Plant;
 use sqlx::{prelude::FromRow, PgPool};
 use uuid::Uuid;
 
+use crate::app::plants::extra_data::get_plant_extra_data;
+
 /// Struct which represents an entire plant
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct PlantDatabase {
@@ -34,11 +36,23 @@ pub async fn request_plant(Path(plant_id): Path<String>, State(pool): State<PgPo
         }
     };
 
+    let extra_data = match get_plant_extra_data(result.id, pool).await {
+        Ok(extra_data) => extra_data,
+        Err(error) => {
+            return Response::new(
+                serde_json::ser::to_string(&error.to_string())
+                    .unwrap()
+                    .into(),
+            )
+        }
+    };
+
     println!("New Plant Registered: {:?}", result);
     let plant = Plant {
         id: result.id,
         date_created: result.date_created,
         event_modified: result.event_modified,
+        extra_data,
     };
     Response::new(serde_json::ser::to_string(&plant).unwrap().into())
 }
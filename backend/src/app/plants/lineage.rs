@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use shared::plant::{LineageNode, PlantLineage};
+use sqlx::{PgPool, Pool, Postgres};
+use uuid::Uuid;
+
+/// How many generations up and down the family tree are followed. Stops a bad parent link from looping forever
+static MAX_LINEAGE_DEPTH: i32 = 64;
+
+/// Gets the ancestors and descendants of a plant
+pub async fn get_plant_lineage(
+    Path(plant_id): Path<String>,
+    State(pool): State<PgPool>,
+) -> Response {
+    let plant_id = match Uuid::parse_str(&plant_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let lineage = match plant_lineage(plant_id, pool).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let serialize = match serde_json::to_string(&lineage) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+pub async fn plant_lineage(
+    plant_id: Uuid,
+    pool: Pool<Postgres>,
+) -> Result<PlantLineage, sqlx::Error> {
+    let ancestors: Vec<Uuid> = sqlx::query_scalar(
+        r#"WITH RECURSIVE ancestors AS (
+            SELECT parent_id AS id, 1 AS depth FROM plants WHERE id = $1 AND parent_id IS NOT NULL
+            UNION ALL
+            SELECT plants.parent_id, ancestors.depth + 1 FROM plants JOIN ancestors ON plants.id = ancestors.id
+            WHERE plants.parent_id IS NOT NULL AND ancestors.depth < $2
+        )
+        SELECT ancestors.id FROM ancestors JOIN plants ON plants.id = ancestors.id ORDER BY ancestors.depth"#,
+    )
+    .bind(plant_id)
+    .bind(MAX_LINEAGE_DEPTH)
+    .fetch_all(&pool)
+    .await?;
+
+    let descendants: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        r#"WITH RECURSIVE descendants AS (
+            SELECT id, parent_id, 1 AS depth FROM plants WHERE parent_id = $1
+            UNION ALL
+            SELECT plants.id, plants.parent_id, descendants.depth + 1 FROM plants JOIN descendants ON plants.parent_id = descendants.id
+            WHERE descendants.depth < $2
+        )
+        SELECT id, parent_id FROM descendants"#,
+    )
+    .bind(plant_id)
+    .bind(MAX_LINEAGE_DEPTH)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut children: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (child, parent) in descendants {
+        children.entry(parent).or_default().push(child);
+    }
+
+    Ok(PlantLineage {
+        plant_id,
+        ancestors,
+        descendants: build_lineage_nodes(plant_id, &children, MAX_LINEAGE_DEPTH),
+    })
+}
+
+fn build_lineage_nodes(
+    parent: Uuid,
+    children: &HashMap<Uuid, Vec<Uuid>>,
+    depth_remaining: i32,
+) -> Vec<LineageNode> {
+    if depth_remaining <= 0 {
+        return vec![];
+    }
+    children
+        .get(&parent)
+        .map(|plants| {
+            plants
+                .iter()
+                .map(|plant_id| LineageNode {
+                    plant_id: *plant_id,
+                    children: build_lineage_nodes(*plant_id, children, depth_remaining - 1),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
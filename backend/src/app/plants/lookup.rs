@@ -0,0 +1,219 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use serde::{Deserialize, Serialize};
+use shared::{
+    events::PLANT_NAME_EVENT_ID,
+    plant::plant_http::{PlantCandidate, RequestPlant, RequestPlantResponse},
+};
+use sqlx::{PgPool, Pool, Postgres, prelude::FromRow};
+use uuid::{Uuid, uuid};
+
+/// Names that are less similar than this to the requested name aren't offered as candidates
+pub static FUZZY_THRESHOLD: f64 = 0.5;
+/// A name this short only counts as contained in a longer one if it is at least a third of its length
+static MIN_CONTAINED_LENGTH: usize = 3;
+/// The most candidates returned for an ambiguous name
+static MAX_CANDIDATES: usize = 10;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PlantNameRow {
+    pub plant_id: Uuid,
+    pub name: Option<String>,
+}
+
+/// Finds a plant by its id or its name. Names that don't match exactly one plant return the closest plants instead
+pub async fn lookup_plant(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<RequestPlant>,
+) -> Response {
+    let plant_id = match &request {
+        RequestPlant::ByUuid(plant_id) => Some(*plant_id),
+        RequestPlant::ByName(_) => None,
+    };
+    let plants = match get_plant_names(plant_id, pool).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let response = match request {
+        RequestPlant::ByUuid(_) => match plants.into_iter().next() {
+            Some(plant) => RequestPlantResponse::Found(PlantCandidate {
+                plant_id: plant.plant_id,
+                name: plant.name,
+                score: 1.0,
+            }),
+            None => RequestPlantResponse::NotFound,
+        },
+        RequestPlant::ByName(name) => match_name(&name, plants),
+    };
+
+    let serialize = match serde_json::to_string(&response) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets the name of a plant, or of every plant when none is given
+pub async fn get_plant_names(
+    plant_id: Option<Uuid>,
+    pool: Pool<Postgres>,
+) -> Result<Vec<PlantNameRow>, sqlx::Error> {
+    // The name of a plant is the data of its latest name event
+    sqlx::query_as(
+        r#"SELECT plants.id AS plant_id, names.name FROM plants
+        LEFT JOIN (
+            SELECT DISTINCT ON (plant_id) plant_id, data->>'String' AS name FROM events
+            WHERE event_type_id = $1
+            ORDER BY plant_id, event_date DESC
+        ) names ON names.plant_id = plants.id
+        WHERE ($2::uuid IS NULL OR plants.id = $2)"#,
+    )
+    .bind(uuid!(PLANT_NAME_EVENT_ID))
+    .bind(plant_id)
+    .fetch_all(&pool)
+    .await
+}
+
+/// Matches a name against every plant, ignoring case. A single exact match is found, otherwise every exact match or else every close match is returned
+fn match_name(name: &str, plants: Vec<PlantNameRow>) -> RequestPlantResponse {
+    let name = name.trim().to_lowercase();
+
+    let mut candidates: Vec<PlantCandidate> = plants
+        .into_iter()
+        .filter_map(|plant| {
+            let score = similarity(&name, &plant.name.as_ref()?.trim().to_lowercase());
+            Some(PlantCandidate {
+                plant_id: plant.plant_id,
+                name: plant.name,
+                score,
+            })
+        })
+        .filter(|candidate| candidate.score >= FUZZY_THRESHOLD)
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let exact = candidates
+        .iter()
+        .take_while(|candidate| candidate.score >= 1.0)
+        .count();
+    match exact {
+        1 => RequestPlantResponse::Found(candidates.swap_remove(0)),
+        0 if candidates.is_empty() => RequestPlantResponse::NotFound,
+        0 => {
+            candidates.truncate(MAX_CANDIDATES);
+            RequestPlantResponse::Candidates(candidates)
+        }
+        _ => {
+            candidates.truncate(exact);
+            RequestPlantResponse::Candidates(candidates)
+        }
+    }
+}
+
+/// How similar two names are from 0 to 1. Names containing the other score highly, otherwise it is based on the number of edits between them
+pub fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let edit_score = 1.0 - edit_distance(&a, &b) as f64 / longest as f64;
+    let (shorter, longer) = match a.len() <= b.len() {
+        true => (&a, &b),
+        false => (&b, &a),
+    };
+    // Short names are contained in too many others to mean anything on their own
+    let meaningful = shorter.len() >= MIN_CONTAINED_LENGTH || shorter.len() * 3 >= longer.len();
+    let contains = !shorter.is_empty()
+        && meaningful
+        && longer
+            .windows(shorter.len())
+            .any(|window| window == shorter.as_slice());
+    // Containing the other name is a good match but never as good as an exact one
+    let contains_score = match contains {
+        true => 0.7 + 0.29 * shorter.len() as f64 / longer.len() as f64,
+        false => 0.0,
+    };
+    edit_score.max(contains_score)
+}
+
+/// The number of single character insertions, deletions or substitutions to turn one string into the other
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        text.chars().collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_edits() {
+        assert_eq!(edit_distance(&chars(""), &chars("")), 0);
+        assert_eq!(edit_distance(&chars(""), &chars("fern")), 4);
+        assert_eq!(edit_distance(&chars("fern"), &chars("fern")), 0);
+        assert_eq!(edit_distance(&chars("fern"), &chars("fen")), 1);
+        assert_eq!(edit_distance(&chars("fern"), &chars("ferns")), 1);
+        assert_eq!(edit_distance(&chars("fern"), &chars("fear")), 2);
+        assert_eq!(edit_distance(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(edit_distance(&chars("ivy"), &chars("ivy")), 0);
+    }
+
+    #[test]
+    fn similarity_of_exact_and_empty_names() {
+        assert_eq!(similarity("Monstera", "Monstera"), 1.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("", "Monstera"), 0.0);
+    }
+
+    #[test]
+    fn similarity_of_contained_names() {
+        let score = similarity("Mon", "Monstera");
+        assert!((0.7..1.0).contains(&score));
+        assert!(
+            similarity("Monstera", "Monstera deliciosa") > similarity("Mon", "Monstera deliciosa")
+        );
+        // Long enough relative to the other name even though it is short
+        assert!(similarity("Ro", "Rose") >= FUZZY_THRESHOLD);
+    }
+
+    #[test]
+    fn short_queries_do_not_match_everything() {
+        assert!(similarity("a", "Monstera") < FUZZY_THRESHOLD);
+        assert!(similarity("on", "Monstera") < FUZZY_THRESHOLD);
+        assert!(similarity("er", "Fiddle leaf fern") < FUZZY_THRESHOLD);
+    }
+
+    #[test]
+    fn similarity_of_misspelt_names() {
+        assert!(similarity("Monstara", "Monstera") >= FUZZY_THRESHOLD);
+        assert!(similarity("Pothos", "Monstera") < FUZZY_THRESHOLD);
+    }
+}
@@ -5,19 +5,43 @@ use axum::{
 
 use crate::{
     app::plants::{
-        delete::delete_plant, get_demographic::request_plant_demographic,
-        get_full_plant::request_plant, get_plant_list::get_plant_list, plant_new::new_plant,
+        delete::delete_plant,
+        extra_data::{get_extra_data_fields, new_extra_data_field, set_extra_data},
+        get_demographic::request_plant_demographic,
+        get_full_plant::request_plant,
+        get_plant_list::get_plant_list,
+        lineage::get_plant_lineage,
+        lookup::lookup_plant,
+        modify::modify_plant,
+        plant_new::new_plant,
+        search::search_plants,
+        tags::{get_all_tags, set_plant_tags},
+        timeline::get_timeline,
     },
     AppState,
 };
 
 pub mod delete;
+/// Module for user defined fields and their values on each plant
+pub mod extra_data;
 /// Module for creating plant demographics from the database
 pub mod get_demographic;
 /// Module for creating full Plant instances from the database
 pub mod get_full_plant;
 pub mod get_plant_list;
+/// Module for the family tree of propagated plants
+pub mod lineage;
+/// Module for finding plants by id or by a loosely matching name
+pub mod lookup;
+/// Module for applying batches of changes to a plant
+pub mod modify;
 pub mod plant_new;
+/// Module for searching and filtering plants
+pub mod search;
+/// Module for the tags given to plants
+pub mod tags;
+/// Module for the merged history of a plant
+pub mod timeline;
 
 pub fn rout_plant() -> Router<AppState> {
     Router::new()
@@ -26,4 +50,14 @@ pub fn rout_plant() -> Router<AppState> {
         .route("/delete/{id}", get(delete_plant))
         .route("/get-demographic/{id}", get(request_plant_demographic))
         .route("/get-plant-list/{timestamp}", get(get_plant_list))
+        .route("/extra-data/new-field", post(new_extra_data_field))
+        .route("/extra-data/fields", get(get_extra_data_fields))
+        .route("/extra-data/set", post(set_extra_data))
+        .route("/lineage/{id}", get(get_plant_lineage))
+        .route("/{id}/modify", post(modify_plant))
+        .route("/{id}/timeline", post(get_timeline))
+        .route("/request", post(lookup_plant))
+        .route("/search", post(search_plants))
+        .route("/tags/set", post(set_plant_tags))
+        .route("/tags/get-all", get(get_all_tags))
 }
@@ -0,0 +1,146 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use shared::{
+    DirtyCache,
+    events::{EventData, PLANT_NAME_EVENT_ID, PLANT_STATE_ID},
+    plant::{StateTransition, plant_http::ModifyPlant},
+};
+use sqlx::{PgPool, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::{Uuid, uuid};
+
+use crate::app::plants::get_demographic::request_plant_demographic;
+
+/// Applies a batch of changes to a plant, saving each one as an event. Either every change is saved or none are
+pub async fn modify_plant(
+    Path(plant_id): Path<String>,
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(modifications): axum::Json<Vec<ModifyPlant>>,
+) -> Response {
+    let plant_id = match Uuid::parse_str(&plant_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if modifications.is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("No changes were sent".to_string()))
+            .unwrap();
+    }
+
+    let now = Utc::now().naive_utc();
+    let result: Result<Result<Vec<(Uuid, NaiveDateTime)>, String>, sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
+
+        // Locking the plant keeps two batches from changing its state at the same time
+        let plant_exists: Option<Uuid> =
+            sqlx::query_scalar(r#"SELECT id FROM plants WHERE id = $1 FOR UPDATE"#)
+                .bind(plant_id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+        if plant_exists.is_none() {
+            return Ok(Err("Plant sent does not exist".to_string()));
+        }
+
+        let last_state: Option<Json<EventData>> = sqlx::query_scalar(
+            r#"SELECT data FROM events WHERE plant_id = $1 AND event_type_id = $2 ORDER BY event_date DESC LIMIT 1"#,
+        )
+        .bind(plant_id)
+        .bind(uuid!(PLANT_STATE_ID))
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let mut current_state = match last_state {
+            Some(Json(EventData::Lifecycle(transition))) => Some(transition.to),
+            _ => None,
+        };
+
+        let mut changed = vec![];
+        for (index, modification) in modifications.into_iter().enumerate() {
+            let (event_type, event_data) = match modification {
+                ModifyPlant::ChangeName(name) => {
+                    if name.trim().is_empty() {
+                        return Ok(Err("Plant name must not be empty".to_string()));
+                    }
+                    (
+                        uuid!(PLANT_NAME_EVENT_ID),
+                        EventData::String(name.trim().to_string()),
+                    )
+                }
+                ModifyPlant::ChangeState(state) => {
+                    let transition = StateTransition {
+                        from: current_state.clone(),
+                        to: state,
+                        note: None,
+                    };
+                    if let Some(problem) = transition.check(current_state.as_ref()) {
+                        return Ok(Err(problem));
+                    }
+                    current_state = Some(transition.to.clone());
+                    (uuid!(PLANT_STATE_ID), EventData::Lifecycle(transition))
+                }
+            };
+
+            // Later changes in the batch get later dates so they stay the latest event of their type
+            let event_date = now + Duration::microseconds(index as i64);
+            sqlx::query(
+                r#"INSERT INTO events(id, event_type_id, plant_id, data, event_date) VALUES ($1, $2, $3, $4, $5)"#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(event_type)
+            .bind(plant_id)
+            .bind(Json(event_data))
+            .bind(event_date)
+            .execute(&mut *transaction)
+            .await?;
+            changed.push((event_type, event_date));
+        }
+
+        sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = $1")
+            .bind(plant_id)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(Ok(changed))
+    }
+    .await;
+
+    let changed = match result {
+        Ok(Ok(changed)) => changed,
+        Ok(Err(problem)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(problem))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    for (event_type, event_date) in changed {
+        let _ = dirt_cache
+            .send(DirtyCache {
+                cache: shared::CacheType::Event(plant_id, event_type, event_date),
+            })
+            .await;
+    }
+
+    request_plant_demographic(Path(plant_id.to_string()), State(pool)).await
+}
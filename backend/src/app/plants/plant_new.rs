@@ -8,8 +8,10 @@ use axum::{
 };
 use chrono::Utc;
 use shared::{
-    events::{events_http::NewEvent, CustomEnum, PLANT_NAME_EVENT_ID, PLANT_STATE_ID},
-    plant::plant_http::NewPlant,
+    events::{
+        events_http::NewEvent, PLANT_NAME_EVENT_ID, PLANT_STATE_ID, PROPAGATED_EVENT_TYPE_ID,
+    },
+    plant::{plant_http::NewPlant, StateTransition},
     DirtyCache,
 };
 use sqlx::PgPool;
@@ -26,11 +28,35 @@ pub async fn new_plant(
 ) -> Response {
     let plant_uuid = Uuid::new_v4();
 
+    if let Some(parent_id) = new_plant.parent_id {
+        let parent_exists: bool =
+            match sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM plants WHERE id = $1)"#)
+                .bind(parent_id)
+                .fetch_one(&pool)
+                .await
+            {
+                Ok(ok) => ok,
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(err.to_string()))
+                        .unwrap()
+                }
+            };
+        if !parent_exists {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("Parent plant sent does not exist".to_string()))
+                .unwrap();
+        }
+    }
+
     let result = match sqlx::query(
-        "INSERT INTO plants(id, date_created, event_modified) VALUES ($1, $2, $2)",
+        "INSERT INTO plants(id, date_created, event_modified, parent_id) VALUES ($1, $2, $2, $3)",
     )
     .bind(plant_uuid.clone())
     .bind(Utc::now().naive_utc())
+    .bind(new_plant.parent_id)
     .execute(&pool)
     .await
     {
@@ -62,12 +88,27 @@ pub async fn new_plant(
         Json(NewEvent {
             event_type: uuid!(PLANT_STATE_ID),
             plant_id: plant_uuid,
-            event_data: shared::events::EventData::CustomEnum(CustomEnum::plant_state()),
+            event_data: shared::events::EventData::Lifecycle(StateTransition::initial()),
             event_date: Utc::now().naive_utc(),
         }),
     )
     .await;
 
+    // Record the cutting on the parent so it shows up in the parents history
+    if let Some(parent_id) = new_plant.parent_id {
+        let _ = new_event(
+            State(pool.clone()),
+            State(dirt_cache.clone()),
+            Json(NewEvent {
+                event_type: uuid!(PROPAGATED_EVENT_TYPE_ID),
+                plant_id: parent_id,
+                event_data: shared::events::EventData::Plant(plant_uuid),
+                event_date: Utc::now().naive_utc(),
+            }),
+        )
+        .await;
+    }
+
     let _ = dirt_cache
         .send(DirtyCache {
             cache: shared::CacheType::Plant(plant_uuid),
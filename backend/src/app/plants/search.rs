@@ -0,0 +1,246 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::{
+    collections::CollectionFilter,
+    events::{MOVED_EVENT_TYPE_ID, PLANT_NAME_EVENT_ID, WATERED_EVENT_ID},
+    plant::{
+        PlantState, normalize_tag,
+        plant_http::{PlantCursor, PlantSort, SearchPlants, SearchPlantsResponse},
+    },
+};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::{Uuid, uuid};
+
+use crate::app::collections::get_collection;
+
+static MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PlantSearchRow {
+    id: Uuid,
+    sort_name: String,
+    date_created: NaiveDateTime,
+    event_modified: NaiveDateTime,
+}
+
+impl PlantSearchRow {
+    fn cursor(&self, sort: PlantSort) -> PlantCursor {
+        match sort {
+            PlantSort::Name => PlantCursor::Name(self.sort_name.clone(), self.id),
+            PlantSort::Created => PlantCursor::Created(self.date_created, self.id),
+            PlantSort::LastEvent => PlantCursor::LastEvent(self.event_modified, self.id),
+        }
+    }
+}
+
+/// Searches every plant by name, notes and species along with filters on what is known about them
+pub async fn search_plants(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<SearchPlants>,
+) -> Response {
+    let limit = request.limit.clamp(1, MAX_PAGE_SIZE);
+    let query = request.query.as_deref().and_then(prefix_query);
+
+    let (order, after) = match request.sort {
+        PlantSort::Name => ("sort_name ASC, id ASC", "(sort_name, id) > ($10, $12)"),
+        PlantSort::Created => (
+            "date_created DESC, id DESC",
+            "(date_created, id) < ($11, $12)",
+        ),
+        PlantSort::LastEvent => (
+            "event_modified DESC, id DESC",
+            "(event_modified, id) < ($11, $12)",
+        ),
+    };
+    let (cursor_name, cursor_date, cursor_id) = match &request.cursor {
+        Some(PlantCursor::Name(name, id)) if request.sort == PlantSort::Name => {
+            (Some(name.clone()), None, Some(*id))
+        }
+        Some(PlantCursor::Created(date, id)) if request.sort == PlantSort::Created => {
+            (None, Some(*date), Some(*id))
+        }
+        Some(PlantCursor::LastEvent(date, id)) if request.sort == PlantSort::LastEvent => {
+            (None, Some(*date), Some(*id))
+        }
+        Some(_) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "Cursor sent is for a different sort".to_string(),
+                ))
+                .unwrap();
+        }
+        None => (None, None, None),
+    };
+
+    let tags: Vec<String> = request
+        .tags
+        .iter()
+        .filter_map(|tag| normalize_tag(tag))
+        .collect();
+
+    // Values in the collection filter are bound after the fixed parameters
+    let mut collection_params = vec![];
+    let collection_filter = match request.collection_id {
+        Some(collection_id) => {
+            let collection = match get_collection(collection_id, pool.clone()).await {
+                Ok(Some(result)) => result,
+                Ok(None) => {
+                    return Response::builder()
+                        .status(StatusCode::BAD_REQUEST)
+                        .body(Body::from("Collection sent does not exist".to_string()))
+                        .unwrap();
+                }
+                Err(err) => {
+                    return Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(err.to_string()))
+                        .unwrap();
+                }
+            };
+            match CollectionFilter::parse(&collection.filter) {
+                Ok(filter) => filter_sql(&filter, &mut collection_params, 16),
+                Err(problem) => {
+                    return Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from(problem))
+                        .unwrap();
+                }
+            }
+        }
+        None => "TRUE".to_string(),
+    };
+
+    // Everything a plant is searched by comes from its latest events so it is gathered up first
+    let query_string = format!(
+        r#"WITH plant_info AS (
+            SELECT plants.id, plants.date_created, plants.event_modified,
+                (SELECT data->>'String' FROM events
+                    WHERE events.plant_id = plants.id AND event_type_id = $1
+                    ORDER BY event_date DESC LIMIT 1) AS name,
+                plant_current_state(plants.id) AS state_name,
+                (SELECT (data->>'Location')::uuid FROM events
+                    WHERE events.plant_id = plants.id AND event_type_id = $3
+                    ORDER BY event_date DESC LIMIT 1) AS location_id,
+                (SELECT MAX(event_date) FROM events
+                    WHERE events.plant_id = plants.id AND event_type_id = $4) AS last_watered,
+                EXISTS (SELECT 1 FROM photos WHERE photos.plant_id = plants.id) AS has_photo,
+                (SELECT string_agg(body, ' ') FROM notes WHERE notes.plant_id = plants.id) AS notes,
+                concat_ws(' ', species.scientific_name, species.common_names::text) AS species,
+                (species.care -> 'watering_interval' ->> 'max_days')::int AS watering_max_days
+            FROM plants
+            LEFT JOIN species ON species.id = plants.species_id
+        ), plant_search AS (
+            SELECT *, lower(COALESCE(name, '')) AS sort_name FROM plant_info
+        )
+        SELECT id, sort_name, date_created, event_modified FROM plant_search
+        WHERE ($5::text IS NULL
+                OR to_tsvector('simple', concat_ws(' ', name, notes, species)) @@ to_tsquery('simple', $5))
+            AND ($6::text IS NULL OR state_name = $6)
+            AND ($7::bool IS NULL OR (state_name = ANY($2)) = $7)
+            AND ($8::uuid IS NULL OR location_id = $8)
+            AND ($9::timestamp IS NULL OR last_watered IS NULL OR last_watered < $9)
+            AND ($13::bool IS NULL OR has_photo = $13)
+            AND $15::text[] <@ ARRAY(SELECT tag FROM plant_tags WHERE plant_tags.plant_id = plant_search.id)
+            AND ({collection_filter})
+            AND ($12::uuid IS NULL OR {after})
+        ORDER BY {order}
+        LIMIT $14"#
+    );
+
+    let mut plants_query = sqlx::query_as(&query_string)
+        .bind(uuid!(PLANT_NAME_EVENT_ID))
+        .bind(&PlantState::RETIRED_NAMES[..])
+        .bind(uuid!(MOVED_EVENT_TYPE_ID))
+        .bind(uuid!(WATERED_EVENT_ID))
+        .bind(query)
+        .bind(&request.state)
+        .bind(request.retired)
+        .bind(request.location_id)
+        .bind(request.last_watered_before)
+        .bind(cursor_name)
+        .bind(cursor_date)
+        .bind(cursor_id)
+        .bind(request.has_photo)
+        .bind(limit)
+        .bind(tags);
+    for param in collection_params {
+        plants_query = plants_query.bind(param);
+    }
+    let plants: Vec<PlantSearchRow> = match plants_query.fetch_all(&pool).await {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // A short page means there is nothing left to request
+    let next_cursor = match plants.len() as i64 == limit {
+        true => plants.last().map(|plant| plant.cursor(request.sort)),
+        false => None,
+    };
+
+    let response = SearchPlantsResponse {
+        plants: plants.into_iter().map(|plant| plant.id).collect(),
+        next_cursor,
+    };
+
+    let serialize = match serde_json::to_string(&response) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Turns a collection filter into a condition on the searched plants. Values are added to the params and referenced starting from the given parameter number
+fn filter_sql(filter: &CollectionFilter, params: &mut Vec<String>, first_param: usize) -> String {
+    let mut param = |value: &str| {
+        params.push(value.to_string());
+        format!("${}::text", first_param + params.len() - 1)
+    };
+    match filter {
+        CollectionFilter::Tag(tag) => format!(
+            "EXISTS (SELECT 1 FROM plant_tags WHERE plant_tags.plant_id = plant_search.id AND tag = {})",
+            param(tag)
+        ),
+        CollectionFilter::State(state) => format!("state_name = {}", param(state)),
+        CollectionFilter::Retired => "state_name = ANY($2)".to_string(),
+        // Plants that have never been watered are overdue as soon as their species says how often to water
+        CollectionFilter::OverdueWatering => "(watering_max_days IS NOT NULL AND (last_watered IS NULL OR last_watered + make_interval(days => watering_max_days) < (now() AT TIME ZONE 'utc')))".to_string(),
+        CollectionFilter::HasPhoto => "has_photo".to_string(),
+        CollectionFilter::Not(filter) => format!("NOT ({})", filter_sql(filter, params, first_param)),
+        CollectionFilter::And(left, right) => format!(
+            "(({}) AND ({}))",
+            filter_sql(left, params, first_param),
+            filter_sql(right, params, first_param)
+        ),
+        CollectionFilter::Or(left, right) => format!(
+            "(({}) OR ({}))",
+            filter_sql(left, params, first_param),
+            filter_sql(right, params, first_param)
+        ),
+    }
+}
+
+/// Turns what the user typed into a query where every word has to match the start of a word. None if there were no words
+fn prefix_query(query: &str) -> Option<String> {
+    // Only letters and numbers are kept so nothing the user types can be read as query syntax
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("{}:*", word.to_lowercase()))
+        .collect();
+    match words.is_empty() {
+        true => None,
+        false => Some(words.join(" & ")),
+    }
+}
@@ -0,0 +1,124 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    plant::{normalize_tag, plant_http::SetPlantTags},
+};
+use sqlx::{PgPool, Pool, Postgres};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+/// Replaces the tags of a plant with the given tags
+pub async fn set_plant_tags(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(set_tags): axum::Json<SetPlantTags>,
+) -> Response {
+    let mut tags = vec![];
+    for tag in set_tags.tags.iter() {
+        let Some(tag) = normalize_tag(tag) else {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "Tags must not be empty or longer than 100 characters".to_string(),
+                ))
+                .unwrap();
+        };
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let result: Result<bool, sqlx::Error> = async {
+        let mut transaction = pool.begin().await?;
+        let plant_exists: bool =
+            sqlx::query_scalar(r#"SELECT EXISTS (SELECT 1 FROM plants WHERE id = $1)"#)
+                .bind(set_tags.plant_id)
+                .fetch_one(&mut *transaction)
+                .await?;
+        if !plant_exists {
+            return Ok(false);
+        }
+        sqlx::query("DELETE FROM plant_tags WHERE plant_id = $1")
+            .bind(set_tags.plant_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("INSERT INTO plant_tags(plant_id, tag) SELECT $1, UNNEST($2::text[])")
+            .bind(set_tags.plant_id)
+            .bind(&tags)
+            .execute(&mut *transaction)
+            .await?;
+        // Tags are part of the plant demographic so let clients know it changed
+        sqlx::query("UPDATE plants SET event_modified = $2 WHERE id = $1")
+            .bind(set_tags.plant_id)
+            .bind(Utc::now().naive_utc())
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(true)
+    }
+    .await;
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("Plant sent does not exist".to_string()))
+                .unwrap();
+        }
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    }
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Plant(set_tags.plant_id),
+        })
+        .await;
+
+    Response::new(Body::empty())
+}
+
+/// Gets every tag in use, in alphabetical order
+pub async fn get_all_tags(State(pool): State<PgPool>) -> Response {
+    let tags: Vec<String> =
+        match sqlx::query_scalar(r#"SELECT DISTINCT tag FROM plant_tags ORDER BY tag"#)
+            .fetch_all(&pool)
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from(err.to_string()))
+                    .unwrap();
+            }
+        };
+
+    let serialize = match serde_json::to_string(&tags) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
+
+/// Gets the tags of a plant in alphabetical order
+pub async fn get_plant_tags(
+    plant_id: Uuid,
+    pool: Pool<Postgres>,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(r#"SELECT tag FROM plant_tags WHERE plant_id = $1 ORDER BY tag"#)
+        .bind(plant_id)
+        .fetch_all(&pool)
+        .await
+}
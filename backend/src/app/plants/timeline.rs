@@ -0,0 +1,162 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Response,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use shared::{
+    events::{EventData, EventInstance, PHOTO_EVENT_TYPE_ID},
+    notes::Note,
+    photos::Photo,
+    plant::plant_http::{GetTimeline, GetTimelineResponse, TimelineCursor, TimelineEntry},
+};
+use sqlx::{PgPool, prelude::FromRow, types::Json};
+use uuid::{Uuid, uuid};
+
+/// The most entries that will be returned in a single page
+static MAX_PAGE_SIZE: i64 = 100;
+
+/// A row of the merged timeline. Columns that don't belong to the kind of the row are null
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct TimelineRow {
+    pub kind: String,
+    pub id: Uuid,
+    pub entry_date: NaiveDateTime,
+    pub event_type_id: Option<Uuid>,
+    pub data: Option<Json<EventData>>,
+    pub event_id: Option<Uuid>,
+    pub body: Option<String>,
+    pub date_edited: Option<NaiveDateTime>,
+    pub file_location: Option<String>,
+}
+
+impl TimelineRow {
+    pub fn into_entry(self, plant_id: Uuid) -> Option<TimelineEntry> {
+        match self.kind.as_str() {
+            "Event" => Some(TimelineEntry::Event(EventInstance {
+                id: self.id,
+                event_type_id: self.event_type_id?,
+                plant_id,
+                data: self.data?.0,
+                event_date: self.entry_date,
+            })),
+            "Note" => Some(TimelineEntry::Note(Note {
+                id: self.id,
+                plant_id,
+                event_id: self.event_id,
+                body: self.body?,
+                date_created: self.entry_date,
+                date_edited: self.date_edited?,
+            })),
+            "Photo" => Some(TimelineEntry::Photo(Photo {
+                id: self.id,
+                plant_id,
+                photo_date: self.entry_date,
+                file_location: self.file_location?,
+                thumbnail_location: Photo::thumbnail_location(self.id),
+            })),
+            _ => None,
+        }
+    }
+}
+
+/// Returns a page of every event, note and photo of a plant merged together, newest first
+pub async fn get_timeline(
+    Path(plant_id): Path<String>,
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetTimeline>,
+) -> Response {
+    let plant_id = match Uuid::parse_str(&plant_id) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let limit = request.limit.clamp(1, MAX_PAGE_SIZE);
+    let kinds: Vec<String> = request
+        .kinds
+        .iter()
+        .map(|kind| format!("{:?}", kind))
+        .collect();
+    let (cursor_date, cursor_id) = match &request.cursor {
+        Some(cursor) => (Some(cursor.date), Some(cursor.id)),
+        None => (None, None),
+    };
+
+    // Photo events are left out since the photo itself is part of the timeline
+    let rows: Vec<TimelineRow> = match sqlx::query_as(
+        r#"WITH entries AS (
+            SELECT 'Event' AS kind, id, event_date AS entry_date, event_type_id, data::jsonb AS data,
+                NULL::uuid AS event_id, NULL::text AS body, NULL::timestamp AS date_edited, NULL::varchar AS file_location
+            FROM events WHERE plant_id = $1 AND event_type_id <> $2
+            UNION ALL
+            SELECT 'Event', id, event_date, event_type_id, data::jsonb, NULL, NULL, NULL, NULL
+            FROM events_unique WHERE plant_id = $1 AND event_type_id <> $2
+            UNION ALL
+            SELECT 'Note', id, date_created, NULL, NULL, event_id, body, date_edited, NULL
+            FROM notes WHERE plant_id = $1
+            UNION ALL
+            SELECT 'Photo', id, photo_date, NULL, NULL, NULL, NULL, NULL, file_location
+            FROM photos WHERE plant_id = $1
+        )
+        SELECT * FROM entries
+        WHERE (cardinality($3::text[]) = 0 OR kind = ANY($3))
+            AND (kind <> 'Event' OR cardinality($4::uuid[]) = 0 OR event_type_id = ANY($4))
+            AND ($5::timestamp IS NULL OR (entry_date, id) < ($5, $6::uuid))
+        ORDER BY entry_date DESC, id DESC
+        LIMIT $7"#,
+    )
+    .bind(plant_id)
+    .bind(uuid!(PHOTO_EVENT_TYPE_ID))
+    .bind(&kinds)
+    .bind(&request.event_types)
+    .bind(cursor_date)
+    .bind(cursor_id)
+    .bind(limit)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // A short page means there is nothing left to request
+    let next_cursor = match rows.len() as i64 == limit {
+        true => rows.last().map(|row| TimelineCursor {
+            date: row.entry_date,
+            id: row.id,
+        }),
+        false => None,
+    };
+
+    let response = GetTimelineResponse {
+        entries: rows
+            .into_iter()
+            .filter_map(|row| row.into_entry(plant_id))
+            .collect(),
+        next_cursor,
+    };
+
+    let serialize = match serde_json::to_string(&response) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
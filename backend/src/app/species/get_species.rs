@@ -0,0 +1,62 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use serde::{Deserialize, Serialize};
+use shared::species::{CareProfile, Species};
+use sqlx::{PgPool, prelude::FromRow, types::Json};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct SpeciesRow {
+    pub id: Uuid,
+    pub scientific_name: String,
+    pub common_names: Json<Vec<String>>,
+    pub family: String,
+    pub care: Json<CareProfile>,
+    pub user_defined: bool,
+}
+
+impl SpeciesRow {
+    pub fn into_species(self) -> Species {
+        Species {
+            id: self.id,
+            scientific_name: self.scientific_name,
+            common_names: self.common_names.0,
+            family: self.family,
+            care: self.care.0,
+            user_defined: self.user_defined,
+        }
+    }
+}
+
+/// Gets every species in the catalog
+pub async fn get_all_species(State(pool): State<PgPool>) -> Response {
+    let species: Vec<SpeciesRow> = match sqlx::query_as(
+        r#"SELECT id, scientific_name, common_names, family, care, user_defined FROM species ORDER BY scientific_name"#,
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let species: Vec<Species> = species
+        .into_iter()
+        .map(|species| species.into_species())
+        .collect();
+
+    let serialize = match serde_json::to_string(&species) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
@@ -0,0 +1,20 @@
+use axum::{
+    Router,
+    routing::{get, post},
+};
+
+use crate::AppState;
+
+pub use seed::seed_species;
+
+mod get_species;
+mod new_species;
+mod seed;
+mod set_plant_species;
+
+pub fn rout_species() -> Router<AppState> {
+    Router::new()
+        .route("/new", post(new_species::new_species))
+        .route("/get-all", get(get_species::get_all_species))
+        .route("/set-plant", post(set_plant_species::set_plant_species))
+}
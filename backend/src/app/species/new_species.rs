@@ -0,0 +1,98 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{
+    DirtyCache,
+    species::{Species, species_http::NewSpecies},
+};
+use sqlx::{PgPool, types::Json};
+use tokio::sync::mpsc::Sender;
+use uuid::Uuid;
+
+/// Adds a user defined species to the catalog and returns it to the client
+pub async fn new_species(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(new_species): axum::Json<NewSpecies>,
+) -> Response {
+    if new_species.scientific_name.trim().is_empty() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from("Scientific name must not be empty".to_string()))
+            .unwrap();
+    }
+
+    if let Some(interval) = &new_species.care.watering_interval {
+        if interval.min_days > interval.max_days {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "Watering interval minimum must not be more than the maximum".to_string(),
+                ))
+                .unwrap();
+        }
+    }
+
+    if let Some(temperature) = &new_species.care.temperature {
+        if temperature.min_celsius > temperature.max_celsius {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(
+                    "Temperature range minimum must not be more than the maximum".to_string(),
+                ))
+                .unwrap();
+        }
+    }
+
+    let species = Species {
+        id: Uuid::new_v4(),
+        scientific_name: new_species.scientific_name.trim().to_string(),
+        common_names: new_species
+            .common_names
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        family: new_species.family.trim().to_string(),
+        care: new_species.care,
+        user_defined: true,
+    };
+
+    let _result = match sqlx::query(
+        "INSERT INTO species(id, scientific_name, common_names, family, care, user_defined, date_created) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(species.id)
+    .bind(&species.scientific_name)
+    .bind(Json(&species.common_names))
+    .bind(&species.family)
+    .bind(Json(&species.care))
+    .bind(species.user_defined)
+    .bind(Utc::now().naive_utc())
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Species(species.id),
+        })
+        .await;
+
+    let serialize = match serde_json::to_string(&species) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+    Response::new(Body::from(serialize))
+}
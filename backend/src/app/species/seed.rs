@@ -0,0 +1,31 @@
+use chrono::Utc;
+use shared::species::Species;
+use sqlx::{Pool, Postgres, types::Json};
+
+/// The catalog of common houseplants that ships with the app
+static BUNDLED_SPECIES: &str = include_str!("../../../data/species.json");
+
+/// Adds every bundled species that isn't in the catalog yet. Species the catalog already has are left alone
+pub async fn seed_species(pool: &Pool<Postgres>) -> Result<u64, sqlx::Error> {
+    let species: Vec<Species> =
+        serde_json::from_str(BUNDLED_SPECIES).map_err(|err| sqlx::Error::Decode(Box::new(err)))?;
+
+    let mut seeded = 0;
+    for species in species {
+        seeded += sqlx::query(
+            "INSERT INTO species(id, scientific_name, common_names, family, care, user_defined, date_created) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT DO NOTHING",
+        )
+        .bind(species.id)
+        .bind(&species.scientific_name)
+        .bind(Json(&species.common_names))
+        .bind(&species.family)
+        .bind(Json(&species.care))
+        .bind(false)
+        .bind(Utc::now().naive_utc())
+        .execute(pool)
+        .await?
+        .rows_affected();
+    }
+
+    Ok(seeded)
+}
@@ -0,0 +1,47 @@
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::Utc;
+use shared::{DirtyCache, species::species_http::SetPlantSpecies};
+use sqlx::PgPool;
+use tokio::sync::mpsc::Sender;
+
+/// Links a plant to a species in the catalog
+pub async fn set_plant_species(
+    State(pool): State<PgPool>,
+    State(dirt_cache): State<Sender<DirtyCache>>,
+    axum::Json(set_species): axum::Json<SetPlantSpecies>,
+) -> Response {
+    let result = match sqlx::query(
+        r#"UPDATE plants SET species_id = $2, event_modified = $3 WHERE id = $1 AND ($2 IS NULL OR EXISTS (SELECT 1 FROM species WHERE species.id = $2))"#,
+    )
+    .bind(set_species.plant_id)
+    .bind(set_species.species_id)
+    .bind(Utc::now().naive_utc())
+    .execute(&pool)
+    .await
+    {
+        Ok(ok) => ok,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    if result.rows_affected() == 0 {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(
+                "Plant or species sent does not exist".to_string(),
+            ))
+            .unwrap();
+    }
+
+    let _ = dirt_cache
+        .send(DirtyCache {
+            cache: shared::CacheType::Plant(set_species.plant_id),
+        })
+        .await;
+
+    Response::new(Body::empty())
+}
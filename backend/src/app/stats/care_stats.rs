@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use axum::{body::Body, extract::State, http::StatusCode, response::Response};
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{
+    events::{PLANT_NAME_EVENT_ID, PLANT_STATE_ID, PROPAGATED_EVENT_TYPE_ID, WATERED_EVENT_ID},
+    plant::PlantState,
+    stats::{
+        CareStats, DayCount, HEATMAP_DAYS, MonthCount, NeglectedPlant, stats_http::GetCareStats,
+    },
+};
+use sqlx::{PgPool, prelude::FromRow};
+use uuid::{Uuid, uuid};
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct WateringRow {
+    pub plant_id: Uuid,
+    pub event_date: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct PlantStatsRow {
+    pub id: Uuid,
+    pub date_created: NaiveDateTime,
+    pub name: Option<String>,
+    pub state_name: Option<String>,
+    /// When the plant moved into its current state
+    pub state_date: Option<NaiveDateTime>,
+    pub watering_min_days: Option<i32>,
+    pub watering_max_days: Option<i32>,
+}
+
+impl PlantStatsRow {
+    fn is_retired(&self) -> bool {
+        self.state_name
+            .as_ref()
+            .and_then(|name| PlantState::from_name(name, String::new()))
+            .map(|state| state.is_retired())
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow)]
+pub struct DayRow {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+/// Computes care statistics from the event history of a plant, or of every plant when none is given
+pub async fn get_care_stats(
+    State(pool): State<PgPool>,
+    axum::Json(request): axum::Json<GetCareStats>,
+) -> Response {
+    let waterings: Vec<WateringRow> = match sqlx::query_as(
+        r#"SELECT plant_id, event_date FROM events
+        WHERE event_type_id = $1 AND ($2::uuid IS NULL OR plant_id = $2)
+        ORDER BY plant_id, event_date"#,
+    )
+    .bind(uuid!(WATERED_EVENT_ID))
+    .bind(request.plant_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let plants: Vec<PlantStatsRow> = match sqlx::query_as(
+        r#"SELECT plants.id, plants.date_created,
+            (SELECT data->>'String' FROM events
+                WHERE events.plant_id = plants.id AND event_type_id = $1
+                ORDER BY event_date DESC LIMIT 1) AS name,
+            plant_current_state(plants.id) AS state_name,
+            (SELECT event_date FROM events
+                WHERE events.plant_id = plants.id AND event_type_id = $2
+                ORDER BY event_date DESC LIMIT 1) AS state_date,
+            (species.care -> 'watering_interval' ->> 'min_days')::int AS watering_min_days,
+            (species.care -> 'watering_interval' ->> 'max_days')::int AS watering_max_days
+        FROM plants
+        LEFT JOIN species ON species.id = plants.species_id
+        WHERE $3::uuid IS NULL OR plants.id = $3"#,
+    )
+    .bind(uuid!(PLANT_NAME_EVENT_ID))
+    .bind(uuid!(PLANT_STATE_ID))
+    .bind(request.plant_id)
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    // Only care counts towards streaks and the heatmap, the name, state and propagation of a plant are bookkeeping
+    let days: Vec<DayRow> = match sqlx::query_as(
+        r#"SELECT event_date::date AS day, count(*) AS count FROM (
+            SELECT plant_id, event_type_id, event_date FROM events
+            UNION ALL
+            SELECT plant_id, event_type_id, event_date FROM events_unique
+        ) AS all_events
+        WHERE ($1::uuid IS NULL OR plant_id = $1) AND NOT (event_type_id = ANY($2))
+        GROUP BY day
+        ORDER BY day"#,
+    )
+    .bind(request.plant_id)
+    .bind(
+        &[
+            uuid!(PLANT_NAME_EVENT_ID),
+            uuid!(PLANT_STATE_ID),
+            uuid!(PROPAGATED_EVENT_TYPE_ID),
+        ][..],
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    let now = Utc::now().naive_utc();
+    let (average_watering_interval, interval_adherence) = watering_intervals(&waterings, &plants);
+    let (current_streak, longest_streak) = streaks(&days, now.date());
+    let heatmap_start = now.date() - Duration::days(HEATMAP_DAYS);
+
+    let stats = CareStats {
+        plant_id: request.plant_id,
+        waterings_per_month: count_by_month(
+            waterings.iter().map(|watering| watering.event_date.date()),
+        ),
+        average_watering_interval,
+        interval_adherence,
+        longest_neglected: longest_neglected(&waterings, &plants, now),
+        plants_added: count_by_month(plants.iter().map(|plant| plant.date_created.date())),
+        plants_retired: count_by_month(
+            plants
+                .iter()
+                .filter(|plant| plant.is_retired())
+                .filter_map(|plant| plant.state_date.map(|date| date.date())),
+        ),
+        current_streak,
+        longest_streak,
+        heatmap: days
+            .iter()
+            .filter(|day| day.day > heatmap_start)
+            .map(|day| DayCount {
+                day: day.day,
+                count: day.count,
+            })
+            .collect(),
+    };
+
+    let serialize = match serde_json::to_string(&stats) {
+        Ok(result) => result,
+        Err(err) => {
+            return Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(err.to_string()))
+                .unwrap();
+        }
+    };
+
+    Response::new(Body::from(serialize))
+}
+
+/// Counts the dates in each month from the earliest month to the latest, months without any dates are counted as 0
+fn count_by_month(dates: impl Iterator<Item = NaiveDate>) -> Vec<MonthCount> {
+    let mut counts: HashMap<NaiveDate, i64> = HashMap::new();
+    for date in dates {
+        let month = date.with_day(1).unwrap_or(date);
+        *counts.entry(month).or_default() += 1;
+    }
+
+    let (Some(first), Some(last)) = (counts.keys().min(), counts.keys().max()) else {
+        return vec![];
+    };
+    let mut months = vec![];
+    let mut month = *first;
+    while month <= *last {
+        months.push(MonthCount {
+            month,
+            count: counts.get(&month).copied().unwrap_or(0),
+        });
+        month = match month.checked_add_months(Months::new(1)) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    months
+}
+
+/// Returns the average days between waterings of the same plant, and the share of those gaps that fit the interval
+/// the species of the plant recommends. Waterings must be ordered by plant and then date
+fn watering_intervals(
+    waterings: &[WateringRow],
+    plants: &[PlantStatsRow],
+) -> (Option<f64>, Option<f64>) {
+    let intervals: HashMap<Uuid, (i32, i32)> = plants
+        .iter()
+        .filter_map(
+            |plant| match (plant.watering_min_days, plant.watering_max_days) {
+                (Some(min_days), Some(max_days)) => Some((plant.id, (min_days, max_days))),
+                _ => None,
+            },
+        )
+        .collect();
+
+    let mut total_days = 0.0;
+    let mut gaps = 0;
+    let mut checked_gaps = 0;
+    let mut on_time_gaps = 0;
+    for pair in waterings.windows(2) {
+        if pair[0].plant_id != pair[1].plant_id {
+            continue;
+        }
+        let days = pair[1]
+            .event_date
+            .signed_duration_since(pair[0].event_date)
+            .num_seconds() as f64
+            / 86400.0;
+        total_days += days;
+        gaps += 1;
+
+        if let Some((min_days, max_days)) = intervals.get(&pair[0].plant_id) {
+            checked_gaps += 1;
+            let rounded = days.round() as i32;
+            if rounded >= *min_days && rounded <= *max_days {
+                on_time_gaps += 1;
+            }
+        }
+    }
+
+    let average = match gaps {
+        0 => None,
+        _ => Some(total_days / gaps as f64),
+    };
+    let adherence = match checked_gaps {
+        0 => None,
+        _ => Some(on_time_gaps as f64 / checked_gaps as f64),
+    };
+    (average, adherence)
+}
+
+/// Finds the plant still in the users care that has gone the longest without being watered.
+/// Plants that have never been watered count from when they were added
+fn longest_neglected(
+    waterings: &[WateringRow],
+    plants: &[PlantStatsRow],
+    now: NaiveDateTime,
+) -> Option<NeglectedPlant> {
+    let mut last_watered: HashMap<Uuid, NaiveDateTime> = HashMap::new();
+    for watering in waterings {
+        let last = last_watered
+            .entry(watering.plant_id)
+            .or_insert(watering.event_date);
+        if watering.event_date > *last {
+            *last = watering.event_date;
+        }
+    }
+
+    plants
+        .iter()
+        .filter(|plant| !plant.is_retired())
+        .map(|plant| {
+            let last_watered = last_watered.get(&plant.id).copied();
+            NeglectedPlant {
+                plant_id: plant.id,
+                name: plant.name.clone(),
+                last_watered,
+                days_without_water: now
+                    .signed_duration_since(last_watered.unwrap_or(plant.date_created))
+                    .num_days(),
+            }
+        })
+        .max_by_key(|plant| plant.days_without_water)
+}
+
+/// Returns the current and the longest run of days in a row that had events. Days must be in order.
+/// The current streak isn't broken until a whole day goes by without an event, so a streak that ended yesterday still counts
+fn streaks(days: &[DayRow], today: NaiveDate) -> (i64, i64) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if previous + Duration::days(1) == day.day => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(day.day);
+    }
+
+    let current = match previous {
+        Some(last) if last >= today - Duration::days(1) => run,
+        _ => 0,
+    };
+    (current, longest)
+}
@@ -0,0 +1,9 @@
+use axum::{Router, routing::post};
+
+use crate::AppState;
+
+mod care_stats;
+
+pub fn rout_stats() -> Router<AppState> {
+    Router::new().route("/care", post(care_stats::get_care_stats))
+}
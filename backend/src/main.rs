@@ -1,7 +1,7 @@
 use axum::{extract::FromRef, routing::get, Router};
 
 use futures_util::lock::Mutex;
-use shared::DirtyCache;
+use shared::{archive::MAX_ARCHIVE_SIZE, DirtyCache};
 use tokio::sync::broadcast;
 use tokio::sync::mpsc::{self, Sender};
 use tokio::time::sleep;
@@ -14,13 +14,17 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 mod db;
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
 use std::io::Error;
-use std::{sync::Arc, time::Duration};
+use std::{path::Path, sync::Arc, time::Duration};
 
 mod app;
 mod static_support;
 use static_support::using_serve_dir;
 
-use crate::app::{dirty_cache_sse_handler, rout_event, rout_plant, route_photos};
+use crate::app::{
+    build_archive, calendar_ics, dirty_cache_sse_handler, restore_archive, rout_archive,
+    rout_calendar, rout_collection, rout_csv_import, rout_event, rout_location, rout_note,
+    rout_plant, rout_species, rout_stats, route_photos, seed_species,
+};
 
 // the application state
 #[derive(Clone)]
@@ -75,6 +79,21 @@ async fn main() {
             failed_migration
         ),
     }
+    match seed_species(&pool).await {
+        Ok(seeded) => println!("Seeded {} species into the catalog", seeded),
+        Err(err) => panic!("Failed to seed the species catalog with error: {}", err),
+    }
+
+    // `backend export <file>` and `backend import <file>` run against the database and exit instead of serving
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, command, path] = args.as_slice() {
+        match run_command(command, path, pool.clone()).await {
+            Ok(message) => println!("{}", message),
+            Err(err) => panic!("Failed to {} archive {} with error: {}", command, path, err),
+        }
+        return;
+    }
+
     let (sender, receiver) = mpsc::channel(250);
 
     let state = AppState {
@@ -87,6 +106,15 @@ async fn main() {
         .nest("/plants", rout_plant())
         .nest("/events", rout_event())
         .nest("/photos", route_photos())
+        .nest("/locations", rout_location())
+        .nest("/notes", rout_note())
+        .nest("/species", rout_species())
+        .nest("/collections", rout_collection())
+        .nest("/stats", rout_stats())
+        .nest("/calendar", rout_calendar())
+        .nest("/archive", rout_archive())
+        .nest("/csv-import", rout_csv_import())
+        .route("/calendar.ics", get(calendar_ics))
         .merge(using_serve_dir())
         .route("/dirty-cache", get(dirty_cache_sse_handler))
         .with_state(state)
@@ -95,6 +123,62 @@ async fn main() {
     let _ = serve(app, 8080).await;
 }
 
+/// Runs a command given on the command line, returns what to print when it finishes
+async fn run_command(command: &str, path: &str, pool: Pool<Postgres>) -> Result<String, String> {
+    match command {
+        "export" => {
+            let size = build_archive(pool, Path::new(path))
+                .await
+                .map_err(|err| err.to_string())?;
+            if size > MAX_ARCHIVE_SIZE as u64 {
+                println!(
+                    "The archive is larger than the {} bytes the import endpoint accepts, restore it with the import command",
+                    MAX_ARCHIVE_SIZE
+                );
+            }
+            Ok(format!("Exported {} bytes to {}", size, path))
+        }
+        "import" => {
+            let file = std::fs::File::open(path).map_err(|err| err.to_string())?;
+            let (report, _dirty_caches) = restore_archive(file, pool)
+                .await
+                .map_err(|err| err.to_string())?;
+            for rejected in &report.rejected_events {
+                println!(
+                    "Rejected event {} of plant {}: {}",
+                    rejected.event_id, rejected.plant_id, rejected.reason
+                );
+            }
+            for problem in &report.problems {
+                println!("{}", problem);
+            }
+            Ok(format!(
+                "Imported {}: {} event types added, {} merged. {} plants added, {} existing. {} events added, {} existing, {} rejected. {} photos added, {} existing. {} species, {} locations, {} extra data values, {} notes, {} tags and {} collections added",
+                path,
+                report.event_types_added,
+                report.event_types_merged,
+                report.plants_added,
+                report.plants_existing,
+                report.events_added,
+                report.events_existing,
+                report.rejected_events.len(),
+                report.photos_added,
+                report.photos_existing,
+                report.species_added,
+                report.locations_added,
+                report.extra_data_added,
+                report.notes_added,
+                report.tags_added,
+                report.collections_added
+            ))
+        }
+        _ => Err(format!(
+            "Unknown command {}, expected export or import",
+            command
+        )),
+    }
+}
+
 fn rout_main() -> Router<AppState> {
     Router::new()
         .route("/health_check", get(health_check_handler))
@@ -0,0 +1,123 @@
+//! Recent activity across every plant in the household
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::events::events_http::{ActivityCursor, ActivityItem, GetActivity, GetActivityResponse};
+use thaw::Button;
+
+use crate::{
+    components::plant_components::event::local_time_ago_humanized,
+    data_storage::DirtyManagerContext,
+    server_helpers::post_request,
+};
+
+static PAGINATION: i64 = 10;
+
+/// The latest events logged for any plant, newest first. New events show up as the server reports them
+#[component]
+pub fn ActivityFeedComponent() -> impl IntoView {
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    let items = RwSignal::new(Vec::<ActivityItem>::new());
+    let cursor = RwSignal::new(None::<ActivityCursor>);
+    let loaded_once = StoredValue::new(false);
+
+    let load_more = move |_| {
+        let request = GetActivity {
+            cursor: cursor.get_untracked(),
+            limit: PAGINATION,
+        };
+        spawn_local(async move {
+            let Some(response) = get_activity(request).await else {
+                return;
+            };
+            items.update(|items| items.extend(response.items));
+            cursor.set(response.next_cursor);
+        });
+    };
+
+    // The first page is requested again whenever an event is logged and merged into what is already shown
+    // so older pages that have been loaded stay in place
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.events.len());
+        let request = GetActivity {
+            cursor: None,
+            limit: PAGINATION,
+        };
+        spawn_local(async move {
+            let Some(response) = get_activity(request).await else {
+                return;
+            };
+            items.update(|items| {
+                let new_items: Vec<ActivityItem> = response
+                    .items
+                    .into_iter()
+                    .filter(|item| items.iter().all(|shown| shown.event.id != item.event.id))
+                    .collect();
+                items.extend(new_items);
+                items.sort_by(|a, b| {
+                    (b.event.event_date, b.event.id).cmp(&(a.event.event_date, a.event.id))
+                });
+            });
+            if !loaded_once.get_value() {
+                loaded_once.set_value(true);
+                cursor.set(response.next_cursor);
+            }
+        });
+    });
+
+    view! {
+        <div class="bg-(--card) p-2 m-3 rounded-(--radius) flex flex-col">
+            <h3 class="text-secondary text-lg font-bold">"Recent activity"</h3>
+            <For
+                each=move || items.get()
+                key=|item| item.event.id
+                children=|item| {
+                    view! {
+                        <div class="flex flex-row justify-between gap-3 p-1 border-b-1 border-border">
+                            <a href=format!("/plant/{}/view", item.event.plant_id)>
+                                {item.summary()}
+                            </a>
+                            <p class="text-sm text-muted-foreground">
+                                {local_time_ago_humanized(item.event.event_date)}
+                            </p>
+                        </div>
+                    }
+                }
+            />
+            {move || match items.get().is_empty() {
+                true => view! { <p>"Nothing has happened yet..."</p> }.into_any(),
+                false => view! {}.into_any(),
+            }}
+            {move || {
+                cursor.get().is_some().then(|| view! { <Button on_click=load_more>"Show more"</Button> })
+            }}
+        </div>
+    }
+}
+
+async fn get_activity(request: GetActivity) -> Option<GetActivityResponse> {
+    let request_builder = post_request("/events/activity");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<GetActivityResponse>(&body_text).ok()
+}
@@ -0,0 +1,215 @@
+//! Month and week calendar of logged events and upcoming scheduled care
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::calendar::calendar_http::{GetCalendar, GetCalendarResponse};
+use thaw::{Button, Select};
+
+use crate::{data_storage::DirtyManagerContext, server_helpers::post_request};
+
+static WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[component]
+pub fn CalendarComponent() -> impl IntoView {
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    let today = Utc::now().naive_utc().date();
+    let view_kind = RwSignal::new("Month".to_string());
+    // Any date inside of the month or week being shown
+    let anchor = RwSignal::new(today);
+    let calendar = RwSignal::new(None::<GetCalendarResponse>);
+
+    // The shown days always start on a monday, months are padded out to whole weeks
+    let days = Memo::new(move |_| {
+        let (first, last) = match view_kind.get().as_str() {
+            "Week" => (anchor.get(), anchor.get()),
+            _ => {
+                let first = anchor.get().with_day(1).unwrap_or(anchor.get());
+                let last =
+                    first.checked_add_months(Months::new(1)).unwrap_or(first) - Duration::days(1);
+                (first, last)
+            }
+        };
+        let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+        let weeks = last.signed_duration_since(start).num_days() / 7 + 1;
+        (0..weeks * 7)
+            .map(|offset| start + Duration::days(offset))
+            .collect::<Vec<NaiveDate>>()
+    });
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.events.len());
+        let days = days.get();
+        let (Some(first), Some(last)) = (days.first(), days.last()) else {
+            return;
+        };
+        let request = GetCalendar {
+            start: first.and_time(Default::default()),
+            end: (*last + Duration::days(1)).and_time(Default::default()),
+        };
+        spawn_local(async move {
+            if let Some(response) = get_calendar(request).await {
+                calendar.set(Some(response));
+            }
+        });
+    });
+
+    let step = move |forward: bool| {
+        let direction = if forward { 1 } else { -1 };
+        anchor.update(|anchor| {
+            *anchor = match view_kind.get_untracked().as_str() {
+                "Week" => *anchor + Duration::days(7 * direction),
+                _ => {
+                    let first = anchor.with_day(1).unwrap_or(*anchor);
+                    match forward {
+                        true => first.checked_add_months(Months::new(1)),
+                        false => first.checked_sub_months(Months::new(1)),
+                    }
+                    .unwrap_or(first)
+                }
+            }
+        });
+    };
+
+    let title = move || match view_kind.get().as_str() {
+        "Week" => {
+            let days = days.get();
+            match (days.first(), days.last()) {
+                (Some(first), Some(last)) => {
+                    format!("{} - {}", first.format("%B %d"), last.format("%B %d %Y"))
+                }
+                _ => "".to_string(),
+            }
+        }
+        _ => anchor.get().format("%B %Y").to_string(),
+    };
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full p-3 gap-3">
+            <div class="flex flex-row flex-wrap items-center gap-2">
+                <Button on_click=move |_| step(false)>"Previous"</Button>
+                <Button on_click=move |_| anchor.set(today)>"Today"</Button>
+                <Button on_click=move |_| step(true)>"Next"</Button>
+                <Select value=view_kind>
+                    <option>"Month"</option>
+                    <option>"Week"</option>
+                </Select>
+                <h3 class="text-secondary text-lg font-bold">{title}</h3>
+            </div>
+            <div class="grid grid-cols-7 gap-1 w-full">
+                {WEEKDAYS
+                    .iter()
+                    .map(|weekday| view! { <p class="text-center font-bold">{*weekday}</p> })
+                    .collect_view()}
+                {move || {
+                    let calendar = calendar.get();
+                    let month = anchor.get().month();
+                    days.get()
+                        .into_iter()
+                        .map(|day| {
+                            let day_start = day.and_time(Default::default());
+                            let day_end = day_start + Duration::days(1) - Duration::seconds(1);
+                            let events: Vec<(String, String)> = calendar
+                                .iter()
+                                .flat_map(|calendar| calendar.events.iter())
+                                .filter(|item| item.event.event_date.date() == day)
+                                .map(|item| {
+                                    (
+                                        format!("/plant/{}/view", item.event.plant_id),
+                                        item.summary(),
+                                    )
+                                })
+                                .collect();
+                            let scheduled: Vec<(String, String, bool)> = calendar
+                                .iter()
+                                .flat_map(|calendar| calendar.schedules.iter())
+                                .filter_map(|schedule| {
+                                    // Overdue care also shows up today, so it isn't lost once the day it was due is out of view
+                                    let overdue_today =
+                                        day == today && schedule.is_overdue(day_start);
+                                    let due = !schedule.occurrences(day_start, day_end).is_empty();
+                                    (due || overdue_today).then(|| {
+                                        (
+                                            format!("/plant/{}/view", schedule.plant_id),
+                                            schedule.summary(),
+                                            day < today || overdue_today,
+                                        )
+                                    })
+                                })
+                                .collect();
+                            // Days from the months around the shown month are faded out
+                            let outside_month =
+                                view_kind.get_untracked() == "Month" && day.month() != month;
+                            let faded = match outside_month {
+                                true => "opacity-50",
+                                false => "",
+                            };
+                            let highlight = match day == today {
+                                true => "border-2 border-secondary",
+                                false => "",
+                            };
+                            view! {
+                                <div class=format!(
+                                    "bg-(--card) p-1 rounded-(--radius) flex flex-col min-h-24 text-sm {} {}",
+                                    faded,
+                                    highlight,
+                                )>
+                                    <p class="font-bold">{day.day()}</p>
+                                    {events
+                                        .into_iter()
+                                        .map(|(link, summary)| {
+                                            view! { <a href=link>{summary}</a> }
+                                        })
+                                        .collect_view()}
+                                    {scheduled
+                                        .into_iter()
+                                        .map(|(link, summary, overdue)| {
+                                            view! {
+                                                <a
+                                                    href=link
+                                                    class=match overdue {
+                                                        true => "italic text-red-500",
+                                                        false => "italic text-secondary",
+                                                    }
+                                                >
+                                                    {format!("Due: {}", summary)}
+                                                </a>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </div>
+    }
+}
+
+async fn get_calendar(request: GetCalendar) -> Option<GetCalendarResponse> {
+    let request_builder = post_request("/calendar/events");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<GetCalendarResponse>(&body_text).ok()
+}
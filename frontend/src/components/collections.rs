@@ -0,0 +1,87 @@
+//! The saved smart collections along with a form to create new ones
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::collections::{collections_http::NewCollection, CollectionFilter};
+use thaw::{Button, Input, Label};
+
+use crate::data_storage::collections::{
+    delete_collection, new_collection_action, CollectionStorageContext,
+};
+
+#[component]
+pub fn CollectionsComponent() -> impl IntoView {
+    let collection_context: CollectionStorageContext =
+        expect_context::<CollectionStorageContext>();
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full">
+            <NewCollectionComponent />
+            <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-3 p-3 w-full">
+                <For
+                    each=move || collection_context.get_collections.get().0
+                    key=|collection| collection.id
+                    children=|collection| {
+                        let collection_id = collection.id;
+                        view! {
+                            <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col">
+                                <a
+                                    class="text-secondary text-lg font-bold"
+                                    href=format!("/collection/{}", collection.id)
+                                >
+                                    {collection.name}
+                                </a>
+                                <p class="font-mono text-sm">{collection.filter}</p>
+                                <Button on_click=move |_| {
+                                    spawn_local(delete_collection(collection_id))
+                                }>"Delete"</Button>
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
+
+/// Form to save a new collection. The filter is checked as it is typed
+#[component]
+pub fn NewCollectionComponent() -> impl IntoView {
+    let new_collection_action = new_collection_action();
+
+    let name = RwSignal::new("".to_string());
+    let filter = RwSignal::new("".to_string());
+
+    let filter_error = Memo::new(move |_| match filter.get().trim().is_empty() {
+        true => None,
+        false => CollectionFilter::parse(&filter.get()).err(),
+    });
+
+    let submit = move |_| {
+        new_collection_action.dispatch(NewCollection {
+            name: name.get_untracked(),
+            filter: filter.get_untracked(),
+        });
+    };
+
+    let response = move || match filter_error.get() {
+        Some(err) => format!("ERROR: {}", err),
+        None => match new_collection_action.value().get() {
+            Some(Ok(collection)) => format!("Saved {}", collection.name),
+            Some(Err(err)) => format!("ERROR: {}", err),
+            None => "".to_string(),
+        },
+    };
+
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-row flex-wrap items-center gap-2">
+            <Input value=name placeholder="Collection name" />
+            <Input value=filter placeholder="tag:office AND state:alive AND overdue:watered" />
+            <Button
+                on_click=submit
+                disabled=Signal::derive(move || filter_error.get().is_some())
+            >
+                "Save Collection"
+            </Button>
+            <Label>{response}</Label>
+        </div>
+    }
+}
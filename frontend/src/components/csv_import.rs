@@ -0,0 +1,266 @@
+//! Form to import plants and their history from a CSV, previewing what the import would do before committing it
+use std::collections::HashMap;
+
+use leptos::{prelude::*, reactive::spawn_local};
+use serde::de::DeserializeOwned;
+use shared::csv_import::{
+    csv_import_http::{CsvImport, CsvImportResult, CsvPreview, ProbableDuplicate},
+    CsvColumns, CsvTable,
+};
+use thaw::{Button, Input, Label, Select, Textarea};
+use uuid::Uuid;
+
+use crate::server_helpers::post_request;
+
+#[component]
+pub fn CsvImportComponent() -> impl IntoView {
+    let csv = RwSignal::new("".to_string());
+    let plant_column = RwSignal::new("".to_string());
+    let event_type_column = RwSignal::new("".to_string());
+    let date_column = RwSignal::new("".to_string());
+    let value_column = RwSignal::new("".to_string());
+    let date_format = RwSignal::new("".to_string());
+    let plant_overrides = RwSignal::new(HashMap::<String, Uuid>::new());
+
+    let preview = RwSignal::new(None::<CsvPreview>);
+    let result = RwSignal::new(None::<CsvImportResult>);
+    let error = RwSignal::new("".to_string());
+
+    let header = Memo::new(move |_| {
+        CsvTable::parse(&csv.get())
+            .map(|table| table.header)
+            .unwrap_or_default()
+    });
+
+    let request = move || CsvImport {
+        csv: csv.get_untracked(),
+        columns: CsvColumns {
+            plant_name: plant_column.get_untracked(),
+            event_type: event_type_column.get_untracked(),
+            date: date_column.get_untracked(),
+            value: Some(value_column.get_untracked()).filter(|column| !column.is_empty()),
+        },
+        date_format: Some(date_format.get_untracked()).filter(|format| !format.trim().is_empty()),
+        plant_overrides: plant_overrides.get_untracked(),
+    };
+
+    let on_preview = move |_| {
+        let request = request();
+        spawn_local(async move {
+            match send_csv_import::<CsvPreview>("/csv-import/preview", request).await {
+                Ok(response) => {
+                    error.set("".to_string());
+                    result.set(None);
+                    preview.set(Some(response));
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    let on_commit = move |_| {
+        let request = request();
+        spawn_local(async move {
+            match send_csv_import::<CsvImportResult>("/csv-import/commit", request).await {
+                Ok(response) => {
+                    error.set("".to_string());
+                    preview.set(None);
+                    result.set(Some(response));
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    let column_select = move |label: &'static str, column: RwSignal<String>| {
+        view! {
+            <div class="flex flex-col">
+                <Label>{label}</Label>
+                <Select value=column>
+                    <option value="">"None"</option>
+                    {move || {
+                        header
+                            .get()
+                            .into_iter()
+                            .map(|name| view! { <option value=name.clone()>{name}</option> })
+                            .collect_view()
+                    }}
+                </Select>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full gap-2 p-3">
+            <Textarea value=csv placeholder="Paste a CSV with a header row" />
+            <div class="bg-(--card) p-2 rounded-(--radius) flex flex-row flex-wrap items-end gap-2">
+                {column_select("Plant name", plant_column)}
+                {column_select("Event type", event_type_column)}
+                {column_select("Date", date_column)}
+                {column_select("Value", value_column)}
+                <Input value=date_format placeholder="Date format, eg %d/%m/%Y" />
+            </div>
+            <div class="flex flex-row items-center gap-2">
+                <Button on_click=on_preview>"Preview"</Button>
+                <Button
+                    on_click=on_commit
+                    disabled=Signal::derive(move || preview.get().is_none())
+                >
+                    "Import"
+                </Button>
+                <Label>{move || error.get()}</Label>
+            </div>
+            {move || {
+                preview
+                    .get()
+                    .map(|preview| view! { <CsvPreviewComponent preview plant_overrides /> })
+            }}
+            {move || {
+                result
+                    .get()
+                    .map(|result| {
+                        view! {
+                            <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col w-full">
+                                <p class="font-bold">
+                                    {format!(
+                                        "Created {} plants and logged {} events",
+                                        result.plants_created.len(),
+                                        result.events_logged,
+                                    )}
+                                </p>
+                                {result
+                                    .problems
+                                    .into_iter()
+                                    .map(|problem| {
+                                        view! {
+                                            <p>{format!("Line {}: {}", problem.row, problem.problem)}</p>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
+                    })
+            }}
+        </div>
+    }
+}
+
+/// What the import would do, with a choice of plant for every name that is probably already in the collection
+#[component]
+fn CsvPreviewComponent(
+    preview: CsvPreview,
+    plant_overrides: RwSignal<HashMap<String, Uuid>>,
+) -> impl IntoView {
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col w-full gap-1">
+            <p class="font-bold">
+                {format!("{} of {} rows are ready to import", preview.events_ready, preview.rows)}
+            </p>
+            <p>{format!("Existing plants: {}", preview.matched_plants.join(", "))}</p>
+            <p>{format!("New plants: {}", preview.new_plants.join(", "))}</p>
+            {(!preview.unknown_event_types.is_empty())
+                .then(|| {
+                    view! {
+                        <p>
+                            {format!(
+                                "Unknown event types: {}",
+                                preview.unknown_event_types.join(", "),
+                            )}
+                        </p>
+                    }
+                })}
+            {preview
+                .probable_duplicates
+                .into_iter()
+                .map(|duplicate| view! { <ProbableDuplicateComponent duplicate plant_overrides /> })
+                .collect_view()}
+            {preview
+                .problems
+                .into_iter()
+                .map(|problem| {
+                    view! { <p>{format!("Line {}: {}", problem.row, problem.problem)}</p> }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Lets the user pick which existing plant a name from the CSV belongs to, or keep it as a new plant
+#[component]
+fn ProbableDuplicateComponent(
+    duplicate: ProbableDuplicate,
+    plant_overrides: RwSignal<HashMap<String, Uuid>>,
+) -> impl IntoView {
+    let name = duplicate.name.clone();
+    let picked = RwSignal::new(
+        plant_overrides
+            .get_untracked()
+            .get(&name)
+            .map(|plant_id| plant_id.to_string())
+            .unwrap_or_default(),
+    );
+
+    Effect::new(move |_| {
+        let picked = picked.get();
+        plant_overrides.update(|overrides| match Uuid::parse_str(&picked) {
+            Ok(plant_id) => {
+                overrides.insert(name.clone(), plant_id);
+            }
+            Err(_) => {
+                overrides.remove(&name);
+            }
+        });
+    });
+
+    view! {
+        <div class="flex flex-row flex-wrap items-center gap-2">
+            <p>{format!("{} is probably", duplicate.name)}</p>
+            <Select value=picked>
+                <option value="">"Not any of these"</option>
+                {duplicate
+                    .candidates
+                    .into_iter()
+                    .map(|candidate| {
+                        let plant_id = candidate.plant_id.to_string();
+                        view! {
+                            <option value=plant_id>
+                                {format!(
+                                    "{} ({:.0}% match)",
+                                    candidate.name.unwrap_or("Unnamed".to_string()),
+                                    candidate.score * 100.0,
+                                )}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </Select>
+        </div>
+    }
+}
+
+/// Sends a CSV import to the server. The server explains why an import was rejected in the body
+async fn send_csv_import<T: DeserializeOwned>(path: &str, request: CsvImport) -> Result<T, String> {
+    let request_builder = post_request(path);
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    serde_json::de::from_str::<T>(&body_text).map_err(|_| body_text)
+}
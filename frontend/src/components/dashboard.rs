@@ -0,0 +1,242 @@
+//! Charts of care statistics for the household or a single plant
+use chrono::{Datelike, Duration, Utc};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::stats::{stats_http::GetCareStats, CareStats, DayCount, MonthCount, HEATMAP_DAYS};
+use thaw::Select;
+use uuid::Uuid;
+
+use crate::{
+    data_storage::{plants::PlantStorageContext, DirtyManagerContext},
+    server_helpers::post_request,
+};
+
+#[component]
+pub fn DashboardComponent() -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    let plant_filter = RwSignal::new("".to_string());
+    let stats = RwSignal::new(None::<CareStats>);
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.events.len());
+        let request = GetCareStats {
+            plant_id: Uuid::parse_str(&plant_filter.get()).ok(),
+        };
+        spawn_local(async move {
+            if let Some(new_stats) = get_care_stats(request).await {
+                stats.set(Some(new_stats));
+            }
+        });
+    });
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full p-3 gap-3">
+            <Select value=plant_filter>
+                <option value="">"Every plant"</option>
+                {move || {
+                    let mut plants: Vec<(Uuid, String)> = plant_storage_context
+                        .get_plant_storage
+                        .get()
+                        .plants
+                        .iter()
+                        .map(|(plant_id, (demographic, _))| {
+                            let name = demographic.name.clone();
+                            (*plant_id, name.unwrap_or("Unnamed plant".to_string()))
+                        })
+                        .collect();
+                    plants.sort_by(|a, b| a.1.cmp(&b.1));
+                    plants
+                        .into_iter()
+                        .map(|(plant_id, name)| {
+                            view! { <option value=plant_id.to_string()>{name}</option> }
+                        })
+                        .collect_view()
+                }}
+            </Select>
+            {move || match stats.get() {
+                None => view! { <p>"Loading statistics..."</p> }.into_any(),
+                Some(stats) => {
+                    let neglected = stats
+                        .longest_neglected
+                        .clone()
+                        .map(|plant| {
+                            format!(
+                                "{} - {} days",
+                                plant.name.unwrap_or("Unnamed plant".to_string()),
+                                plant.days_without_water,
+                            )
+                        })
+                        .unwrap_or("-".to_string());
+                    view! {
+                        <div class="grid grid-cols-2 md:grid-cols-4 gap-3 w-full">
+                            <StatTile
+                                title="Average days between waterings"
+                                value=stats
+                                    .average_watering_interval
+                                    .map(|days| format!("{:.1}", days))
+                                    .unwrap_or("-".to_string())
+                            />
+                            <StatTile
+                                title="Watered on schedule"
+                                value=stats
+                                    .interval_adherence
+                                    .map(|share| format!("{:.0}%", share * 100.0))
+                                    .unwrap_or("-".to_string())
+                            />
+                            <StatTile
+                                title="Streak"
+                                value=format!(
+                                    "{} days, best {}",
+                                    stats.current_streak,
+                                    stats.longest_streak,
+                                )
+                            />
+                            <StatTile title="Longest without water" value=neglected />
+                        </div>
+                        <MonthChart title="Waterings per month" months=stats.waterings_per_month />
+                        <MonthChart title="Plants added per month" months=stats.plants_added />
+                        <MonthChart title="Plants retired per month" months=stats.plants_retired />
+                        <Heatmap days=stats.heatmap />
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn StatTile(title: &'static str, value: String) -> impl IntoView {
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col">
+            <p class="text-sm text-muted-foreground">{title}</p>
+            <p class="text-secondary text-lg font-bold">{value}</p>
+        </div>
+    }
+}
+
+/// Bar chart with one bar per month
+#[component]
+fn MonthChart(title: &'static str, months: Vec<MonthCount>) -> impl IntoView {
+    let highest = months.iter().map(|month| month.count).max().unwrap_or(0).max(1) as f64;
+    let width = 100.0 / months.len().max(1) as f64;
+    let first = months.first().map(|month| month.month.format("%b %Y").to_string());
+    let last = months.last().map(|month| month.month.format("%b %Y").to_string());
+
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col w-full">
+            <h3 class="text-secondary font-bold">{title}</h3>
+            {match months.is_empty() {
+                true => view! { <p>"Nothing logged yet..."</p> }.into_any(),
+                false => {
+                    view! {
+                        <svg
+                            class="w-full h-32 text-secondary"
+                            viewBox="0 0 100 40"
+                            preserveAspectRatio="none"
+                        >
+                            {months
+                                .iter()
+                                .enumerate()
+                                .map(|(index, month)| {
+                                    let height = month.count as f64 / highest * 40.0;
+                                    view! {
+                                        <rect
+                                            x=format!("{:.2}", index as f64 * width + width * 0.1)
+                                            y=format!("{:.2}", 40.0 - height)
+                                            width=format!("{:.2}", width * 0.8)
+                                            height=format!("{:.2}", height)
+                                            fill="currentColor"
+                                        >
+                                            <title>
+                                                {format!(
+                                                    "{}: {}",
+                                                    month.month.format("%B %Y"),
+                                                    month.count,
+                                                )}
+                                            </title>
+                                        </rect>
+                                    }
+                                })
+                                .collect_view()}
+                        </svg>
+                        <div class="flex flex-row justify-between text-sm text-muted-foreground">
+                            <p>{first}</p>
+                            <p>{last}</p>
+                        </div>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+/// Calendar heatmap of the last year, one column per week and one row per weekday. Darker days had more events
+#[component]
+fn Heatmap(days: Vec<DayCount>) -> impl IntoView {
+    let today = Utc::now().naive_utc().date();
+    // Start on a monday so every column is a full week
+    let start = today - Duration::days(HEATMAP_DAYS);
+    let start = start - Duration::days(start.weekday().num_days_from_monday() as i64);
+    let highest = days.iter().map(|day| day.count).max().unwrap_or(0).max(1) as f64;
+
+    let cells = days
+        .iter()
+        .filter(|day| day.day >= start)
+        .map(|day| {
+            let offset = day.day.signed_duration_since(start).num_days();
+            view! {
+                <rect
+                    x=(offset / 7 * 2).to_string()
+                    y=(offset % 7 * 2).to_string()
+                    width="1.8"
+                    height="1.8"
+                    fill="currentColor"
+                    fill-opacity=format!("{:.2}", 0.2 + day.count as f64 / highest * 0.8)
+                >
+                    <title>{format!("{}: {}", day.day.format("%B %d %Y"), day.count)}</title>
+                </rect>
+            }
+        })
+        .collect_view();
+    let weeks = today.signed_duration_since(start).num_days() / 7 + 1;
+
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col w-full">
+            <h3 class="text-secondary font-bold">"Activity over the last year"</h3>
+            <svg class="w-full text-secondary" viewBox=format!("0 0 {} 14", weeks * 2)>
+                {cells}
+            </svg>
+        </div>
+    }
+}
+
+async fn get_care_stats(request: GetCareStats) -> Option<CareStats> {
+    let request_builder = post_request("/stats/care");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<CareStats>(&body_text).ok()
+}
@@ -1,30 +1,342 @@
 //! Generic gallery component that shows users plants with customizable options for what settings to show alongside them
-use leptos::prelude::*;
-use thaw::Pagination;
+use chrono::{NaiveDate, Utc};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::{
+    events::{
+        events_http::{BulkEventPlant, BulkEventResult, NewBulkEvent},
+        EventData, WATERED_EVENT_ID,
+    },
+    plant::{
+        plant_http::{PlantCursor, PlantSort, SearchPlants, SearchPlantsResponse},
+        PlantState,
+    },
+};
+use thaw::{Button, DatePicker, Input, Label, Select};
+use uuid::Uuid;
 
-use crate::{components::plant_card::PlantCard, data_storage::plants::PlantStorageContext};
+use crate::{
+    components::plant_card::PlantCard,
+    data_storage::{locations::LocationStorageContext, DirtyManagerContext},
+    server_helpers::post_request,
+};
 
-static PAGINATION: usize = 20;
+static PAGINATION: i64 = 20;
 
+static SORTS: [PlantSort; 3] = [PlantSort::Name, PlantSort::Created, PlantSort::LastEvent];
+
+/// Shows the plants still in the users care, or the retired plants when archived is set, along with a search box and filters.
+/// Only the plants in the collection are shown when one is given. Plants can be selected to log watering for all of them at once
 #[component]
-pub fn GalleryComponent() -> impl IntoView {
-    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
-    //let plant = move || plant_storage_context.get_plant_storage.get();
-    let page_count =
-        move || 1usize.max(plant_storage_context.get_plant_storage.get().plants.len() / PAGINATION);
-    let current_page = RwSignal::new(1);
+pub fn GalleryComponent(
+    archived: bool,
+    #[prop(optional, into)] collection_id: MaybeProp<Uuid>,
+) -> impl IntoView {
+    let location_context: LocationStorageContext = expect_context::<LocationStorageContext>();
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    let query = RwSignal::new("".to_string());
+    let tag_filter = RwSignal::new("".to_string());
+    let state_filter = RwSignal::new("".to_string());
+    let location_filter = RwSignal::new("".to_string());
+    let photo_filter = RwSignal::new("".to_string());
+    let watered_before = RwSignal::new(None::<NaiveDate>);
+    let sort = RwSignal::new(format!("{:?}", PlantSort::Name));
+
+    let filters = Memo::new(move |_| SearchPlants {
+        query: match query.get().trim().is_empty() {
+            true => None,
+            false => Some(query.get()),
+        },
+        state: match state_filter.get().is_empty() {
+            true => None,
+            false => Some(state_filter.get()),
+        },
+        // Collections decide for themselves whether retired plants belong in them
+        retired: match collection_id.get() {
+            Some(_) => None,
+            None => Some(archived),
+        },
+        location_id: Uuid::parse_str(&location_filter.get()).ok(),
+        last_watered_before: watered_before
+            .get()
+            .map(|date| date.and_time(Default::default())),
+        has_photo: match photo_filter.get().as_str() {
+            "With photos" => Some(true),
+            "Without photos" => Some(false),
+            _ => None,
+        },
+        tags: tag_filter
+            .get()
+            .split(',')
+            .filter(|tag| !tag.trim().is_empty())
+            .map(|tag| tag.trim().to_string())
+            .collect(),
+        collection_id: collection_id.get(),
+        sort: SORTS
+            .iter()
+            .find(|item| format!("{:?}", item) == sort.get())
+            .copied()
+            .unwrap_or_default(),
+        cursor: None,
+        limit: PAGINATION,
+    });
+
+    let plants = RwSignal::new(Vec::<Uuid>::new());
+    let cursor = RwSignal::new(None::<PlantCursor>);
+    // Bumped whenever the filters change so that pages requested with old filters are thrown away
+    let generation = StoredValue::new(0u32);
+
+    let load_page = move |first_page: bool| {
+        let request = SearchPlants {
+            cursor: match first_page {
+                true => None,
+                false => cursor.get_untracked(),
+            },
+            ..filters.get_untracked()
+        };
+        let request_generation = generation.get_value();
+        spawn_local(async move {
+            let Some(response) = search_plants(request).await else {
+                return;
+            };
+            if generation.get_value() != request_generation {
+                return;
+            }
+            match first_page {
+                true => plants.set(response.plants),
+                false => plants.update(|plants| plants.extend(response.plants)),
+            }
+            cursor.set(response.next_cursor);
+        });
+    };
+
+    let select_mode = RwSignal::new(false);
+    let selected = RwSignal::new(Vec::<Uuid>::new());
+    let bulk_message = RwSignal::new("".to_string());
+
+    let water_selected = move |_| {
+        let new_bulk_event = NewBulkEvent {
+            event_type: Uuid::parse_str(WATERED_EVENT_ID).expect("Invalid UUID"),
+            event_date: Utc::now().naive_utc(),
+            event_data: Some(EventData::DateTime),
+            plants: selected
+                .get_untracked()
+                .into_iter()
+                .map(|plant_id| BulkEventPlant {
+                    plant_id,
+                    event_data: None,
+                })
+                .collect(),
+        };
+        spawn_local(async move {
+            match log_bulk_event(new_bulk_event).await {
+                Ok(results) => {
+                    let failed: Vec<String> = results
+                        .iter()
+                        .filter_map(|result| result.result.clone().err())
+                        .collect();
+                    // Plants that failed stay selected so they can be retried
+                    selected.update(|selected| {
+                        selected.retain(|plant_id| {
+                            results.iter().any(|result| {
+                                &result.plant_id == plant_id && result.result.is_err()
+                            })
+                        })
+                    });
+                    bulk_message.set(match failed.is_empty() {
+                        true => format!("Watered {} plants", results.len()),
+                        false => format!(
+                            "Watered {} plants, {} failed: {}",
+                            results.len() - failed.len(),
+                            failed.len(),
+                            failed.join(", ")
+                        ),
+                    });
+                }
+                Err(err) => bulk_message.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    // New filters or changed plants start the search over
+    Effect::new(move |_| {
+        filters.track();
+        dirty_manager.get.with(|dirty_manager| dirty_manager.plants.len());
+        generation.update_value(|generation| *generation += 1);
+        load_page(true);
+    });
+
     view! {
         <div class="flex flex-col items-center self-center">
+            <div class="flex flex-row flex-wrap items-center gap-2 p-3">
+                <Input value=query placeholder="Search names, notes and species" />
+                <Input value=tag_filter placeholder="Tags, separated by commas" />
+                <Select value=state_filter>
+                    <option value="">"Any state"</option>
+                    {PlantState::NAMES
+                        .into_iter()
+                        .map(|name| view! { <option value=name>{name}</option> })
+                        .collect_view()}
+                </Select>
+                <Select value=location_filter>
+                    <option value="">"Any location"</option>
+                    <For
+                        each=move || location_context.get_locations.get().0
+                        key=|location| location.id
+                        children=|location| {
+                            view! { <option value=location.id.to_string()>{location.name}</option> }
+                        }
+                    />
+                </Select>
+                <Select value=photo_filter>
+                    <option value="">"With or without photos"</option>
+                    <option>"With photos"</option>
+                    <option>"Without photos"</option>
+                </Select>
+                <Label>"Last watered before"</Label>
+                <DatePicker value=watered_before />
+                <Button on_click=move |_| watered_before.set(None)>"Any time"</Button>
+                <Label>"Sort by"</Label>
+                <Select value=sort>
+                    {SORTS
+                        .iter()
+                        .map(|item| view! { <option>{format!("{:?}", item)}</option> })
+                        .collect_view()}
+                </Select>
+                <Button on_click=move |_| {
+                    select_mode.update(|select_mode| *select_mode = !*select_mode);
+                    selected.set(Vec::new());
+                    bulk_message.set("".to_string());
+                }>
+                    {move || match select_mode.get() {
+                        true => "Stop selecting",
+                        false => "Select plants",
+                    }}
+                </Button>
+            </div>
+            {move || {
+                select_mode
+                    .get()
+                    .then(|| {
+                        view! {
+                            <div class="flex flex-row flex-wrap items-center gap-2 p-3">
+                                <Button on_click=move |_| {
+                                    selected.set(plants.get_untracked())
+                                }>"Select all"</Button>
+                                <Button on_click=move |_| selected.set(Vec::new())>"Clear"</Button>
+                                <Button
+                                    on_click=water_selected
+                                    disabled=Signal::derive(move || selected.get().is_empty())
+                                >
+                                    {move || {
+                                        format!("Log watered for selected ({})", selected.get().len())
+                                    }}
+                                </Button>
+                                <Label>{move || bulk_message.get()}</Label>
+                            </div>
+                        }
+                    })
+            }}
             <div class="grid grid-cols-2 md:grid-cols-4 lg:grid-cols-6 xl:grid-cols-8 gap-3 p-3 justify-center">
                 <For
-                    each=move || plant_storage_context.get_plant_storage.get().plants.clone()
-                    key=|item| item.0
-                    children=|(id, _item)| {
-                        view! { <PlantCard plant_id=id /> }
+                    each=move || plants.get()
+                    key=|id| *id
+                    children=move |id| {
+                        view! {
+                            <div class="relative">
+                                <PlantCard plant_id=id />
+                                {move || {
+                                    select_mode
+                                        .get()
+                                        .then(|| {
+                                            view! {
+                                                <input
+                                                    type="checkbox"
+                                                    class="absolute top-2 left-2 size-6"
+                                                    prop:checked=move || selected.get().contains(&id)
+                                                    on:change=move |_| {
+                                                        selected
+                                                            .update(|selected| {
+                                                                match selected.contains(&id) {
+                                                                    true => selected.retain(|plant_id| plant_id != &id),
+                                                                    false => selected.push(id),
+                                                                }
+                                                            })
+                                                    }
+                                                />
+                                            }
+                                        })
+                                }}
+                            </div>
+                        }
                     }
                 />
             </div>
-            <Pagination page=current_page page_count=1 sibling_count=1 class="justify-center" />
+            {move || match plants.get().is_empty() {
+                true => view! { <p>"No plants found..."</p> }.into_any(),
+                false => view! {}.into_any(),
+            }}
+            {move || {
+                cursor
+                    .get()
+                    .is_some()
+                    .then(|| view! { <Button on_click=move |_| load_page(false)>"Load more"</Button> })
+            }}
         </div>
     }
 }
+
+async fn search_plants(request: SearchPlants) -> Option<SearchPlantsResponse> {
+    let request_builder = post_request("/plants/search");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<SearchPlantsResponse>(&body_text).ok()
+}
+
+async fn log_bulk_event(new_bulk_event: NewBulkEvent) -> Result<Vec<BulkEventResult>, String> {
+    let request = post_request("/events/bulk");
+
+    let Some(request_with_json) = request
+        .json(&new_bulk_event)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why the whole request was rejected in the body
+    serde_json::de::from_str::<Vec<BulkEventResult>>(&body_text).map_err(|_| body_text)
+}
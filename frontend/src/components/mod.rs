@@ -1,3 +1,13 @@
+/// Feed of the latest events across every plant
+pub mod activity_feed;
+/// Calendar of logged events and scheduled care
+pub mod calendar;
+/// Saved smart collections and a form to create them
+pub mod collections;
+/// Import of plants and events from a CSV
+pub mod csv_import;
+/// Charts of care statistics
+pub mod dashboard;
 pub mod footer;
 pub mod gallery;
 pub mod navbar;
@@ -6,6 +16,8 @@ pub mod navigation_components;
 ///
 /// Includes fields for plant settings
 pub mod new_plant;
+/// Gallery of the photos of every plant
+pub mod photo_gallery;
 /// A condensed and simplified view into a plant without all the features of the full plant page.
 ///
 /// Does not include the Timeline or the ability to edit the plant
@@ -14,3 +26,7 @@ pub mod new_plant;
 pub mod plant_card;
 /// Module for plant related components
 pub mod plant_components;
+/// Plants grouped by the room they are kept in
+pub mod rooms;
+/// The species catalog and a form to extend it
+pub mod species_catalog;
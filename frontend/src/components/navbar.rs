@@ -57,7 +57,56 @@ pub fn Navbar() -> impl IntoView {
                             on:click=open_mobile_nav
                             href="/gallery"
                         >
-                            "Plants"
+                            "Gallery"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/rooms"
+                        >
+                            "Rooms"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/species"
+                        >
+                            "Species"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/collections"
+                        >
+                            "Collections"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/archive"
+                        >
+                            "Archive"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/dashboard"
+                        >
+                            "Dashboard"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/calendar"
+                        >
+                            "Calendar"
+                        </a>
+                        <a
+                            class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-2xl font-bold tracking-wide"
+                            on:click=open_mobile_nav
+                            href="/import"
+                        >
+                            "Import"
                         </a>
                         <a
                             href="/settings"
@@ -82,7 +131,49 @@ pub fn Navbar() -> impl IntoView {
                     class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
                     href="/gallery"
                 >
-                    "Plants"
+                    "Gallery"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/rooms"
+                >
+                    "Rooms"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/species"
+                >
+                    "Species"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/collections"
+                >
+                    "Collections"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/archive"
+                >
+                    "Archive"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/dashboard"
+                >
+                    "Dashboard"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/calendar"
+                >
+                    "Calendar"
+                </a>
+                <a
+                    class="text-foreground hover:bg-accent p-1 px-2 rounded-(--radius) text-lg font-bold tracking-wide"
+                    href="/import"
+                >
+                    "Import"
                 </a>
                 <a
                     href="/settings"
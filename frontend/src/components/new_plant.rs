@@ -1,7 +1,8 @@
 use chrono::{Local, Utc};
 use leptos::{prelude::*, reactive::spawn_local};
 use shared::plant::{plant_http::NewPlant, PlantDemographic};
-use thaw::{Button, DatePicker, Input, Label};
+use thaw::{Button, DatePicker, Input, Label, Select};
+use uuid::Uuid;
 
 use crate::{
     data_storage::plants::{PlantStorage, PlantStorageContext},
@@ -16,11 +17,13 @@ pub fn NewPlant() -> impl IntoView {
     let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
     let submit_response_2 = RwSignal::new("Unknown".to_string());
     let date_value = RwSignal::new(Local::now().date_naive());
+    let parent = RwSignal::new("".to_string());
     let click = move |_| {
         spawn_local(submit_new_plant(
             submit_response,
             submit_response_2,
             value.get(),
+            Uuid::parse_str(&parent.get()).ok(),
             plant_storage_context.get_plant_storage.get(),
             plant_storage_context.write_plant_storage,
         ))
@@ -35,6 +38,24 @@ pub fn NewPlant() -> impl IntoView {
                 <Label>{move || submit_response.get()}</Label>
             </div>
             <DatePicker value=date_value />
+            <Select value=parent>
+                <option value="">"Not propagated"</option>
+                <For
+                    each=move || plant_storage_context.get_plant_storage.get().plants.clone()
+                    key=|(id, _)| *id
+                    children=|(id, (demographic, _))| {
+                        view! {
+                            <option value=id
+                                .to_string()>
+                                {format!(
+                                    "Propagated from {}",
+                                    demographic.name.unwrap_or("Unnamed plant".to_string()),
+                                )}
+                            </option>
+                        }
+                    }
+                />
+            </Select>
 
             <Button on_click=click>"Create new Plant"</Button>
             <Label>{move || submit_response_2.get()}</Label>
@@ -47,6 +68,7 @@ async fn submit_new_plant(
     submit_response: RwSignal<String>,
     submit_response_2: RwSignal<String>,
     plant_name: String,
+    parent_id: Option<Uuid>,
     mut plant_storage: PlantStorage,
     plant_storage_write: WriteSignal<PlantStorage>,
 ) {
@@ -62,6 +84,7 @@ async fn submit_new_plant(
             name: plant_name,
             timestamp: Utc::now().naive_utc().and_utc().timestamp(),
             starting_events: vec![],
+            parent_id,
         })
         .map_err(|e| log::error!("{e}"))
         .ok()
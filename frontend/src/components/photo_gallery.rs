@@ -0,0 +1,338 @@
+//! Gallery of the photos of every plant with filters, infinite scrolling and a lightbox
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use leptos::{html::Div, prelude::*, reactive::spawn_local};
+use leptos_use::{
+    use_infinite_scroll_with_options, use_swipe_with_options, SwipeDirection,
+    UseInfiniteScrollOptions, UseSwipeOptions,
+};
+use shared::{
+    photos::{GetPhotos, GetPhotosResponse, Photo, PhotoCursor, SetCoverPhoto},
+    plant::PlantState,
+};
+use thaw::{
+    Button, DatePicker, Dialog, DialogBody, DialogContent, DialogSurface, DialogTitle, Label,
+    Select, Spinner,
+};
+use uuid::Uuid;
+
+use crate::{
+    components::plant_components::photo::PhotoDisplayComponent,
+    data_storage::plants::PlantStorageContext, server_helpers::post_request,
+};
+
+static PAGE_SIZE: i64 = 30;
+
+#[component]
+pub fn PhotoGalleryComponent() -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+
+    let plant_filter = RwSignal::new("".to_string());
+    let state_filter = RwSignal::new("".to_string());
+    let span_start = RwSignal::new(None::<NaiveDate>);
+    let span_end = RwSignal::new(None::<NaiveDate>);
+
+    let filters = Memo::new(move |_| GetPhotos {
+        plant_id: Uuid::parse_str(&plant_filter.get()).ok(),
+        span: match (span_start.get(), span_end.get()) {
+            (None, None) => None,
+            (start, end) => Some((
+                start
+                    .map(|date| date.and_time(Default::default()))
+                    .unwrap_or(DateTime::UNIX_EPOCH.naive_utc()),
+                end.and_then(|date| date.checked_add_days(Days::new(1)))
+                    .map(|date| date.and_time(Default::default()))
+                    .unwrap_or(Utc::now().naive_utc()),
+            )),
+        },
+        plant_state: match state_filter.get().is_empty() {
+            true => None,
+            false => Some(state_filter.get()),
+        },
+        cursor: None,
+        limit: PAGE_SIZE,
+    });
+
+    let photos = RwSignal::new(Vec::<Photo>::new());
+    let cursor = RwSignal::new(None::<PhotoCursor>);
+    let exhausted = RwSignal::new(false);
+    let loading = RwSignal::new(false);
+    // Bumped whenever the filters change so that pages requested with old filters are thrown away
+    let generation = StoredValue::new(0u32);
+
+    let load_page = move |reset: bool| {
+        if !reset && (loading.get_untracked() || exhausted.get_untracked()) {
+            return;
+        }
+        if reset {
+            generation.update_value(|generation| *generation += 1);
+        }
+        let request_generation = generation.get_value();
+        let mut request = filters.get_untracked();
+        request.cursor = match reset {
+            true => None,
+            false => cursor.get_untracked(),
+        };
+
+        loading.set(true);
+        spawn_local(async move {
+            let response = request_photos(request).await;
+            if generation.get_value() != request_generation {
+                return;
+            }
+            if let Some(response) = response {
+                match reset {
+                    true => photos.set(response.photos),
+                    false => photos.update(|photos| photos.extend(response.photos)),
+                }
+                exhausted.set(response.next_cursor.is_none());
+                cursor.set(response.next_cursor);
+            }
+            loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        filters.track();
+        load_page(true);
+    });
+
+    let scroll_ref = NodeRef::<Div>::new();
+    let _ = use_infinite_scroll_with_options(
+        scroll_ref,
+        move |_| async move { load_page(false) },
+        UseInfiniteScrollOptions::default().distance(200.0),
+    );
+
+    let lightbox_open = RwSignal::new(false);
+    let lightbox_index = RwSignal::new(0usize);
+    let cover_response = RwSignal::new("".to_string());
+
+    let current_photo = move || photos.get().get(lightbox_index.get()).cloned();
+    let next_photo = move || {
+        let count = photos.get_untracked().len();
+        if lightbox_index.get_untracked() + 1 < count {
+            lightbox_index.update(|index| *index += 1);
+            cover_response.set("".to_string());
+        }
+        // Keep the next page ready before the user swipes onto the last photo
+        if lightbox_index.get_untracked() + 2 >= count {
+            load_page(false);
+        }
+    };
+    let previous_photo = move || {
+        if lightbox_index.get_untracked() > 0 {
+            lightbox_index.update(|index| *index -= 1);
+            cover_response.set("".to_string());
+        }
+    };
+
+    let swipe_ref = NodeRef::<Div>::new();
+    let _ = use_swipe_with_options(
+        swipe_ref,
+        UseSwipeOptions::default().on_swipe_end(move |_, direction| match direction {
+            SwipeDirection::Left => next_photo(),
+            SwipeDirection::Right => previous_photo(),
+            _ => {}
+        }),
+    );
+
+    let plant_name = move |plant_id: Uuid| {
+        plant_storage_context
+            .get_plant_storage
+            .get()
+            .plants
+            .get(&plant_id)
+            .and_then(|(demographic, _)| demographic.name.clone())
+            .unwrap_or("...".to_string())
+    };
+
+    view! {
+        <div class="flex flex-col items-center w-full">
+            <div class="flex flex-row flex-wrap justify-center items-center gap-2 p-3">
+                <Select value=plant_filter>
+                    <option value="">"All plants"</option>
+                    <For
+                        each=move || {
+                            plant_storage_context
+                                .get_plant_storage
+                                .get()
+                                .plants
+                                .into_iter()
+                                .map(|(id, (demographic, _))| {
+                                    (id, demographic.name.unwrap_or("...".to_string()))
+                                })
+                                .collect::<Vec<(Uuid, String)>>()
+                        }
+                        key=|item| item.clone()
+                        children=|(id, name)| {
+                            view! { <option value=id.to_string()>{name}</option> }
+                        }
+                    />
+                </Select>
+                <Select value=state_filter>
+                    <option value="">"Any state"</option>
+                    <For
+                        each=move || PlantState::NAMES
+                        key=|item| item.clone()
+                        children=|name| {
+                            view! { <option value=name>{name}</option> }
+                        }
+                    />
+                </Select>
+                <Label>"From"</Label>
+                <DatePicker value=span_start />
+                <Label>"To"</Label>
+                <DatePicker value=span_end />
+                <Button on_click=move |_| {
+                    span_start.set(None);
+                    span_end.set(None);
+                }>"Any date"</Button>
+            </div>
+
+            <div node_ref=scroll_ref class="h-[75vh] w-full overflow-y-auto">
+                <div class="grid grid-cols-3 md:grid-cols-5 lg:grid-cols-8 gap-2 p-3">
+                    <For
+                        each=move || photos.get().into_iter().enumerate()
+                        key=|(index, photo)| (*index, photo.id)
+                        children=move |(index, photo)| {
+                            view! {
+                                <div
+                                    class="aspect-square cursor-pointer hover:scale-105 transition duration-150"
+                                    on:click=move |_| {
+                                        lightbox_index.set(index);
+                                        cover_response.set("".to_string());
+                                        lightbox_open.set(true);
+                                    }
+                                >
+                                    <PhotoDisplayComponent photo_location=photo
+                                        .thumbnail_location
+                                        .clone() />
+                                </div>
+                            }
+                        }
+                    />
+                </div>
+                <div class="flex justify-center p-3">
+                    {move || {
+                        match (loading.get(), exhausted.get() && photos.get().is_empty()) {
+                            (true, _) => view! { <Spinner /> }.into_any(),
+                            (false, true) => view! { <p>"No photos found..."</p> }.into_any(),
+                            (false, false) => view! {}.into_any(),
+                        }
+                    }}
+                </div>
+            </div>
+
+            <Dialog open=lightbox_open>
+                <DialogSurface>
+                    <DialogBody>
+                        <DialogTitle>
+                            <div class="flex justify-between">
+                                <h2>
+                                    {move || {
+                                        current_photo()
+                                            .map(|photo| {
+                                                format!(
+                                                    "{} - {}",
+                                                    plant_name(photo.plant_id),
+                                                    photo.photo_date.format("%B %d %Y"),
+                                                )
+                                            })
+                                    }}
+                                </h2>
+                                <Button on_click=move |_| lightbox_open.set(false)>"Close"</Button>
+                            </div>
+                        </DialogTitle>
+                        <DialogContent>
+                            <div node_ref=swipe_ref class="flex justify-center max-h-[70vh]">
+                                {move || {
+                                    current_photo()
+                                        .map(|photo| {
+                                            view! {
+                                                <PhotoDisplayComponent photo_location=photo
+                                                    .file_location />
+                                            }
+                                        })
+                                }}
+                            </div>
+                            <div class="flex flex-row justify-between items-center pt-2">
+                                <Button on_click=move |_| previous_photo()>"Previous"</Button>
+                                <div class="flex flex-col items-center">
+                                    <Button on_click=move |_| {
+                                        let Some(photo) = current_photo() else {
+                                            return;
+                                        };
+                                        spawn_local(set_cover_photo(
+                                            SetCoverPhoto {
+                                                plant_id: photo.plant_id,
+                                                photo_id: photo.id,
+                                            },
+                                            cover_response,
+                                        ));
+                                    }>"Set as cover"</Button>
+                                    <Label>{move || cover_response.get()}</Label>
+                                </div>
+                                <Button on_click=move |_| next_photo()>"Next"</Button>
+                            </div>
+                        </DialogContent>
+                    </DialogBody>
+                </DialogSurface>
+            </Dialog>
+        </div>
+    }
+}
+
+async fn request_photos(request: GetPhotos) -> Option<GetPhotosResponse> {
+    let request_builder = post_request("/photos/get-photos");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<GetPhotosResponse>(&body_text).ok()
+}
+
+async fn set_cover_photo(request: SetCoverPhoto, cover_response: RwSignal<String>) {
+    let request_builder = post_request("/photos/set-cover");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        cover_response.set("ERROR".to_string());
+        return;
+    };
+
+    match response.ok() {
+        true => cover_response.set("Cover photo set".to_string()),
+        false => cover_response.set(response.text().await.unwrap_or("ERROR".to_string())),
+    }
+}
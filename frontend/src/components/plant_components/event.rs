@@ -2,12 +2,16 @@ use std::time::Duration;
 
 use chrono::{Local, NaiveDateTime};
 use leptos::prelude::*;
-use shared::events::{events_http::NewEvent, EventData, EventInstance, EventType};
-use thaw::{Button, DatePicker, Select, TimePicker};
+use shared::events::{
+    events_http::NewEvent, EventData, EventDataKind, EventInstance, EventType, Period,
+};
+use thaw::{Button, DatePicker, Input, Select, TimePicker};
 use uuid::Uuid;
 
-use crate::data_storage::events::{
-    new_event_action, EventListContext,
+use crate::data_storage::{
+    events::{new_event_action, EventListContext},
+    locations::LocationStorageContext,
+    plants::PlantStorageContext,
 };
 
 #[component]
@@ -66,16 +70,124 @@ pub fn EventViewComponent(event: EventInstance) -> impl IntoView {
             </div>
         }
         .into_any(),
-        shared::events::EventData::Period(period) => todo!(),
-        shared::events::EventData::CustomEnum(custom_enum) => {
-            view! { <p>{format!("{}", *custom_enum.selected().unwrap())}</p> }.into_any()
+        shared::events::EventData::Period(period) => view! {
+            <div class="flex flex-row items-center p-2">
+                <div class="text-center font-bold mx-2">
+                    {match period {
+                        shared::events::Period::PeriodStart => "Started",
+                        shared::events::Period::PeriodEnd => "Ended",
+                    }}
+                </div>
+                <div class="text-center mx-2">{event.event_date.format("%B %d").to_string()}</div>
+                <div class="text-center">{move || humanized_time.get()}</div>
+            </div>
+        }
+        .into_any(),
+        shared::events::EventData::CustomEnum(custom_enum) => view! {
+            <p>{custom_enum.selected().cloned().unwrap_or("Unknown option".to_string())}</p>
+        }
+        .into_any(),
+        shared::events::EventData::MultiEnum(multi_enum) => {
+            let selected = multi_enum.selected();
+            view! {
+                <div class="flex flex-row items-center p-2">
+                    <div class="text-center mx-2">
+                        {match selected.is_empty() {
+                            true => "None".to_string(),
+                            false => {
+                                selected
+                                    .iter()
+                                    .map(|option| option.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            }
+                        }}
+                    </div>
+                    <div class="text-center mx-2">{event.event_date.format("%B %d").to_string()}</div>
+                    <div class="text-center">{move || humanized_time.get()}</div>
+                </div>
+            }
+                .into_any()
         }
         shared::events::EventData::Number(number) => {
-            view! { <p>{format!("{}", number)}</p> }.into_any()
+            let number = *number;
+            let event_type_id = event.event_type_id;
+            let event_list_context = expect_context::<EventListContext>();
+            view! {
+                <div class="flex flex-row items-center p-2">
+                    <div class="text-center font-bold mx-2">
+                        {move || {
+                            match event_list_context
+                                .get_event_list
+                                .get()
+                                .0
+                                .iter()
+                                .find(|event_type| event_type.id == event_type_id)
+                                .map(|event_type| event_type.kind.clone())
+                            {
+                                Some(EventDataKind::Number(format)) => format.unit.format(number),
+                                _ => format!("{}", number),
+                            }
+                        }}
+                    </div>
+                    <div class="text-center mx-2">{event.event_date.format("%B %d").to_string()}</div>
+                    <div class="text-center">{move || humanized_time.get()}</div>
+                </div>
+            }
+                .into_any()
         }
         shared::events::EventData::String(string) => {
             view! { <p>{format!("{}", string)}</p> }.into_any()
         }
+        shared::events::EventData::Location(location_id) => {
+            let location_id = *location_id;
+            let location_context = expect_context::<LocationStorageContext>();
+            view! {
+                <p>
+                    {move || {
+                        location_context
+                            .get_locations
+                            .get()
+                            .find(&location_id)
+                            .map(|location| location.name.clone())
+                            .unwrap_or("Unknown location".to_string())
+                    }}
+                </p>
+            }
+                .into_any()
+        }
+        shared::events::EventData::Lifecycle(transition) => view! {
+            <div class="flex flex-row items-center p-2">
+                <div class="text-center font-bold mx-2">
+                    {match &transition.from {
+                        Some(from) => format!("{} → {}", from.name(), transition.to),
+                        None => format!("{}", transition.to),
+                    }}
+                </div>
+                {transition.note.clone().map(|note| view! { <div class="mx-2">{note}</div> })}
+                <div class="text-center mx-2">{event.event_date.format("%B %d").to_string()}</div>
+                <div class="text-center">{move || humanized_time.get()}</div>
+            </div>
+        }
+        .into_any(),
+        shared::events::EventData::Plant(other_plant_id) => {
+            let other_plant_id = *other_plant_id;
+            let plant_storage_context = expect_context::<PlantStorageContext>();
+            view! {
+                <a href=format!("/plant/{}/view", other_plant_id) rel="external">
+                    {move || {
+                        plant_storage_context
+                            .get_plant_storage
+                            .get()
+                            .plants
+                            .get(&other_plant_id)
+                            .and_then(|(demographic, _)| demographic.name.clone())
+                            .unwrap_or("Unnamed plant".to_string())
+                    }}
+                </a>
+            }
+                .into_any()
+        }
     }
 }
 
@@ -118,6 +230,7 @@ fn event_data_input(
                 let local_time = RwSignal::new(Local::now().time());
                 let mut event_data = EventData::DateTime;
                 let value = RwSignal::new("".to_string());
+                let checked = RwSignal::new(Vec::<String>::new());
                 view! {
                     <div class="flex flex-col">
                         <div class="flex flex-row">
@@ -131,9 +244,22 @@ fn event_data_input(
                                     }
                                         .into_any()
                                 }
-                                shared::events::EventDataKind::Period => todo!(),
+                                shared::events::EventDataKind::Period => {
+                                    event_data = EventData::Period(Period::PeriodStart);
+                                    value.set("Start".to_string());
+                                    view! {
+                                        <Select value=value>
+                                            <option>"Start"</option>
+                                            <option>"End"</option>
+                                        </Select>
+                                        <DatePicker value=local_date />
+                                        <TimePicker value=local_time />
+                                    }
+                                        .into_any()
+                                }
                                 shared::events::EventDataKind::CustomEnum(data) => {
                                     event_data = EventData::CustomEnum(data.clone());
+                                    value.set(data.selected().cloned().unwrap_or_default());
                                     let custom_enum = data.clone();
 
                                     view! {
@@ -149,8 +275,127 @@ fn event_data_input(
                                     }
                                         .into_any()
                                 }
-                                shared::events::EventDataKind::Number => todo!(),
+                                shared::events::EventDataKind::MultiEnum(data) => {
+                                    event_data = EventData::MultiEnum(data.clone());
+                                    let options = data.options().clone();
+
+                                    view! {
+                                        <div class="flex flex-col">
+                                            {options
+                                                .into_iter()
+                                                .map(|option| {
+                                                    let is_checked = option.clone();
+                                                    let toggled = option.clone();
+                                                    view! {
+                                                        <label class="flex flex-row items-center gap-2">
+                                                            <input
+                                                                type="checkbox"
+                                                                prop:checked=move || {
+                                                                    checked.get().contains(&is_checked)
+                                                                }
+                                                                on:change=move |ev| {
+                                                                    let toggled = toggled.clone();
+                                                                    match event_target_checked(&ev) {
+                                                                        true => {
+                                                                            checked
+                                                                                .update(|checked| {
+                                                                                    if !checked.contains(&toggled) {
+                                                                                        checked.push(toggled);
+                                                                                    }
+                                                                                })
+                                                                        }
+                                                                        false => {
+                                                                            checked
+                                                                                .update(|checked| {
+                                                                                    checked.retain(|option| option != &toggled)
+                                                                                })
+                                                                        }
+                                                                    }
+                                                                }
+                                                            />
+                                                            {option}
+                                                        </label>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </div>
+                                        <DatePicker value=local_date />
+                                        <TimePicker value=local_time />
+                                    }
+                                        .into_any()
+                                }
+                                shared::events::EventDataKind::Number(format) => {
+                                    event_data = EventData::Number(0.0);
+                                    view! {
+                                        <Input value=value placeholder="Measurement" />
+                                        <p class="p-2">{format.unit.symbol().to_string()}</p>
+                                        <DatePicker value=local_date />
+                                        <TimePicker value=local_time />
+                                    }
+                                        .into_any()
+                                }
                                 shared::events::EventDataKind::String => view! {}.into_any(),
+                                shared::events::EventDataKind::Location => {
+                                    event_data = EventData::Location(Uuid::nil());
+                                    let location_context = expect_context::<LocationStorageContext>();
+
+                                    view! {
+                                        <Select value=value>
+                                            <option value="" disabled=true>
+                                                "Select a location"
+                                            </option>
+                                            <For
+                                                each=move || location_context.get_locations.get().0
+                                                key=|location| location.id
+                                                children=|location| {
+                                                    view! {
+                                                        <option value=location
+                                                            .id
+                                                            .to_string()>{location.name}</option>
+                                                    }
+                                                }
+                                            />
+                                        </Select>
+                                    }
+                                        .into_any()
+                                }
+                                // Lifecycle changes depend on the current state so they are made from the lifecycle panel
+                                shared::events::EventDataKind::Lifecycle => view! {}.into_any(),
+                                shared::events::EventDataKind::Plant => {
+                                    event_data = EventData::Plant(Uuid::nil());
+                                    let plant_storage_context = expect_context::<PlantStorageContext>();
+
+                                    view! {
+                                        <Select value=value>
+                                            <option value="" disabled=true>
+                                                "Select a plant"
+                                            </option>
+                                            <For
+                                                each=move || {
+                                                    plant_storage_context
+                                                        .get_plant_storage
+                                                        .get()
+                                                        .plants
+                                                        .into_iter()
+                                                        .filter(|(id, _)| *id != plant_id)
+                                                        .collect::<Vec<_>>()
+                                                }
+                                                key=|(id, _)| *id
+                                                children=|(id, (demographic, _))| {
+                                                    view! {
+                                                        <option value=id
+                                                            .to_string()>
+                                                            {demographic
+                                                                .name
+                                                                .unwrap_or("Unnamed plant".to_string())}
+                                                        </option>
+                                                    }
+                                                }
+                                            />
+                                        </Select>
+                                    }
+                                        .into_any()
+                                }
                             }}
                         </div>
 
@@ -160,9 +405,44 @@ fn event_data_input(
                                 .and_time(local_time.get_untracked());
                             let mut event_data = event_data.clone();
                             if let EventData::CustomEnum(mut custom_enum) = event_data {
-                                custom_enum.select_by_string(value.get());
+                                if custom_enum.select_by_string(&value.get_untracked()).is_err() {
+                                    return;
+                                }
                                 event_data = EventData::CustomEnum(custom_enum.clone());
                             }
+                            if let EventData::MultiEnum(mut multi_enum) = event_data {
+                                multi_enum.clear();
+                                for option in checked.get_untracked() {
+                                    if multi_enum.select_by_string(&option).is_err() {
+                                        return;
+                                    }
+                                }
+                                event_data = EventData::MultiEnum(multi_enum);
+                            }
+                            if let EventData::Location(_) = event_data {
+                                let Ok(location_id) = Uuid::parse_str(&value.get_untracked()) else {
+                                    return;
+                                };
+                                event_data = EventData::Location(location_id);
+                            }
+                            if let EventData::Period(_) = event_data {
+                                event_data = EventData::Period(match value.get_untracked().as_str() {
+                                    "End" => Period::PeriodEnd,
+                                    _ => Period::PeriodStart,
+                                });
+                            }
+                            if let EventData::Number(_) = event_data {
+                                let Ok(number) = value.get_untracked().trim().parse::<f64>() else {
+                                    return;
+                                };
+                                event_data = EventData::Number(number);
+                            }
+                            if let EventData::Plant(_) = event_data {
+                                let Ok(other_plant_id) = Uuid::parse_str(&value.get_untracked()) else {
+                                    return;
+                                };
+                                event_data = EventData::Plant(other_plant_id);
+                            }
                             new_event_action
                                 .clone()
                                 .dispatch(
@@ -1,13 +1,17 @@
 use leptos::prelude::*;
 use shared::events::{
     events_http::{GetEvent, GetEventType},
-    EventType,
+    EventDataKind, EventType,
 };
 use thaw::{Button, Dialog, DialogBody, DialogContent, DialogSurface, DialogTitle};
 use uuid::Uuid;
 
 use crate::{
-    components::plant_components::event::{EventEditComponent, EventViewComponent},
+    components::plant_components::{
+        event::{EventEditComponent, EventViewComponent},
+        measurement::MeasurementChartComponent,
+        period::PeriodSpansComponent,
+    },
     data_storage::events::{
         event_storage::request_events_resource, new_event_action, EventListContext,
     },
@@ -66,6 +70,15 @@ pub fn EventDisplayComponent(event_type: EventType, plant_id: Uuid, num_events:
                 </Dialog>
             </div>
 
+            {(event_type.kind == EventDataKind::Period)
+                .then(|| {
+                    view! { <PeriodSpansComponent event_type_id=event_type.id plant_id /> }
+                })}
+            {matches!(event_type.kind, EventDataKind::Number(_))
+                .then(|| {
+                    view! { <MeasurementChartComponent event_type=event_type.clone() plant_id /> }
+                })}
+
             {move || match events.get().is_empty() {
                 false => {
                     // We have succesfully requested the data
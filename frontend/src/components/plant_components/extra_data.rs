@@ -0,0 +1,291 @@
+use chrono::{Local, NaiveDate};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::plant::{
+    plant_http::{NewExtraDataField, SetExtraData},
+    ExtraData, ExtraDataField, ExtraDataKind, ExtraDataType, Plant,
+};
+use thaw::{Button, DatePicker, Input, Label, Select};
+use uuid::Uuid;
+
+use crate::{
+    data_storage::DirtyManagerContext,
+    server_helpers::{get_request, post_request},
+};
+
+/// Component to view and edit the user defined fields of a plant
+#[component]
+pub fn ExtraDataComponent(plant_id: Uuid) -> impl IntoView {
+    let fields = RwSignal::new(Vec::<ExtraDataField>::new());
+    let extra_data = RwSignal::new(Vec::<ExtraData>::new());
+
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        dirty_manager.get.get();
+        spawn_local(async move {
+            if let Some(new_fields) = get_extra_data_fields().await {
+                fields.set(new_fields);
+            }
+            if let Some(plant) = get_plant(plant_id).await {
+                extra_data.set(plant.extra_data);
+            }
+        });
+    });
+
+    // Every field paired with its current value on this plant if it has one
+    let rows = move || {
+        let extra_data = extra_data.get();
+        fields
+            .get()
+            .into_iter()
+            .map(|field| {
+                let current = extra_data.iter().find(|data| data.field.id == field.id).cloned();
+                (field, current)
+            })
+            .collect::<Vec<(ExtraDataField, Option<ExtraData>)>>()
+    };
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Details"</h3>
+            <For
+                each=rows
+                key=|(field, current)| {
+                    (field.id, current.as_ref().map(|current| current.date_changed))
+                }
+                children=move |(field, current)| {
+                    view! { <ExtraDataFieldComponent plant_id field current extra_data /> }
+                }
+            />
+            <NewExtraDataFieldComponent fields />
+        </div>
+    }
+}
+
+/// A single field of a plant with an input to change its value and the values it used to have
+#[component]
+fn ExtraDataFieldComponent(
+    plant_id: Uuid,
+    field: ExtraDataField,
+    current: Option<ExtraData>,
+    extra_data: RwSignal<Vec<ExtraData>>,
+) -> impl IntoView {
+    let value = RwSignal::new(match &current {
+        Some(ExtraData {
+            data: ExtraDataType::Date(_),
+            ..
+        })
+        | None => "".to_string(),
+        Some(current) => current.data.to_string(),
+    });
+    let date = RwSignal::new(match &current {
+        Some(ExtraData {
+            data: ExtraDataType::Date(date),
+            ..
+        }) => *date,
+        _ => Local::now().date_naive(),
+    });
+    let error = RwSignal::new("".to_string());
+
+    let unit = field.unit.clone().unwrap_or_default();
+    let kind = field.kind;
+    let field_id = field.id;
+
+    let save = move |_| {
+        let data = match kind {
+            ExtraDataKind::Text => ExtraDataType::Text(value.get_untracked()),
+            ExtraDataKind::Date => ExtraDataType::Date(date.get_untracked()),
+            ExtraDataKind::Number => match value.get_untracked().trim().parse::<f64>() {
+                Ok(number) => ExtraDataType::Number(number),
+                Err(_) => {
+                    error.set("ERROR: Value must be a number".to_string());
+                    return;
+                }
+            },
+        };
+        error.set("".to_string());
+        spawn_local(async move {
+            if let Some(new_extra_data) = set_extra_data(SetExtraData {
+                plant_id,
+                field_id,
+                data,
+            })
+            .await
+            {
+                extra_data.set(new_extra_data);
+            }
+        });
+    };
+
+    let history = current
+        .as_ref()
+        .map(|current| current.history.clone())
+        .unwrap_or_default();
+    let history_unit = unit.clone();
+
+    view! {
+        <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col">
+            <div class="flex flex-row flex-wrap items-center gap-2">
+                <p class="font-bold">{field.name.clone()}</p>
+                {match kind {
+                    ExtraDataKind::Date => view! { <DatePicker value=date /> }.into_any(),
+                    _ => view! { <Input value=value /> }.into_any(),
+                }}
+                <p>{unit}</p>
+                <Button on_click=save>"Save"</Button>
+                <Label>{move || error.get()}</Label>
+            </div>
+            <div class="flex flex-col text-sm text-muted-foreground">
+                {history
+                    .into_iter()
+                    .map(|(date_changed, data)| {
+                        view! {
+                            <p>
+                                {format!(
+                                    "{} {} until {}",
+                                    data,
+                                    history_unit,
+                                    date_changed.format("%Y-%m-%d"),
+                                )}
+                            </p>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
+
+/// Form to define a new field that every plant can have
+#[component]
+fn NewExtraDataFieldComponent(fields: RwSignal<Vec<ExtraDataField>>) -> impl IntoView {
+    let name = RwSignal::new("".to_string());
+    let kind = RwSignal::new("Text".to_string());
+    let unit = RwSignal::new("".to_string());
+    let error = RwSignal::new("".to_string());
+
+    let submit = move |_| {
+        let kind = match kind.get_untracked().as_str() {
+            "Date" => ExtraDataKind::Date,
+            "Number" => ExtraDataKind::Number,
+            _ => ExtraDataKind::Text,
+        };
+        if name.get_untracked().trim().is_empty() {
+            error.set("ERROR: Field name must not be empty".to_string());
+            return;
+        }
+        error.set("".to_string());
+        let new_field = NewExtraDataField {
+            name: name.get_untracked(),
+            kind,
+            unit: Some(unit.get_untracked()),
+        };
+        spawn_local(async move {
+            match new_extra_data_field(new_field).await {
+                Ok(field) => {
+                    fields.write().push(field);
+                    name.set("".to_string());
+                    unit.set("".to_string());
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-row flex-wrap items-center gap-2">
+            <Input value=name placeholder="New field name" />
+            <Select value=kind>
+                <option>"Text"</option>
+                <option>"Date"</option>
+                <option>"Number"</option>
+            </Select>
+            <Input value=unit placeholder="Unit" />
+            <Button on_click=submit>"Add Field"</Button>
+            <Label>{move || error.get()}</Label>
+        </div>
+    }
+}
+
+async fn get_plant(plant_id: Uuid) -> Option<Plant> {
+    let request = get_request(&format!("/plants/get/{}", plant_id));
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Plant>(&body_text).ok()
+}
+
+async fn get_extra_data_fields() -> Option<Vec<ExtraDataField>> {
+    let request = get_request("/plants/extra-data/fields");
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<ExtraDataField>>(&body_text).ok()
+}
+
+async fn set_extra_data(set_extra_data: SetExtraData) -> Option<Vec<ExtraData>> {
+    let request = post_request("/plants/extra-data/set");
+
+    let Some(request_with_json) = request
+        .json(&set_extra_data)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<ExtraData>>(&body_text).ok()
+}
+
+async fn new_extra_data_field(new_field: NewExtraDataField) -> Result<ExtraDataField, String> {
+    let request = post_request("/plants/extra-data/new-field");
+
+    let Some(request_with_json) = request
+        .json(&new_field)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a field was rejected in the body
+    serde_json::de::from_str::<ExtraDataField>(&body_text).map_err(|_| body_text)
+}
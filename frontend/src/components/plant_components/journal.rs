@@ -0,0 +1,453 @@
+//! The journal of a plant. Notes are shown interleaved with the events of the plant, newest first
+use chrono::NaiveDateTime;
+use leptos::{prelude::*, reactive::spawn_local};
+use pulldown_cmark::{html, CowStr, Event, Parser, Tag};
+use shared::{
+    events::EventInstance,
+    notes::{
+        notes_http::{EditNote, NewNote},
+        Note, NoteRevision, PHOTO_REFERENCE_SCHEME,
+    },
+    photos::Photo,
+};
+use thaw::{Button, Dialog, DialogBody, DialogContent, DialogSurface, DialogTitle, Label, Textarea};
+use uuid::Uuid;
+
+use crate::{
+    components::plant_components::event::{local_time_ago_humanized, EventViewComponent},
+    data_storage::{
+        events::{event_storage::EventStorageContext, EventListContext},
+        DirtyManagerContext,
+    },
+    server_helpers::{base_server_addr, get_request, post_request},
+};
+
+/// A single entry of the journal
+#[derive(Clone, PartialEq)]
+enum JournalEntry {
+    /// An event along with the notes written about it
+    Event(EventInstance, Vec<Note>),
+    Note(Note),
+}
+
+impl JournalEntry {
+    fn date(&self) -> NaiveDateTime {
+        match self {
+            JournalEntry::Event(event, _) => event.event_date,
+            JournalEntry::Note(note) => note.date_created,
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        match self {
+            JournalEntry::Event(event, _) => event.id,
+            JournalEntry::Note(note) => note.id,
+        }
+    }
+}
+
+#[component]
+pub fn JournalComponent(plant_id: Uuid) -> impl IntoView {
+    let notes = RwSignal::new(Vec::<Note>::new());
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+    let event_storage_context = expect_context::<EventStorageContext>();
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.notes.contains(&plant_id));
+        spawn_local(async move {
+            if let Some(new_notes) = get_plant_notes(plant_id).await {
+                notes.set(new_notes);
+            }
+            dirty_manager.write.update(|dirty_manager| {
+                dirty_manager.notes.remove(&plant_id);
+            });
+        });
+    });
+
+    // The event a new note will be written about
+    let note_event = RwSignal::new(None::<EventInstance>);
+
+    // Interleaves the notes with every event of the plant that has been loaded so far
+    let entries = Memo::new(move |_| {
+        let notes = notes.get();
+        let events: Vec<EventInstance> = event_storage_context
+            .get_event_storage
+            .get()
+            .plants_index
+            .get(&plant_id)
+            .map(|plant_events| {
+                plant_events
+                    .events
+                    .values()
+                    .flat_map(|events| events.values().cloned())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut entries: Vec<JournalEntry> = notes
+            .iter()
+            .filter(|note| {
+                !note
+                    .event_id
+                    .is_some_and(|event_id| events.iter().any(|event| event.id == event_id))
+            })
+            .map(|note| JournalEntry::Note(note.clone()))
+            .collect();
+        entries.extend(events.into_iter().map(|event| {
+            let event_notes = notes
+                .iter()
+                .filter(|note| note.event_id == Some(event.id))
+                .cloned()
+                .collect();
+            JournalEntry::Event(event, event_notes)
+        }));
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.date()));
+        entries
+    });
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Journal"</h3>
+            <NewNoteComponent plant_id note_event notes />
+            <For
+                each=move || entries.get()
+                key=|entry| (entry.id(), entry.clone())
+                children=move |entry| {
+                    match entry {
+                        JournalEntry::Note(note) => view! { <NoteComponent note notes /> }.into_any(),
+                        JournalEntry::Event(event, event_notes) => {
+                            view! { <JournalEventComponent event event_notes note_event notes /> }
+                                .into_any()
+                        }
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+/// An event in the journal along with the notes written about it
+#[component]
+fn JournalEventComponent(
+    event: EventInstance,
+    event_notes: Vec<Note>,
+    note_event: RwSignal<Option<EventInstance>>,
+    notes: RwSignal<Vec<Note>>,
+) -> impl IntoView {
+    let event_list_context: EventListContext = expect_context::<EventListContext>();
+    let event_type_id = event.event_type_id;
+    let event_name = move || {
+        event_list_context
+            .get_event_list
+            .get()
+            .0
+            .iter()
+            .find(|event_type| event_type.id == event_type_id)
+            .map(|event_type| event_type.name.clone())
+            .unwrap_or_default()
+    };
+    let note_target = event.clone();
+
+    view! {
+        <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col">
+            <div class="flex flex-row items-center gap-2">
+                <p class="font-bold">{event_name}</p>
+                <EventViewComponent event=event.clone() />
+                <Button on_click=move |_| note_event.set(Some(note_target.clone()))>
+                    "Add Note"
+                </Button>
+            </div>
+            {event_notes
+                .into_iter()
+                .map(|note| view! { <NoteComponent note notes /> })
+                .collect_view()}
+        </div>
+    }
+}
+
+/// Form to write a new note, optionally about an event
+#[component]
+fn NewNoteComponent(
+    plant_id: Uuid,
+    note_event: RwSignal<Option<EventInstance>>,
+    notes: RwSignal<Vec<Note>>,
+) -> impl IntoView {
+    let body = RwSignal::new("".to_string());
+    let error = RwSignal::new("".to_string());
+
+    let submit = move |_| {
+        let new_note = NewNote {
+            plant_id,
+            event_id: note_event.get_untracked().map(|event| event.id),
+            body: body.get_untracked(),
+        };
+        spawn_local(async move {
+            match new_note_request(new_note).await {
+                Ok(note) => {
+                    notes.write().insert(0, note);
+                    body.set("".to_string());
+                    note_event.set(None);
+                    error.set("".to_string());
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col gap-2">
+            {move || {
+                note_event
+                    .get()
+                    .map(|event| {
+                        view! {
+                            <div class="flex flex-row items-center gap-2">
+                                <p>
+                                    {format!(
+                                        "Writing about the event from {}",
+                                        local_time_ago_humanized(event.event_date),
+                                    )}
+                                </p>
+                                <Button on_click=move |_| note_event.set(None)>"Clear"</Button>
+                            </div>
+                        }
+                    })
+            }}
+            <Textarea
+                value=body
+                placeholder=format!(
+                    "Write a note in Markdown. Reference photos with ![caption]({}<photo id>)",
+                    PHOTO_REFERENCE_SCHEME,
+                )
+            />
+            <div class="flex flex-row items-center gap-2">
+                <Button on_click=submit>"Add Note"</Button>
+                <Label>{move || error.get()}</Label>
+            </div>
+        </div>
+    }
+}
+
+/// A rendered note with options to edit it and to view its previous versions
+#[component]
+fn NoteComponent(note: Note, notes: RwSignal<Vec<Note>>) -> impl IntoView {
+    let editing = RwSignal::new(false);
+    let body = RwSignal::new(note.body.clone());
+    let error = RwSignal::new("".to_string());
+    let history_open = RwSignal::new(false);
+    let history = RwSignal::new(Vec::<NoteRevision>::new());
+    let note_id = note.id;
+
+    let save = move |_| {
+        let edit = EditNote {
+            note_id,
+            body: body.get_untracked(),
+        };
+        spawn_local(async move {
+            match edit_note_request(edit).await {
+                Ok(edited_note) => {
+                    notes.update(|notes| {
+                        if let Some(note) = notes.iter_mut().find(|note| note.id == edited_note.id) {
+                            *note = edited_note;
+                        }
+                    });
+                    editing.set(false);
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    let open_history = move |_| {
+        history_open.set(true);
+        spawn_local(async move {
+            if let Some(revisions) = get_note_history(note_id).await {
+                history.set(revisions);
+            }
+        });
+    };
+
+    let edited = note.date_edited != note.date_created;
+    let rendered = render_note_markdown(&note.body);
+
+    view! {
+        <div class="p-2 m-1 rounded-(--radius) border-border border-1 flex flex-col">
+            <div class="flex flex-row items-center gap-2 text-sm text-muted-foreground">
+                <p>{local_time_ago_humanized(note.date_created)}</p>
+                {edited.then(|| view! { <p>{format!("edited {}", local_time_ago_humanized(note.date_edited))}</p> })}
+                <Button on_click=move |_| editing.set(!editing.get_untracked())>"Edit"</Button>
+                <Button on_click=open_history>"History"</Button>
+            </div>
+            {move || match editing.get() {
+                true => {
+                    view! {
+                        <div class="flex flex-col gap-2">
+                            <Textarea value=body />
+                            <div class="flex flex-row items-center gap-2">
+                                <Button on_click=save>"Save"</Button>
+                                <Label>{move || error.get()}</Label>
+                            </div>
+                        </div>
+                    }
+                        .into_any()
+                }
+                false => view! { <div class="note" inner_html=rendered.clone()></div> }.into_any(),
+            }}
+            <Dialog open=history_open>
+                <DialogSurface>
+                    <DialogBody>
+                        <DialogTitle>
+                            <div class="flex justify-between">
+                                <h2>"Note History"</h2>
+                                <Button on_click=move |_| history_open.set(false)>"Close"</Button>
+                            </div>
+                        </DialogTitle>
+                        <DialogContent>
+                            {move || match history.get().is_empty() {
+                                true => view! { <p>"This note has never been edited"</p> }.into_any(),
+                                false => {
+                                    history
+                                        .get()
+                                        .into_iter()
+                                        .map(|revision| {
+                                            view! {
+                                                <div class="flex flex-col p-2">
+                                                    <p class="text-sm text-muted-foreground">
+                                                        {format!(
+                                                            "Replaced {}",
+                                                            local_time_ago_humanized(revision.date_replaced),
+                                                        )}
+                                                    </p>
+                                                    <div inner_html=render_note_markdown(
+                                                        &revision.body,
+                                                    )></div>
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()
+                                        .into_any()
+                                }
+                            }}
+                        </DialogContent>
+                    </DialogBody>
+                </DialogSurface>
+            </Dialog>
+        </div>
+    }
+}
+
+/// Renders the Markdown body of a note into HTML
+///
+/// Raw HTML in the note is shown as text and photo references are pointed at the thumbnail of the photo
+pub fn render_note_markdown(body: &str) -> String {
+    let parser = Parser::new(body).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: photo_reference_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: photo_reference_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, parser);
+    rendered
+}
+
+/// Turns a photo reference into the url of the thumbnail of the photo. Other urls are left alone unless they would run scripts
+fn photo_reference_url(url: CowStr) -> CowStr {
+    if url.trim_start().to_lowercase().starts_with("javascript:") {
+        return CowStr::from("#");
+    }
+    let Some(photo_id) = url
+        .strip_prefix(PHOTO_REFERENCE_SCHEME)
+        .and_then(|photo_id| Uuid::parse_str(photo_id).ok())
+    else {
+        return url;
+    };
+    let thumbnail = Photo::thumbnail_location(photo_id);
+    CowStr::from(format!(
+        "{}{}",
+        base_server_addr(),
+        thumbnail.trim_start_matches('.')
+    ))
+}
+
+async fn get_plant_notes(plant_id: Uuid) -> Option<Vec<Note>> {
+    let request = get_request(&format!("/notes/plant/{}", plant_id));
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<Note>>(&body_text).ok()
+}
+
+async fn get_note_history(note_id: Uuid) -> Option<Vec<NoteRevision>> {
+    let request = get_request(&format!("/notes/history/{}", note_id));
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<NoteRevision>>(&body_text).ok()
+}
+
+async fn new_note_request(new_note: NewNote) -> Result<Note, String> {
+    send_note_request("/notes/new", &new_note).await
+}
+
+async fn edit_note_request(edit_note: EditNote) -> Result<Note, String> {
+    send_note_request("/notes/edit", &edit_note).await
+}
+
+async fn send_note_request<T: serde::Serialize>(path: &str, body: &T) -> Result<Note, String> {
+    let request = post_request(path);
+
+    let Some(request_with_json) = request.json(body).map_err(|e| log::error!("{e}")).ok() else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a note was rejected in the body
+    serde_json::de::from_str::<Note>(&body_text).map_err(|_| body_text)
+}
@@ -0,0 +1,146 @@
+use chrono::Utc;
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::{
+    events::{events_http::NewEvent, EventData, EventInstance, PLANT_STATE_ID},
+    plant::{PlantState, StateTransition},
+};
+use thaw::{Button, Input, Label, Select};
+use uuid::Uuid;
+
+use crate::{data_storage::plants::PlantStorageContext, server_helpers::post_request};
+
+/// Panel showing where the plant is in its life and the states it can move to next
+#[component]
+pub fn LifecycleComponent(plant_id: Uuid) -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+
+    let current_state = Memo::new(move |_| {
+        plant_storage_context
+            .get_plant_storage
+            .get()
+            .plants
+            .get(&plant_id)
+            .map(|(demographic, _)| demographic.plant_state.clone())
+    });
+
+    let next_state = RwSignal::new("".to_string());
+    let details = RwSignal::new("".to_string());
+    let note = RwSignal::new("".to_string());
+    let error = RwSignal::new("".to_string());
+
+    let change_state = move |_| {
+        let Some(to) = PlantState::from_name(&next_state.get_untracked(), details.get_untracked())
+        else {
+            error.set("ERROR: Pick the state to move to".to_string());
+            return;
+        };
+        let new_event = NewEvent {
+            event_type: Uuid::parse_str(PLANT_STATE_ID).unwrap(),
+            plant_id,
+            event_data: EventData::Lifecycle(StateTransition {
+                from: current_state.get_untracked(),
+                to,
+                note: match note.get_untracked().trim().is_empty() {
+                    true => None,
+                    false => Some(note.get_untracked().trim().to_string()),
+                },
+            }),
+            event_date: Utc::now().naive_utc(),
+        };
+        spawn_local(async move {
+            match change_plant_state(new_event).await {
+                Ok(_) => {
+                    next_state.set("".to_string());
+                    details.set("".to_string());
+                    note.set("".to_string());
+                    error.set("".to_string());
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Lifecycle"</h3>
+            <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col gap-2">
+                <p class="font-bold">
+                    {move || {
+                        current_state
+                            .get()
+                            .map(|state| state.to_string())
+                            .unwrap_or("...".to_string())
+                    }}
+                </p>
+                {move || {
+                    let next_states = current_state.get().unwrap_or_default().next_states();
+                    match next_states.is_empty() {
+                        true => {
+                            view! { <p>"This plant is retired and kept in the archive"</p> }
+                                .into_any()
+                        }
+                        false => {
+                            view! {
+                                <div class="flex flex-row flex-wrap items-center gap-2">
+                                    <Select value=next_state>
+                                        <option value="" disabled=true>
+                                            "Change state to"
+                                        </option>
+                                        {next_states
+                                            .into_iter()
+                                            .map(|name| view! { <option value=name>{name}</option> })
+                                            .collect_view()}
+                                    </Select>
+                                    <Input
+                                        value=details
+                                        placeholder=Signal::derive(move || {
+                                            match next_state.get().as_str() {
+                                                "Sick" => "Symptoms",
+                                                "Gifted" => "Who it was given to",
+                                                "Died" => "Cause",
+                                                "Sold" => "Who bought it",
+                                                _ => "No details needed",
+                                            }
+                                                .to_string()
+                                        })
+                                    />
+                                    <Input value=note placeholder="Note" />
+                                    <Button on_click=change_state>"Change state"</Button>
+                                    <Label>{move || error.get()}</Label>
+                                </div>
+                            }
+                                .into_any()
+                        }
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
+
+async fn change_plant_state(new_event: NewEvent) -> Result<EventInstance, String> {
+    let request = post_request("/events/new");
+
+    let Some(request_with_json) = request
+        .json(&new_event)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a transition was rejected in the body
+    serde_json::de::from_str::<EventInstance>(&body_text).map_err(|_| body_text)
+}
@@ -0,0 +1,185 @@
+use chrono::Utc;
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::plant::{plant_http::NewPlant, LineageNode, PlantDemographic, PlantLineage};
+use thaw::{Button, Input, Label};
+use uuid::Uuid;
+
+use crate::{
+    data_storage::{plants::PlantStorageContext, DirtyManagerContext},
+    server_helpers::{get_request, post_request},
+};
+
+/// Panel showing the plants this plant was propagated from and the plants propagated from it
+#[component]
+pub fn LineageComponent(plant_id: Uuid) -> impl IntoView {
+    let lineage = RwSignal::new(None::<PlantLineage>);
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.plants.len());
+        spawn_local(async move {
+            if let Some(new_lineage) = get_lineage(plant_id).await {
+                lineage.set(Some(new_lineage));
+            }
+        });
+    });
+
+    let cutting_name = RwSignal::new("".to_string());
+    let error = RwSignal::new("".to_string());
+
+    let propagate = move |_| {
+        if cutting_name.get_untracked().trim().is_empty() {
+            error.set("ERROR: Cutting name must not be empty".to_string());
+            return;
+        }
+        let new_plant = NewPlant {
+            name: cutting_name.get_untracked(),
+            timestamp: Utc::now().naive_utc().and_utc().timestamp(),
+            starting_events: vec![],
+            parent_id: Some(plant_id),
+        };
+        spawn_local(async move {
+            match propagate_plant(new_plant).await {
+                Ok(_) => {
+                    cutting_name.set("".to_string());
+                    error.set("".to_string());
+                    if let Some(new_lineage) = get_lineage(plant_id).await {
+                        lineage.set(Some(new_lineage));
+                    }
+                }
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Family Tree"</h3>
+            <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col gap-2">
+                {move || {
+                    lineage
+                        .get()
+                        .map(|lineage| {
+                            let mut ancestors = lineage.ancestors.clone();
+                            // Oldest ancestor first so the tree reads top down
+                            ancestors.reverse();
+                            view! {
+                                <div class="flex flex-row flex-wrap items-center gap-2">
+                                    {ancestors
+                                        .into_iter()
+                                        .map(|ancestor| {
+                                            view! {
+                                                <LineagePlantLink plant_id=ancestor />
+                                                <p>"→"</p>
+                                            }
+                                        })
+                                        .collect_view()}
+                                    <p class="font-bold">{move || plant_name(plant_id)}</p>
+                                </div>
+                                {match lineage.descendants.is_empty() {
+                                    true => view! { <p>"No plants propagated yet..."</p> }.into_any(),
+                                    false => {
+                                        view! { <LineageTreeComponent nodes=lineage.descendants /> }
+                                            .into_any()
+                                    }
+                                }}
+                            }
+                        })
+                }}
+                <div class="flex flex-row flex-wrap items-center gap-2">
+                    <Input value=cutting_name placeholder="Name of the new cutting" />
+                    <Button on_click=propagate>"Propagate"</Button>
+                    <Label>{move || error.get()}</Label>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// The descendants of a plant, each indented below its parent
+#[component]
+fn LineageTreeComponent(nodes: Vec<LineageNode>) -> impl IntoView {
+    view! {
+        <ul class="pl-4 border-l border-border">
+            {nodes
+                .into_iter()
+                .map(|node| {
+                    view! {
+                        <li>
+                            <LineagePlantLink plant_id=node.plant_id />
+                            {(!node.children.is_empty())
+                                .then(|| {
+                                    view! { <LineageTreeComponent nodes=node.children.clone() /> }
+                                        .into_any()
+                                })}
+                        </li>
+                    }
+                })
+                .collect_view()}
+        </ul>
+    }
+}
+
+/// Link to the plant page of a plant in the family tree
+#[component]
+fn LineagePlantLink(plant_id: Uuid) -> impl IntoView {
+    // The plant page only reads its id when it is first created so moving between plants needs a full load
+    view! {
+        <a rel="external" class="hover:underline" href=format!("/plant/{}/view", plant_id)>
+            {move || plant_name(plant_id)}
+        </a>
+    }
+}
+
+fn plant_name(plant_id: Uuid) -> String {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+    plant_storage_context
+        .get_plant_storage
+        .get()
+        .plants
+        .get(&plant_id)
+        .and_then(|(demographic, _)| demographic.name.clone())
+        .unwrap_or("Unnamed plant".to_string())
+}
+
+async fn get_lineage(plant_id: Uuid) -> Option<PlantLineage> {
+    let request = get_request(&format!("/plants/lineage/{}", plant_id));
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<PlantLineage>(&body_text).ok()
+}
+
+async fn propagate_plant(new_plant: NewPlant) -> Result<PlantDemographic, String> {
+    let request = post_request("/plants/new");
+
+    let Some(request_with_json) = request
+        .json(&new_plant)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a plant was rejected in the body
+    serde_json::de::from_str::<PlantDemographic>(&body_text).map_err(|_| body_text)
+}
@@ -0,0 +1,160 @@
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::events::{
+    events_http::{AggregateBucket, AggregateEvents, AggregatePoint},
+    EventDataKind, EventType,
+};
+use thaw::Select;
+use uuid::Uuid;
+
+use crate::{data_storage::DirtyManagerContext, server_helpers::post_request};
+
+static BUCKETS: [AggregateBucket; 3] = [
+    AggregateBucket::Day,
+    AggregateBucket::Week,
+    AggregateBucket::Month,
+];
+
+/// Chart of a numeric event type of a plant over time. Shows the average of each day, week or month with its min and max around it
+#[component]
+pub fn MeasurementChartComponent(event_type: EventType, plant_id: Uuid) -> impl IntoView {
+    let EventDataKind::Number(format) = event_type.kind.clone() else {
+        return view! {}.into_any();
+    };
+    let event_type_id = event_type.id;
+    let points = RwSignal::new(Vec::<AggregatePoint>::new());
+    let bucket = RwSignal::new(format!("{:?}", AggregateBucket::Week));
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.events.get(&plant_id).cloned());
+        let bucket = BUCKETS
+            .iter()
+            .find(|item| format!("{:?}", item) == bucket.get())
+            .copied()
+            .unwrap_or(AggregateBucket::Week);
+        spawn_local(async move {
+            if let Some(new_points) = aggregate_events(AggregateEvents {
+                event_type: event_type_id,
+                plant_id,
+                span: None,
+                bucket,
+            })
+            .await
+            {
+                points.set(new_points);
+            }
+        });
+    });
+
+    view! {
+        <div class="flex flex-col p-2">
+            <Select value=bucket>
+                {BUCKETS
+                    .iter()
+                    .map(|item| view! { <option>{format!("{:?}", item)}</option> })
+                    .collect_view()}
+            </Select>
+            {move || {
+                let points = points.get();
+                let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                    return view! { <p>"No measurements found..."</p> }.into_any();
+                };
+                let low = points.iter().map(|point| point.min).fold(f64::INFINITY, f64::min);
+                let high = points.iter().map(|point| point.max).fold(f64::NEG_INFINITY, f64::max);
+                let summary = format!(
+                    "Latest {}, lowest {}, highest {}",
+                    format.unit.format(round(last.avg)),
+                    format.unit.format(low),
+                    format.unit.format(high),
+                );
+                let range = (high - low).max(f64::EPSILON);
+                let first_date = first.bucket_start;
+                let count = points.len();
+                let length = last
+                    .bucket_start
+                    .signed_duration_since(first_date)
+                    .num_seconds()
+                    .max(1) as f64;
+                // Maps a point onto the 100 by 40 chart, higher values are drawn higher up
+                let position = move |point: &AggregatePoint, value: f64| {
+                    let x = match count {
+                        1 => 50.0,
+                        _ => {
+                            point.bucket_start.signed_duration_since(first_date).num_seconds()
+                                as f64 / length * 100.0
+                        }
+                    };
+                    let y = 40.0 - (value - low) / range * 40.0;
+                    format!("{:.2},{:.2}", x, y)
+                };
+                let average_line = points
+                    .iter()
+                    .map(|point| position(point, point.avg))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                // The band goes along the maximums and back along the minimums
+                let band = points
+                    .iter()
+                    .map(|point| position(point, point.max))
+                    .chain(points.iter().rev().map(|point| position(point, point.min)))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                view! {
+                    <div class="flex flex-col">
+                        <p class="text-sm">{summary}</p>
+                        <svg
+                            class="w-full h-32 text-secondary"
+                            viewBox="-2 -2 104 44"
+                            preserveAspectRatio="none"
+                        >
+                            <polygon points=band fill="currentColor" fill-opacity="0.2" />
+                            <polyline
+                                points=average_line
+                                fill="none"
+                                stroke="currentColor"
+                                stroke-width="1"
+                                vector-effect="non-scaling-stroke"
+                            />
+                        </svg>
+                    </div>
+                }
+                    .into_any()
+            }}
+        </div>
+    }
+    .into_any()
+}
+
+/// Averages can run to a lot of decimals, two are plenty for a summary
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+async fn aggregate_events(request: AggregateEvents) -> Option<Vec<AggregatePoint>> {
+    let request_builder = post_request("/events/aggregate");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<AggregatePoint>>(&body_text).ok()
+}
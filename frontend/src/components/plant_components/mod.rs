@@ -2,3 +2,13 @@ pub mod event;
 pub mod photo;
 pub mod photo_placeholder;
 pub mod event_display;
+pub mod timelapse;
+pub mod extra_data;
+pub mod journal;
+pub mod species;
+pub mod lineage;
+pub mod period;
+pub mod measurement;
+pub mod lifecycle;
+pub mod tags;
+pub mod timeline;
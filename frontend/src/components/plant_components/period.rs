@@ -0,0 +1,124 @@
+use chrono::{Local, NaiveDateTime};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::events::{events_http::GetPeriods, PeriodSpan};
+use uuid::Uuid;
+
+use crate::{data_storage::DirtyManagerContext, server_helpers::post_request};
+
+/// Every period of one event type of a plant drawn as spans along a shared timeline
+#[component]
+pub fn PeriodSpansComponent(event_type_id: Uuid, plant_id: Uuid) -> impl IntoView {
+    let spans = RwSignal::new(Vec::<PeriodSpan>::new());
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        dirty_manager.get.with(|dirty_manager| dirty_manager.events.get(&plant_id).cloned());
+        spawn_local(async move {
+            if let Some(new_spans) = get_periods(GetPeriods {
+                plant_id: Some(plant_id),
+                event_type: Some(event_type_id),
+                open_only: false,
+            })
+            .await
+            {
+                spans.set(new_spans);
+            }
+        });
+    });
+
+    view! {
+        {move || {
+            let spans = spans.get();
+            let now = Local::now().naive_utc();
+            let Some(timeline_start) = spans.first().map(|span| span.start.event_date) else {
+                return view! { <p>"No periods found..."</p> }.into_any();
+            };
+            let timeline_length = now
+                .signed_duration_since(timeline_start)
+                .num_seconds()
+                .max(1) as f64;
+            // Where along the timeline a date falls, as a percentage
+            let position = move |date: NaiveDateTime| {
+                date.signed_duration_since(timeline_start).num_seconds() as f64 / timeline_length
+                    * 100.0
+            };
+
+            view! {
+                <div class="flex flex-col gap-1 p-2">
+                    {spans
+                        .into_iter()
+                        .rev()
+                        .map(|span| {
+                            let left = position(span.start.event_date);
+                            let right = position(
+                                span.end.as_ref().map(|end| end.event_date).unwrap_or(now),
+                            );
+                            let days = span.duration(now).num_days();
+                            let label = match &span.end {
+                                Some(end) => {
+                                    format!(
+                                        "{} to {} ({} days)",
+                                        span.start.event_date.format("%B %d %Y"),
+                                        end.event_date.format("%B %d %Y"),
+                                        days,
+                                    )
+                                }
+                                None => {
+                                    format!(
+                                        "Since {} ({} days, still open)",
+                                        span.start.event_date.format("%B %d %Y"),
+                                        days,
+                                    )
+                                }
+                            };
+                            view! {
+                                <div class="flex flex-col">
+                                    <p class="text-sm">{label}</p>
+                                    <div class="relative w-full h-2 bg-border rounded-(--radius)">
+                                        <div
+                                            class="absolute h-2 bg-secondary rounded-(--radius)"
+                                            style=format!(
+                                                "left: {}%; width: {}%;",
+                                                left,
+                                                (right - left).max(1.0),
+                                            )
+                                        ></div>
+                                    </div>
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            }
+                .into_any()
+        }}
+    }
+}
+
+async fn get_periods(request: GetPeriods) -> Option<Vec<PeriodSpan>> {
+    let request_builder = post_request("/events/periods");
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<Vec<PeriodSpan>>(&body_text).ok()
+}
@@ -0,0 +1,205 @@
+use chrono::{Local, NaiveDateTime};
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::{
+    events::{
+        events_http::{GetEvent, GetEventType},
+        WATERED_EVENT_ID,
+    },
+    species::{species_http::SetPlantSpecies, CareProfile},
+};
+use thaw::{Button, Label, Select};
+use uuid::Uuid;
+
+use crate::{
+    data_storage::{
+        events::event_storage::request_events_resource, plants::PlantStorageContext,
+        species::SpeciesStorageContext,
+    },
+    server_helpers::post_request,
+};
+
+/// The species of a plant, the care profile of that species and when the plant should next be watered
+#[component]
+pub fn SpeciesComponent(plant_id: Uuid) -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+    let species_context: SpeciesStorageContext = expect_context::<SpeciesStorageContext>();
+
+    let species = Memo::new(move |_| {
+        let species_id = plant_storage_context
+            .get_plant_storage
+            .get()
+            .plants
+            .get(&plant_id)
+            .and_then(|(demographic, _)| demographic.species_id)?;
+        species_context.get_species.get().find(&species_id).cloned()
+    });
+
+    let selected = RwSignal::new("".to_string());
+    Effect::new(move |_| {
+        selected.set(
+            species
+                .get()
+                .map(|species| species.id.to_string())
+                .unwrap_or_default(),
+        );
+    });
+    let error = RwSignal::new("".to_string());
+
+    let link = move |_| {
+        let set_species = SetPlantSpecies {
+            plant_id,
+            species_id: Uuid::parse_str(&selected.get_untracked()).ok(),
+        };
+        spawn_local(async move {
+            match set_plant_species(set_species).await {
+                Ok(()) => error.set("".to_string()),
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    let get_watered = RwSignal::new(GetEvent {
+        event_type: Uuid::parse_str(WATERED_EVENT_ID).expect("Invalid UUID"),
+        plant_id,
+        request_details: GetEventType::LastNth(1, 0),
+    });
+    let last_watered = request_events_resource(get_watered);
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Species"</h3>
+            <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col gap-2">
+                <div class="flex flex-row flex-wrap items-center gap-2">
+                    <Select value=selected>
+                        <option value="">"Unknown species"</option>
+                        <For
+                            each=move || species_context.get_species.get().0
+                            key=|species| species.id
+                            children=|species| {
+                                view! {
+                                    <option value=species
+                                        .id
+                                        .to_string()>
+                                        {format!(
+                                            "{} ({})",
+                                            species.display_name(),
+                                            species.scientific_name,
+                                        )}
+                                    </option>
+                                }
+                            }
+                        />
+                    </Select>
+                    <Button on_click=link>"Set Species"</Button>
+                    <Label>{move || error.get()}</Label>
+                </div>
+                {move || {
+                    species
+                        .get()
+                        .map(|species| {
+                            let care = species.care.clone();
+                            let schedule_care = species.care.clone();
+                            view! {
+                                <p class="italic">
+                                    {format!("{} - {}", species.scientific_name, species.family)}
+                                </p>
+                                <CareProfileComponent care />
+                                <p class="font-bold">
+                                    {move || {
+                                        let last_watered = last_watered
+                                            .get()
+                                            .and_then(|events| events.first().map(|event| event.event_date));
+                                        watering_schedule(&schedule_care, last_watered)
+                                    }}
+                                </p>
+                            }
+                        })
+                }}
+            </div>
+        </div>
+    }
+}
+
+/// Every documented part of a care profile
+#[component]
+pub fn CareProfileComponent(care: CareProfile) -> impl IntoView {
+    let lines: Vec<String> = [
+        care.light.map(|light| format!("Light: {:?}", light)),
+        care.watering_interval.as_ref().map(|interval| {
+            format!(
+                "Water every {} to {} days",
+                interval.min_days, interval.max_days
+            )
+        }),
+        care.humidity
+            .map(|humidity| format!("Humidity: {:?}", humidity)),
+        care.temperature.as_ref().map(|temperature| {
+            format!(
+                "Temperature: {}°C to {}°C",
+                temperature.min_celsius, temperature.max_celsius
+            )
+        }),
+        care.toxic_to_pets.map(|toxic| match toxic {
+            true => "Toxic to pets".to_string(),
+            false => "Safe for pets".to_string(),
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    view! {
+        <div class="flex flex-col text-sm">
+            {lines.into_iter().map(|line| view! { <p>{line}</p> }).collect_view()}
+        </div>
+    }
+}
+
+/// Describes when the plant should next be watered based on the care profile of its species
+fn watering_schedule(care: &CareProfile, last_watered: Option<NaiveDateTime>) -> String {
+    let Some(last_watered) = last_watered else {
+        return match care.watering_interval {
+            Some(_) => "Log a watering to start the watering schedule".to_string(),
+            None => "".to_string(),
+        };
+    };
+    let Some((earliest, latest)) = care.next_watering(last_watered) else {
+        return "".to_string();
+    };
+    let now = Local::now().naive_utc();
+    match (now < earliest, now <= latest) {
+        (true, _) => format!(
+            "Water again between {} and {}",
+            earliest.format("%B %d"),
+            latest.format("%B %d")
+        ),
+        (false, true) => format!("Water by {}", latest.format("%B %d")),
+        (false, false) => format!("Watering overdue since {}", latest.format("%B %d")),
+    }
+}
+
+async fn set_plant_species(set_species: SetPlantSpecies) -> Result<(), String> {
+    let request = post_request("/species/set-plant");
+
+    let Some(request_with_json) = request
+        .json(&set_species)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+
+    match response.ok() {
+        true => Ok(()),
+        false => Err(response.text().await.unwrap_or_default()),
+    }
+}
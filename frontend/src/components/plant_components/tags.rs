@@ -0,0 +1,106 @@
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::plant::{normalize_tag, plant_http::SetPlantTags};
+use thaw::{Button, Input, Label};
+use uuid::Uuid;
+
+use crate::{data_storage::plants::PlantStorageContext, server_helpers::post_request};
+
+/// The tags of a plant, with the option to add new tags or remove existing ones
+#[component]
+pub fn TagsComponent(plant_id: Uuid) -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+
+    let tags = Memo::new(move |_| {
+        plant_storage_context
+            .get_plant_storage
+            .get()
+            .plants
+            .get(&plant_id)
+            .map(|(demographic, _)| demographic.tags.clone())
+            .unwrap_or_default()
+    });
+
+    let new_tag = RwSignal::new("".to_string());
+    let error = RwSignal::new("".to_string());
+
+    let save_tags = move |tags: Vec<String>| {
+        spawn_local(async move {
+            match set_plant_tags(SetPlantTags { plant_id, tags }).await {
+                Ok(_) => error.set("".to_string()),
+                Err(err) => error.set(format!("ERROR: {}", err)),
+            }
+        });
+    };
+
+    let add_tag = move |_| {
+        let Some(tag) = normalize_tag(&new_tag.get_untracked()) else {
+            error.set("ERROR: Tags can't be empty or longer than 100 characters".to_string());
+            return;
+        };
+        let mut all_tags = tags.get_untracked();
+        if !all_tags.contains(&tag) {
+            all_tags.push(tag);
+        }
+        new_tag.set("".to_string());
+        save_tags(all_tags);
+    };
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Tags"</h3>
+            <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-row flex-wrap items-center gap-2">
+                <For
+                    each=move || tags.get()
+                    key=|tag| tag.clone()
+                    children=move |tag| {
+                        let removed = tag.clone();
+                        view! {
+                            <span class="bg-accent px-2 rounded-(--radius) flex flex-row gap-1">
+                                {tag}
+                                <button on:click=move |_| {
+                                    save_tags(
+                                        tags
+                                            .get_untracked()
+                                            .into_iter()
+                                            .filter(|tag| tag != &removed)
+                                            .collect(),
+                                    )
+                                }>"x"</button>
+                            </span>
+                        }
+                    }
+                />
+                <Input value=new_tag placeholder="New tag" />
+                <Button on_click=add_tag>"Add tag"</Button>
+                <Label>{move || error.get()}</Label>
+            </div>
+        </div>
+    }
+}
+
+async fn set_plant_tags(set_plant_tags: SetPlantTags) -> Result<(), String> {
+    let request = post_request("/plants/tags/set");
+
+    let Some(request_with_json) = request
+        .json(&set_plant_tags)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+
+    match response.ok() {
+        true => Ok(()),
+        // The server explains why the tags were rejected in the body
+        false => Err(response.text().await.unwrap_or_default()),
+    }
+}
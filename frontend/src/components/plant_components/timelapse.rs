@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::photos::{NewTimelapse, TimelapseStatus};
+use thaw::{Button, Spinner};
+use uuid::Uuid;
+
+use crate::{
+    components::plant_components::photo::PhotoDisplayComponent,
+    server_helpers::{get_request, post_request},
+};
+
+/// Component that renders and plays a timelapse of every photo of a plant
+#[component]
+pub fn TimelapseComponent(plant_id: Uuid) -> impl IntoView {
+    let status = RwSignal::new(TimelapseStatus::NotGenerated);
+
+    spawn_local(async move {
+        if let Some(new_status) = get_timelapse_status(plant_id).await {
+            status.set(new_status);
+            poll_timelapse_status(plant_id, status);
+        }
+    });
+
+    let generate = move |_| {
+        spawn_local(async move {
+            if let Some(new_status) = request_timelapse(NewTimelapse {
+                plant_id,
+                frame_size: 512,
+                frame_delay_ms: 500,
+                date_caption: true,
+            })
+            .await
+            {
+                status.set(new_status);
+                poll_timelapse_status(plant_id, status);
+            }
+        })
+    };
+
+    view! {
+        <div class="flex flex-col items-center m-2">
+            {move || match status.get() {
+                TimelapseStatus::NotGenerated => {
+                    view! { <Button on_click=generate>"Watch it grow"</Button> }.into_any()
+                }
+                TimelapseStatus::Generating => {
+                    view! { <Spinner label="Generating timelapse..." /> }.into_any()
+                }
+                TimelapseStatus::Ready(location) => {
+                    view! {
+                        <div class="aspect-square max-w-[240px] max-h-[240px]">
+                            <PhotoDisplayComponent photo_location=location />
+                        </div>
+                        <Button on_click=generate>"Refresh timelapse"</Button>
+                    }
+                        .into_any()
+                }
+                TimelapseStatus::Failed(error) => {
+                    view! {
+                        <p class="text-foreground">{format!("Timelapse failed: {}", error)}</p>
+                        <Button on_click=generate>"Try again"</Button>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+/// Keeps checking on a timelapse that is still generating until the server is done with it
+fn poll_timelapse_status(plant_id: Uuid, status: RwSignal<TimelapseStatus>) {
+    if status.get_untracked() != TimelapseStatus::Generating {
+        return;
+    }
+    set_timeout(
+        move || {
+            spawn_local(async move {
+                if let Some(new_status) = get_timelapse_status(plant_id).await {
+                    status.set(new_status);
+                    poll_timelapse_status(plant_id, status);
+                }
+            })
+        },
+        Duration::from_secs(2),
+    );
+}
+
+async fn get_timelapse_status(plant_id: Uuid) -> Option<TimelapseStatus> {
+    let request = get_request(&format!("/photos/timelapse/{}", plant_id));
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<TimelapseStatus>(&body_text).ok()
+}
+
+async fn request_timelapse(new_timelapse: NewTimelapse) -> Option<TimelapseStatus> {
+    let request = post_request("/photos/timelapse/new");
+
+    let Some(request_with_json) = request
+        .json(&new_timelapse)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return None;
+    };
+
+    match serde_json::de::from_str::<TimelapseStatus>(&body_text) {
+        Ok(status) => Some(status),
+        // The server answers with a plain error message when it can't make a timelapse
+        Err(_) => Some(TimelapseStatus::Failed(body_text)),
+    }
+}
@@ -0,0 +1,206 @@
+use leptos::{prelude::*, reactive::spawn_local};
+use shared::plant::plant_http::{
+    GetTimeline, GetTimelineResponse, TimelineCursor, TimelineEntry, TimelineKind,
+};
+use thaw::{Button, Select};
+use uuid::Uuid;
+
+use crate::{
+    components::plant_components::{
+        event::{local_time_ago_humanized, EventViewComponent},
+        journal::render_note_markdown,
+        photo::PhotoDisplayComponent,
+    },
+    data_storage::{events::EventListContext, DirtyManagerContext},
+    server_helpers::post_request,
+};
+
+static PAGINATION: i64 = 25;
+
+static KINDS: [TimelineKind; 3] = [TimelineKind::Event, TimelineKind::Note, TimelineKind::Photo];
+
+/// Everything that has happened to a plant in one scrolling list, newest first
+#[component]
+pub fn TimelineComponent(plant_id: Uuid) -> impl IntoView {
+    let event_list_context: EventListContext = expect_context::<EventListContext>();
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    let kind_filter = RwSignal::new("".to_string());
+    let event_type_filter = RwSignal::new("".to_string());
+
+    let filters = Memo::new(move |_| GetTimeline {
+        kinds: KINDS
+            .iter()
+            .filter(|kind| format!("{:?}", kind) == kind_filter.get())
+            .copied()
+            .collect(),
+        event_types: Uuid::parse_str(&event_type_filter.get())
+            .ok()
+            .into_iter()
+            .collect(),
+        cursor: None,
+        limit: PAGINATION,
+    });
+
+    let entries = RwSignal::new(Vec::<TimelineEntry>::new());
+    let cursor = RwSignal::new(None::<TimelineCursor>);
+    // Bumped whenever the filters change so that pages requested with old filters are thrown away
+    let generation = StoredValue::new(0u32);
+
+    let load_page = move |first_page: bool| {
+        let request = GetTimeline {
+            cursor: match first_page {
+                true => None,
+                false => cursor.get_untracked(),
+            },
+            ..filters.get_untracked()
+        };
+        let request_generation = generation.get_value();
+        spawn_local(async move {
+            let Some(response) = get_timeline(plant_id, request).await else {
+                return;
+            };
+            if generation.get_value() != request_generation {
+                return;
+            }
+            match first_page {
+                true => entries.set(response.entries),
+                false => entries.update(|entries| entries.extend(response.entries)),
+            }
+            cursor.set(response.next_cursor);
+        });
+    };
+
+    // New filters, events or notes start the timeline over
+    Effect::new(move |_| {
+        filters.track();
+        dirty_manager.get.with(|dirty_manager| {
+            (
+                dirty_manager.events.contains_key(&plant_id),
+                dirty_manager.notes.contains(&plant_id),
+            )
+        });
+        generation.update_value(|generation| *generation += 1);
+        load_page(true);
+    });
+
+    let event_type_name = move |event_type_id: Uuid| {
+        event_list_context
+            .get_event_list
+            .get()
+            .0
+            .iter()
+            .find(|event_type| event_type.id == event_type_id)
+            .map(|event_type| event_type.name.clone())
+            .unwrap_or("Event".to_string())
+    };
+
+    view! {
+        <div class="w-full flex flex-col">
+            <h3 class="text-secondary p-4 text-lg font-bold">"Timeline"</h3>
+            <div class="flex flex-row flex-wrap items-center gap-2 p-1">
+                <Select value=kind_filter>
+                    <option value="">"Everything"</option>
+                    {KINDS
+                        .iter()
+                        .map(|kind| view! { <option>{format!("{:?}", kind)}</option> })
+                        .collect_view()}
+                </Select>
+                <Select value=event_type_filter>
+                    <option value="">"Any event type"</option>
+                    <For
+                        each=move || event_list_context.get_event_list.get().0
+                        key=|event_type| event_type.id
+                        children=|event_type| {
+                            view! {
+                                <option value=event_type.id.to_string()>{event_type.name}</option>
+                            }
+                        }
+                    />
+                </Select>
+            </div>
+            <div class="bg-(--card) p-2 m-1 rounded-(--radius) flex flex-col max-h-[40rem] overflow-y-auto">
+                <For
+                    each=move || entries.get()
+                    key=|entry| entry.id()
+                    children=move |entry| {
+                        let date = entry.date();
+                        let content = match entry {
+                            TimelineEntry::Event(event) => {
+                                let event_type_id = event.event_type_id;
+                                view! {
+                                    <p class="font-bold">{move || event_type_name(event_type_id)}</p>
+                                    <EventViewComponent event=event />
+                                }
+                                    .into_any()
+                            }
+                            TimelineEntry::Note(note) => {
+                                view! {
+                                    <p class="font-bold">"Note"</p>
+                                    <div class="note" inner_html=render_note_markdown(&note.body)></div>
+                                }
+                                    .into_any()
+                            }
+                            TimelineEntry::Photo(photo) => {
+                                view! {
+                                    <p class="font-bold">"Photo"</p>
+                                    <div class="w-32 h-32">
+                                        <PhotoDisplayComponent photo_location=photo.thumbnail_location />
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        };
+                        view! {
+                            <div class="flex flex-row gap-3 p-2 border-b-1 border-border">
+                                <div class="flex flex-col w-28 shrink-0 text-sm text-muted-foreground">
+                                    <p>{date.format("%b %d %Y").to_string()}</p>
+                                    <p>{local_time_ago_humanized(date)}</p>
+                                </div>
+                                <div class="flex flex-col">{content}</div>
+                            </div>
+                        }
+                    }
+                />
+                {move || match entries.get().is_empty() {
+                    true => view! { <p>"Nothing has happened yet..."</p> }.into_any(),
+                    false => view! {}.into_any(),
+                }}
+                {move || {
+                    cursor
+                        .get()
+                        .is_some()
+                        .then(|| view! { <Button on_click=move |_| load_page(false)>"Load older"</Button> })
+                }}
+            </div>
+        </div>
+    }
+}
+
+async fn get_timeline(plant_id: Uuid, request: GetTimeline) -> Option<GetTimelineResponse> {
+    let request_builder = post_request(&format!("/plants/{}/timeline", plant_id));
+
+    let Some(request_with_json) = request_builder
+        .json(&request)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return None;
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        //TODO: Background Error message logging
+        return None;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return None;
+    };
+
+    serde_json::de::from_str::<GetTimelineResponse>(&body_text).ok()
+}
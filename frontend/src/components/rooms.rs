@@ -0,0 +1,218 @@
+//! Plants grouped by the room they currently live in, along with a form to add new locations
+use leptos::prelude::*;
+use shared::locations::{locations_http::NewLocation, Direction, LightLevel, Location, LocationKind};
+use thaw::{Button, Input, Label, Select};
+use uuid::Uuid;
+
+use crate::{
+    components::plant_card::PlantCard,
+    data_storage::{
+        locations::{new_location_action, LocationStorageContext},
+        plants::PlantStorageContext,
+    },
+};
+
+static LOCATION_KINDS: [LocationKind; 3] =
+    [LocationKind::Home, LocationKind::Room, LocationKind::Spot];
+
+static LIGHT_LEVELS: [LightLevel; 4] = [
+    LightLevel::Low,
+    LightLevel::Medium,
+    LightLevel::BrightIndirect,
+    LightLevel::Direct,
+];
+
+static DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+#[component]
+pub fn RoomsComponent() -> impl IntoView {
+    let plant_storage_context: PlantStorageContext = expect_context::<PlantStorageContext>();
+    let location_context: LocationStorageContext = expect_context::<LocationStorageContext>();
+
+    // Every room with the plants inside of it. Plants in a spot count towards the spots room
+    let rooms = Memo::new(move |_| {
+        let locations = location_context.get_locations.get().0;
+        let plants = plant_storage_context.get_plant_storage.get().plants;
+
+        let mut rooms: Vec<(Option<Location>, Vec<Uuid>)> = locations
+            .iter()
+            .filter(|location| location.kind == LocationKind::Room)
+            .map(|room| (Some(room.clone()), vec![]))
+            .collect();
+        let mut unplaced = vec![];
+
+        for (plant_id, (demographic, _)) in plants.iter() {
+            let room = demographic
+                .current_location
+                .and_then(|location_id| locations.iter().find(|location| location.id == location_id))
+                .and_then(|location| location.room(&locations));
+            match room.and_then(|room| {
+                rooms
+                    .iter_mut()
+                    .find(|(location, _)| location.as_ref().map(|location| location.id) == Some(room.id))
+            }) {
+                Some((_, room_plants)) => room_plants.push(*plant_id),
+                None => unplaced.push(*plant_id),
+            }
+        }
+
+        rooms.push((None, unplaced));
+        rooms
+    });
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full">
+            <NewLocationComponent />
+            <For
+                each=move || rooms.get()
+                key=|(room, plants)| (room.as_ref().map(|room| room.id), plants.clone())
+                children=|(room, plants)| {
+                    let title = match &room {
+                        Some(room) => room.name.clone(),
+                        None => "Unplaced".to_string(),
+                    };
+                    let details = room
+                        .as_ref()
+                        .map(|room| {
+                            [
+                                room.light_level.map(|light| format!("{:?} light", light)),
+                                room.direction.map(|direction| format!("Facing {:?}", direction)),
+                            ]
+                                .into_iter()
+                                .flatten()
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_default();
+                    view! {
+                        <div class="w-full flex flex-col p-3">
+                            <div class="flex flex-row items-baseline gap-3">
+                                <h2 class="text-secondary text-xl font-bold">{title}</h2>
+                                <p class="text-muted-foreground">{details}</p>
+                            </div>
+                            {match plants.is_empty() {
+                                false => {
+                                    view! {
+                                        <div class="grid grid-cols-2 md:grid-cols-4 lg:grid-cols-6 xl:grid-cols-8 gap-3 p-3">
+                                            <For
+                                                each=move || plants.clone()
+                                                key=|plant_id| *plant_id
+                                                children=|plant_id| {
+                                                    view! { <PlantCard plant_id /> }
+                                                }
+                                            />
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                true => view! { <p class="p-3">"No plants here yet..."</p> }.into_any(),
+                            }}
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+/// Form to create a new home, room or spot
+#[component]
+pub fn NewLocationComponent() -> impl IntoView {
+    let location_context: LocationStorageContext = expect_context::<LocationStorageContext>();
+    let new_location_action = new_location_action();
+
+    let name = RwSignal::new("".to_string());
+    let kind = RwSignal::new(format!("{:?}", LocationKind::Room));
+    let parent = RwSignal::new("".to_string());
+    let light_level = RwSignal::new("".to_string());
+    let direction = RwSignal::new("".to_string());
+
+    let selected_kind = move || {
+        LOCATION_KINDS
+            .iter()
+            .find(|location_kind| format!("{:?}", location_kind) == kind.get())
+            .copied()
+            .unwrap_or(LocationKind::Room)
+    };
+
+    // Only locations one level up can be chosen as the parent
+    let possible_parents = move || {
+        let parent_kind = selected_kind().parent_kind();
+        location_context
+            .get_locations
+            .get()
+            .0
+            .into_iter()
+            .filter(|location| Some(location.kind) == parent_kind)
+            .collect::<Vec<Location>>()
+    };
+
+    let submit = move |_| {
+        new_location_action.dispatch(NewLocation {
+            name: name.get_untracked(),
+            kind: selected_kind(),
+            parent_id: Uuid::parse_str(&parent.get_untracked()).ok(),
+            light_level: LIGHT_LEVELS
+                .iter()
+                .find(|level| format!("{:?}", level) == light_level.get_untracked())
+                .copied(),
+            direction: DIRECTIONS
+                .iter()
+                .find(|facing| format!("{:?}", facing) == direction.get_untracked())
+                .copied(),
+        });
+    };
+
+    let response = move || match new_location_action.value().get() {
+        Some(Ok(location)) => format!("Added {}", location.name),
+        Some(Err(err)) => format!("ERROR: {}", err),
+        None => "".to_string(),
+    };
+
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-row flex-wrap items-center gap-2">
+            <Input value=name placeholder="Location name" />
+            <Select value=kind>
+                {LOCATION_KINDS
+                    .iter()
+                    .map(|location_kind| view! { <option>{format!("{:?}", location_kind)}</option> })
+                    .collect_view()}
+            </Select>
+            <Select value=parent>
+                <option value="">"No parent"</option>
+                <For
+                    each=possible_parents
+                    key=|location| location.id
+                    children=|location| {
+                        view! { <option value=location.id.to_string()>{location.name}</option> }
+                    }
+                />
+            </Select>
+            <Select value=light_level>
+                <option value="">"Unknown light"</option>
+                {LIGHT_LEVELS
+                    .iter()
+                    .map(|level| view! { <option>{format!("{:?}", level)}</option> })
+                    .collect_view()}
+            </Select>
+            <Select value=direction>
+                <option value="">"Unknown direction"</option>
+                {DIRECTIONS
+                    .iter()
+                    .map(|facing| view! { <option>{format!("{:?}", facing)}</option> })
+                    .collect_view()}
+            </Select>
+            <Button on_click=submit>"Add Location"</Button>
+            <Label>{response}</Label>
+        </div>
+    }
+}
@@ -0,0 +1,158 @@
+//! The species catalog along with a form to add new species to it
+use leptos::prelude::*;
+use shared::{
+    locations::LightLevel,
+    species::{
+        species_http::NewSpecies, CareProfile, Humidity, TemperatureRange, WateringInterval,
+    },
+};
+use thaw::{Button, Input, Label, Select};
+
+use crate::{
+    components::plant_components::species::CareProfileComponent,
+    data_storage::species::{new_species_action, SpeciesStorageContext},
+};
+
+static LIGHT_LEVELS: [LightLevel; 4] = [
+    LightLevel::Low,
+    LightLevel::Medium,
+    LightLevel::BrightIndirect,
+    LightLevel::Direct,
+];
+
+static HUMIDITIES: [Humidity; 3] = [Humidity::Low, Humidity::Medium, Humidity::High];
+
+#[component]
+pub fn SpeciesCatalogComponent() -> impl IntoView {
+    let species_context: SpeciesStorageContext = expect_context::<SpeciesStorageContext>();
+
+    view! {
+        <div class="flex flex-col items-center self-center w-full">
+            <NewSpeciesComponent />
+            <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-3 p-3 w-full">
+                <For
+                    each=move || species_context.get_species.get().0
+                    key=|species| species.id
+                    children=|species| {
+                        view! {
+                            <div class="bg-(--card) p-2 rounded-(--radius) flex flex-col">
+                                <h2 class="text-secondary text-lg font-bold">
+                                    {species.display_name().to_string()}
+                                </h2>
+                                <p class="italic">
+                                    {format!("{} - {}", species.scientific_name, species.family)}
+                                </p>
+                                <p class="text-sm text-muted-foreground">
+                                    {species.common_names.join(", ")}
+                                </p>
+                                <CareProfileComponent care=species.care.clone() />
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
+
+/// Form to add a user defined species to the catalog
+#[component]
+pub fn NewSpeciesComponent() -> impl IntoView {
+    let new_species_action = new_species_action();
+
+    let scientific_name = RwSignal::new("".to_string());
+    let common_names = RwSignal::new("".to_string());
+    let family = RwSignal::new("".to_string());
+    let light = RwSignal::new("".to_string());
+    let humidity = RwSignal::new("".to_string());
+    let water_min = RwSignal::new("".to_string());
+    let water_max = RwSignal::new("".to_string());
+    let temperature_min = RwSignal::new("".to_string());
+    let temperature_max = RwSignal::new("".to_string());
+    let toxic = RwSignal::new("".to_string());
+
+    let submit = move |_| {
+        let watering_interval = match (
+            water_min.get_untracked().trim().parse::<u32>(),
+            water_max.get_untracked().trim().parse::<u32>(),
+        ) {
+            (Ok(min_days), Ok(max_days)) => Some(WateringInterval { min_days, max_days }),
+            _ => None,
+        };
+        let temperature = match (
+            temperature_min.get_untracked().trim().parse::<f32>(),
+            temperature_max.get_untracked().trim().parse::<f32>(),
+        ) {
+            (Ok(min_celsius), Ok(max_celsius)) => Some(TemperatureRange {
+                min_celsius,
+                max_celsius,
+            }),
+            _ => None,
+        };
+        new_species_action.dispatch(NewSpecies {
+            scientific_name: scientific_name.get_untracked(),
+            common_names: common_names
+                .get_untracked()
+                .split(',')
+                .map(|name| name.to_string())
+                .collect(),
+            family: family.get_untracked(),
+            care: CareProfile {
+                light: LIGHT_LEVELS
+                    .iter()
+                    .find(|level| format!("{:?}", level) == light.get_untracked())
+                    .copied(),
+                watering_interval,
+                humidity: HUMIDITIES
+                    .iter()
+                    .find(|level| format!("{:?}", level) == humidity.get_untracked())
+                    .copied(),
+                temperature,
+                toxic_to_pets: match toxic.get_untracked().as_str() {
+                    "Toxic" => Some(true),
+                    "Safe" => Some(false),
+                    _ => None,
+                },
+            },
+        });
+    };
+
+    let response = move || match new_species_action.value().get() {
+        Some(Ok(species)) => format!("Added {}", species.scientific_name),
+        Some(Err(err)) => format!("ERROR: {}", err),
+        None => "".to_string(),
+    };
+
+    view! {
+        <div class="bg-(--card) p-2 rounded-(--radius) flex flex-row flex-wrap items-center gap-2">
+            <Input value=scientific_name placeholder="Scientific name" />
+            <Input value=common_names placeholder="Common names, comma separated" />
+            <Input value=family placeholder="Family" />
+            <Select value=light>
+                <option value="">"Unknown light"</option>
+                {LIGHT_LEVELS
+                    .iter()
+                    .map(|level| view! { <option>{format!("{:?}", level)}</option> })
+                    .collect_view()}
+            </Select>
+            <Select value=humidity>
+                <option value="">"Unknown humidity"</option>
+                {HUMIDITIES
+                    .iter()
+                    .map(|level| view! { <option>{format!("{:?}", level)}</option> })
+                    .collect_view()}
+            </Select>
+            <Input value=water_min placeholder="Water every min days" />
+            <Input value=water_max placeholder="Water every max days" />
+            <Input value=temperature_min placeholder="Min °C" />
+            <Input value=temperature_max placeholder="Max °C" />
+            <Select value=toxic>
+                <option value="">"Unknown toxicity"</option>
+                <option>"Toxic"</option>
+                <option>"Safe"</option>
+            </Select>
+            <Button on_click=submit>"Add Species"</Button>
+            <Label>{response}</Label>
+        </div>
+    }
+}
@@ -0,0 +1,116 @@
+//! Stores a local copy of the saved smart collections
+
+use leptos::{
+    prelude::{Write, WriteSignal},
+    reactive::spawn_local,
+};
+
+use serde::{Deserialize, Serialize};
+use shared::collections::{collections_http::NewCollection, Collection};
+use uuid::Uuid;
+
+use crate::{
+    data_storage::DirtyManagerContext,
+    server_helpers::{get_request, post_request},
+};
+
+use leptos::prelude::*;
+
+#[component]
+pub fn CollectionStorageComponent(children: Children) -> impl IntoView {
+    let (state, set_state) = signal(CollectionList::default());
+
+    provide_context(CollectionStorageContext {
+        get_collections: state,
+        write_collections: set_state,
+    });
+
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        // Rerequest every time a collection is created or deleted
+        dirty_manager.get.with(|dirty_manager| dirty_manager.collections.len());
+        spawn_local(get_collections(set_state))
+    });
+
+    view! { {children()} }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CollectionStorageContext {
+    pub get_collections: ReadSignal<CollectionList>,
+    pub write_collections: WriteSignal<CollectionList>,
+}
+
+/// Local in memory store of every saved collection
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct CollectionList(pub Vec<Collection>);
+
+impl CollectionList {
+    pub fn find(&self, collection_id: &Uuid) -> Option<&Collection> {
+        self.0
+            .iter()
+            .find(|collection| &collection.id == collection_id)
+    }
+}
+
+async fn get_collections(collection_write: WriteSignal<CollectionList>) {
+    let request = get_request("/collections/get-all");
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    let Ok(response) = serde_json::de::from_str::<Vec<Collection>>(&body_text) else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    collection_write.write().0 = response;
+}
+
+pub fn new_collection_action() -> Action<NewCollection, Result<Collection, String>> {
+    Action::new_local(|input: &NewCollection| new_collection(input.clone()))
+}
+
+async fn new_collection(new_collection: NewCollection) -> Result<Collection, String> {
+    let request = post_request("/collections/new");
+
+    let Some(request_with_json) = request
+        .json(&new_collection)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a filter was rejected in the body
+    serde_json::de::from_str::<Collection>(&body_text).map_err(|_| body_text)
+}
+
+/// Deletes a collection, the list is refreshed once the server reports the change
+pub async fn delete_collection(collection_id: Uuid) {
+    let request = get_request(&format!("/collections/delete/{}", collection_id));
+
+    let Some(_response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+}
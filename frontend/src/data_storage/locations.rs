@@ -0,0 +1,103 @@
+//! Stores a local copy of the users locations so plants can be placed and grouped by room
+
+use leptos::{
+    prelude::{Write, WriteSignal},
+    reactive::spawn_local,
+};
+
+use serde::{Deserialize, Serialize};
+use shared::locations::{locations_http::NewLocation, Location};
+
+use crate::{
+    data_storage::DirtyManagerContext,
+    server_helpers::{get_request, post_request},
+};
+
+use leptos::prelude::*;
+
+#[component]
+pub fn LocationStorageComponent(children: Children) -> impl IntoView {
+    let (state, set_state) = signal(LocationList::default());
+
+    provide_context(LocationStorageContext {
+        get_locations: state,
+        write_locations: set_state,
+    });
+
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        // Rerequest every time a location is dirtied
+        dirty_manager.get.with(|dirty_manager| dirty_manager.locations.len());
+        spawn_local(get_locations(set_state))
+    });
+
+    view! { {children()} }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct LocationStorageContext {
+    pub get_locations: ReadSignal<LocationList>,
+    pub write_locations: WriteSignal<LocationList>,
+}
+
+/// Local in memory store of every location
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct LocationList(pub Vec<Location>);
+
+impl LocationList {
+    pub fn find(&self, location_id: &uuid::Uuid) -> Option<&Location> {
+        self.0.iter().find(|location| &location.id == location_id)
+    }
+}
+
+async fn get_locations(locations_write: WriteSignal<LocationList>) {
+    let request = get_request("/locations/get-all");
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    let Ok(response) = serde_json::de::from_str::<Vec<Location>>(&body_text) else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    locations_write.write().0 = response;
+}
+
+pub fn new_location_action() -> Action<NewLocation, Result<Location, String>> {
+    Action::new_local(|input: &NewLocation| new_location(input.clone()))
+}
+
+async fn new_location(new_location: NewLocation) -> Result<Location, String> {
+    let request = post_request("/locations/new");
+
+    let Some(request_with_json) = request
+        .json(&new_location)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a location was rejected in the body
+    serde_json::de::from_str::<Location>(&body_text).map_err(|_| body_text)
+}
@@ -8,12 +8,19 @@ use shared::DirtyCache;
 use uuid::Uuid;
 
 use crate::{
-    data_storage::{events::EventStorageComponent, plants::PlantStorageComponent},
+    data_storage::{
+        collections::CollectionStorageComponent, events::EventStorageComponent,
+        locations::LocationStorageComponent, plants::PlantStorageComponent,
+        species::SpeciesStorageComponent,
+    },
     server_helpers::base_server_addr,
 };
 
+pub mod collections;
 pub mod events;
+pub mod locations;
 pub mod plants;
+pub mod species;
 
 #[component]
 pub fn AppStorageComponent(children: Children) -> impl IntoView {
@@ -53,16 +60,54 @@ pub fn AppStorageComponent(children: Children) -> impl IntoView {
                             });
                     });
                 }
+                shared::CacheType::Events(event_id, plants) => {
+                    pv_set.update(|dirty_context| {
+                        for (plant_id, date_of_event) in plants {
+                            dirty_context
+                                .events
+                                .entry(plant_id)
+                                .and_modify(|(plant_events, _)| {
+                                    plant_events.insert(event_id);
+                                })
+                                .or_insert_with(|| {
+                                    let mut hash = HashSet::new();
+                                    hash.insert(event_id);
+                                    (hash, date_of_event)
+                                });
+                        }
+                    });
+                }
                 shared::CacheType::EventType(uuid) => {
                     pv_set.write().event_types.insert(uuid);
                 }
+                shared::CacheType::Location(uuid) => {
+                    pv_set.write().locations.insert(uuid);
+                }
+                shared::CacheType::ExtraDataField(uuid) => {
+                    pv_set.write().extra_data_fields.insert(uuid);
+                }
+                shared::CacheType::Note(plant_id, _note_id) => {
+                    pv_set.write().notes.insert(plant_id);
+                }
+                shared::CacheType::Species(uuid) => {
+                    pv_set.write().species.insert(uuid);
+                }
+                shared::CacheType::Collection(uuid) => {
+                    pv_set.write().collections.insert(uuid);
+                }
             }
         }
     });
 
     view! {
         <PlantStorageComponent>
-            <EventStorageComponent>{children()}</EventStorageComponent>
+            <EventStorageComponent>
+                <LocationStorageComponent>
+                    <SpeciesStorageComponent>
+                        <CollectionStorageComponent>{children()}</CollectionStorageComponent>
+                    </SpeciesStorageComponent>
+                </LocationStorageComponent>
+            </EventStorageComponent>
         </PlantStorageComponent>
     }
 }
@@ -81,6 +126,16 @@ pub struct DirtyManager {
     /// The key to the hashmap is the plant id. The list are all the event types that are dirty and need requesting
     pub events: HashMap<Uuid, (HashSet<Uuid>, NaiveDateTime)>,
     pub event_types: HashSet<Uuid>,
+    /// Locations that have been created or changed
+    pub locations: HashSet<Uuid>,
+    /// User defined plant fields that have been created or changed
+    pub extra_data_fields: HashSet<Uuid>,
+    /// Plants that have had notes written or edited
+    pub notes: HashSet<Uuid>,
+    /// Species that have been added to the catalog
+    pub species: HashSet<Uuid>,
+    /// Collections that have been created or deleted
+    pub collections: HashSet<Uuid>,
 }
 
 impl DirtyManager {
@@ -0,0 +1,103 @@
+//! Stores a local copy of the species catalog
+
+use leptos::{
+    prelude::{Write, WriteSignal},
+    reactive::spawn_local,
+};
+
+use serde::{Deserialize, Serialize};
+use shared::species::{species_http::NewSpecies, Species};
+
+use crate::{
+    data_storage::DirtyManagerContext,
+    server_helpers::{get_request, post_request},
+};
+
+use leptos::prelude::*;
+
+#[component]
+pub fn SpeciesStorageComponent(children: Children) -> impl IntoView {
+    let (state, set_state) = signal(SpeciesList::default());
+
+    provide_context(SpeciesStorageContext {
+        get_species: state,
+        write_species: set_state,
+    });
+
+    let dirty_manager = expect_context::<DirtyManagerContext>();
+
+    Effect::new(move |_| {
+        // Rerequest every time a species is added
+        dirty_manager.get.with(|dirty_manager| dirty_manager.species.len());
+        spawn_local(get_species(set_state))
+    });
+
+    view! { {children()} }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SpeciesStorageContext {
+    pub get_species: ReadSignal<SpeciesList>,
+    pub write_species: WriteSignal<SpeciesList>,
+}
+
+/// Local in memory store of every species in the catalog
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct SpeciesList(pub Vec<Species>);
+
+impl SpeciesList {
+    pub fn find(&self, species_id: &uuid::Uuid) -> Option<&Species> {
+        self.0.iter().find(|species| &species.id == species_id)
+    }
+}
+
+async fn get_species(species_write: WriteSignal<SpeciesList>) {
+    let request = get_request("/species/get-all");
+
+    let Some(response) = request.send().await.map_err(|e| log::error!("{e}")).ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    let Ok(response) = serde_json::de::from_str::<Vec<Species>>(&body_text) else {
+        //TODO: Background Error message logging
+        return;
+    };
+
+    species_write.write().0 = response;
+}
+
+pub fn new_species_action() -> Action<NewSpecies, Result<Species, String>> {
+    Action::new_local(|input: &NewSpecies| new_species(input.clone()))
+}
+
+async fn new_species(new_species: NewSpecies) -> Result<Species, String> {
+    let request = post_request("/species/new");
+
+    let Some(request_with_json) = request
+        .json(&new_species)
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to build request".to_string());
+    };
+
+    let Some(response) = request_with_json
+        .send()
+        .await
+        .map_err(|e| log::error!("{e}"))
+        .ok()
+    else {
+        return Err("Failed to send request".to_string());
+    };
+    let Some(body_text) = response.text().await.ok() else {
+        return Err("Failed to read response".to_string());
+    };
+
+    // The server explains why a species was rejected in the body
+    serde_json::de::from_str::<Species>(&body_text).map_err(|_| body_text)
+}
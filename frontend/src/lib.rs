@@ -18,7 +18,19 @@ mod theme;
 use crate::{
     components::{footer::Footer, navbar::Navbar},
     data_storage::AppStorageComponent,
-    pages::{home::Home, new_plant::NewPlantPage, plant_page::PlantPage},
+    pages::{
+        archive::Archive,
+        calendar::CalendarPage,
+        collections::{CollectionPage, CollectionsPage},
+        csv_import::CsvImportPage,
+        dashboard::Dashboard,
+        gallery::Gallery,
+        home::Home,
+        new_plant::NewPlantPage,
+        plant_page::PlantPage,
+        rooms::Rooms,
+        species::SpeciesPage,
+    },
 };
 
 /// An app router which renders the homepage and handles 404's
@@ -47,7 +59,15 @@ pub fn App() -> impl IntoView {
                         <Router>
                             <Routes fallback=|| view! { NotFound }>
                                 <Route path=path!("/") view=Home />
-                                //<Route path=path!("/gallery") view=Gallery />
+                                <Route path=path!("/gallery") view=Gallery />
+                                <Route path=path!("/rooms") view=Rooms />
+                                <Route path=path!("/species") view=SpeciesPage />
+                                <Route path=path!("/archive") view=Archive />
+                                <Route path=path!("/collections") view=CollectionsPage />
+                                <Route path=path!("/collection/:id") view=CollectionPage />
+                                <Route path=path!("/dashboard") view=Dashboard />
+                                <Route path=path!("/calendar") view=CalendarPage />
+                                <Route path=path!("/import") view=CsvImportPage />
                                 //<Route path=path!("/plant/new") view=NewPlantPage />
                                 <Route path=path!("/plant/:id/view") view=PlantPage />
                                 <Route path=path!("/plant/:id/timeline") view=NewPlantPage />
@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+
+use crate::components::gallery::GalleryComponent;
+/// Plants that have been gifted, sold or have died
+#[component]
+pub fn Archive() -> impl IntoView {
+    view! {
+        <div class="bg-background flex flex-col justify-center">
+            <h2 class="text-secondary p-4 text-xl font-bold self-center">"Archive"</h2>
+            <GalleryComponent archived=true />
+        </div>
+    }
+}
@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+
+use crate::components::calendar::CalendarComponent;
+/// Calendar of the care that has been logged and the care that is coming up
+#[component]
+pub fn CalendarPage() -> impl IntoView {
+    view! {
+        <div class="bg-background flex flex-col justify-center w-full">
+            <h2 class="text-secondary p-4 text-xl font-bold self-center">"Calendar"</h2>
+            <CalendarComponent />
+        </div>
+    }
+}
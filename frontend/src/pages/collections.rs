@@ -0,0 +1,44 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+use uuid::Uuid;
+
+use crate::{
+    components::{collections::CollectionsComponent, gallery::GalleryComponent},
+    data_storage::collections::CollectionStorageContext,
+};
+/// Every saved collection and a form to create new ones
+#[component]
+pub fn CollectionsPage() -> impl IntoView {
+    view! { <CollectionsComponent /> }
+}
+
+/// The plants that currently match a saved collection
+#[component]
+pub fn CollectionPage() -> impl IntoView {
+    let collection_context: CollectionStorageContext =
+        expect_context::<CollectionStorageContext>();
+
+    let params = use_params_map();
+    let collection_id =
+        Signal::derive(move || Uuid::parse_str(&params.read().get("id").unwrap_or_default()).ok());
+
+    let name = move || {
+        collection_id
+            .get()
+            .and_then(|collection_id| {
+                collection_context
+                    .get_collections
+                    .get()
+                    .find(&collection_id)
+                    .map(|collection| collection.name.clone())
+            })
+            .unwrap_or("Collection".to_string())
+    };
+
+    view! {
+        <div class="bg-background flex flex-col justify-center">
+            <h2 class="text-secondary p-4 text-xl font-bold self-center">{name}</h2>
+            <GalleryComponent archived=false collection_id=collection_id />
+        </div>
+    }
+}
@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+
+use crate::components::csv_import::CsvImportComponent;
+/// Import of plants and their history from spreadsheets and other plant apps
+#[component]
+pub fn CsvImportPage() -> impl IntoView {
+    view! {
+        <div class="bg-background flex flex-col justify-center w-full">
+            <h2 class="text-secondary p-4 text-xl font-bold self-center">"Import"</h2>
+            <CsvImportComponent />
+        </div>
+    }
+}
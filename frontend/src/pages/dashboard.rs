@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+
+use crate::components::dashboard::DashboardComponent;
+/// Care statistics of the household with charts
+#[component]
+pub fn Dashboard() -> impl IntoView {
+    view! {
+        <div class="bg-background flex flex-col justify-center w-full">
+            <h2 class="text-secondary p-4 text-xl font-bold self-center">"Dashboard"</h2>
+            <DashboardComponent />
+        </div>
+    }
+}
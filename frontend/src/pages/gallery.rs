@@ -1,8 +1,8 @@
 use leptos::prelude::*;
 
-use crate::components::gallery::GalleryComponent;
-/// Default Home Page
+use crate::components::photo_gallery::PhotoGalleryComponent;
+/// Gallery of every photo across all plants
 #[component]
 pub fn Gallery() -> impl IntoView {
-    view! { <GalleryComponent /> }
+    view! { <PhotoGalleryComponent /> }
 }
@@ -1,6 +1,13 @@
 pub mod home;
 pub mod not_found;
 pub mod gallery;
+pub mod rooms;
+pub mod species;
+pub mod archive;
+pub mod calendar;
+pub mod collections;
+pub mod csv_import;
+pub mod dashboard;
 pub mod settings;
 pub mod plant_page;
 pub mod new_plant;
@@ -2,9 +2,9 @@
 //!
 //! Includes Timeline and Edit views
 
-use std::{collections::VecDeque, io::Cursor};
+use std::collections::VecDeque;
 
-use chrono::{Local, Utc};
+use chrono::{DateTime, Local, Utc};
 use leptos::{prelude::*, reactive::spawn_local};
 use leptos_router::hooks::use_params_map;
 use shared::{
@@ -22,8 +22,11 @@ use web_sys::js_sys::Uint8Array;
 
 use crate::{
     components::plant_components::{
-        event_display::EventDisplayComponent, photo::PhotoDisplayComponent,
-        photo_placeholder::PhotoPlaceholderDisplayComponent,
+        event_display::EventDisplayComponent, extra_data::ExtraDataComponent,
+        journal::JournalComponent, lifecycle::LifecycleComponent, lineage::LineageComponent,
+        photo::PhotoDisplayComponent,
+        species::SpeciesComponent, tags::TagsComponent, timeline::TimelineComponent,
+        photo_placeholder::PhotoPlaceholderDisplayComponent, timelapse::TimelapseComponent,
     },
     data_storage::events::{
         event_storage::request_events_resource, new_event_action, EventListContext,
@@ -77,22 +80,10 @@ pub fn PlantPage() -> impl IntoView {
     let num_events = RwSignal::new(3);
 
     let name_input_ref = NodeRef::new();
-    let uploaded_image = RwSignal::new(None::<Vec<u8>>);
 
     let new_photo_action = new_photo_action();
     let custom_request = move |file_list: FileList| {
-        let async_closure = async move || {
-            submit_new_photos(file_list, uploaded_image).await;
-            let Some(uploaded_image) = uploaded_image.get_untracked() else {
-                return;
-            };
-            new_photo_action.dispatch(NewPhoto {
-                plant_id,
-                timestamp: Local::now().naive_local().and_utc().timestamp(),
-                photo_binary: uploaded_image,
-            });
-        };
-        spawn_local(async_closure());
+        spawn_local(submit_new_photos(file_list, plant_id, new_photo_action));
     };
 
     view! {
@@ -155,6 +146,8 @@ pub fn PlantPage() -> impl IntoView {
                     })}
                 </Suspense>
 
+                <TimelapseComponent plant_id=plant_id />
+
                 <div class="flex flex-row items-center justify-center">
                     {
                         view! {
@@ -206,6 +199,16 @@ pub fn PlantPage() -> impl IntoView {
 
                 </div>
 
+                <LifecycleComponent plant_id=plant_id />
+
+                <TagsComponent plant_id=plant_id />
+
+                <SpeciesComponent plant_id=plant_id />
+
+                <ExtraDataComponent plant_id=plant_id />
+
+                <LineageComponent plant_id=plant_id />
+
                 <div class="w-full flex flex-col justify-start">
                     <For
                         each=move || {
@@ -229,12 +232,20 @@ pub fn PlantPage() -> impl IntoView {
                         }
                     />
                 </div>
+
+                <TimelineComponent plant_id=plant_id />
+
+                <JournalComponent plant_id=plant_id />
             </div>
         </div>
     }
 }
 
-async fn submit_new_photos(file_list: FileList, uploaded_image: RwSignal<Option<Vec<u8>>>) {
+async fn submit_new_photos(
+    file_list: FileList,
+    plant_id: Uuid,
+    new_photo_action: Action<NewPhoto, ()>,
+) {
     for file_index in 0..file_list.length() {
         let Some(file) = file_list.get(file_index) else {
             continue;
@@ -243,16 +254,20 @@ async fn submit_new_photos(file_list: FileList, uploaded_image: RwSignal<Option<
         let Ok(file_binary) = JsFuture::from(file.array_buffer()).await else {
             continue;
         };
-        let array = Uint8Array::new(&file_binary);
-        let Ok(image) = image::load_from_memory(&array.to_vec()) else {
+        let photo_binary = Uint8Array::new(&file_binary).to_vec();
+
+        // The original file is uploaded untouched so the server can read the EXIF capture date out of it
+        if image::guess_format(&photo_binary).is_err() {
             continue;
-        };
-        let mut buf: Vec<u8> = Vec::new();
-        image
-            .write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)
-            .unwrap();
+        }
 
-        uploaded_image.set(Some(buf));
+        new_photo_action.dispatch(NewPhoto {
+            plant_id,
+            timestamp: Local::now().naive_local().and_utc().timestamp(),
+            photo_binary,
+            photo_date: DateTime::from_timestamp_millis(file.last_modified() as i64)
+                .map(|date| date.naive_utc()),
+        });
     }
 }
 
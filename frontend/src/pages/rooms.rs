@@ -0,0 +1,8 @@
+use leptos::prelude::*;
+
+use crate::components::rooms::RoomsComponent;
+/// Plants grouped by the room they are in
+#[component]
+pub fn Rooms() -> impl IntoView {
+    view! { <RoomsComponent /> }
+}
@@ -0,0 +1,8 @@
+use leptos::prelude::*;
+
+use crate::components::species_catalog::SpeciesCatalogComponent;
+/// The species catalog and the care profile of every species
+#[component]
+pub fn SpeciesPage() -> impl IntoView {
+    view! { <SpeciesCatalogComponent /> }
+}
@@ -0,0 +1,169 @@
+//! Versioned backup archive of everything needed to rebuild the plants of an instance, along with the report of restoring one
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    collections::Collection,
+    events::{EventInstance, EventType},
+    locations::Location,
+    notes::Note,
+    plant::{ExtraDataField, ExtraDataType},
+    species::Species,
+};
+
+/// Version of the archive layout written by this build. Archives with a newer version are refused on import.
+///
+/// Version 2 added species, locations, extra data, notes, tags and collections. Version 1 archives restore without them
+pub static ARCHIVE_VERSION: u32 = 2;
+
+/// Name of the JSON file inside the archive that holds everything except the image files
+pub static ARCHIVE_DATA_FILE: &str = "archive.json";
+
+/// Folder inside the archive that holds the image files, named by the photo they belong to
+pub static ARCHIVE_PHOTO_FOLDER: &str = "photos";
+
+/// The largest archive that can be uploaded to be restored, in bytes
+pub static MAX_ARCHIVE_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Header set on an exported archive that is larger than [`MAX_ARCHIVE_SIZE`], so it can't be uploaded to be restored
+pub static ARCHIVE_TOO_LARGE_HEADER: &str = "x-archive-too-large";
+
+/// The most the data file of an archive can decompress to, in bytes
+pub static MAX_ARCHIVE_DATA_SIZE: u64 = 512 * 1024 * 1024;
+
+/// The most a single photo of an archive can decompress to, in bytes
+pub static MAX_ARCHIVE_PHOTO_SIZE: u64 = 64 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Archive {
+    pub version: u32,
+    /// When the archive was made in UTC
+    pub exported: NaiveDateTime,
+    pub plants: Vec<ArchivePlant>,
+    pub event_types: Vec<EventType>,
+    pub events: Vec<EventInstance>,
+    /// Events of event types that only keep their latest instance per plant
+    pub unique_events: Vec<EventInstance>,
+    pub photos: Vec<ArchivePhoto>,
+    /// Species added by the user. The bundled catalog is seeded on every instance so it isn't archived
+    #[serde(default)]
+    pub species: Vec<ArchiveSpecies>,
+    #[serde(default)]
+    pub locations: Vec<ArchiveLocation>,
+    #[serde(default)]
+    pub extra_data_fields: Vec<ArchiveExtraDataField>,
+    /// Every value ever set for an extra data field, so the history of each field is kept
+    #[serde(default)]
+    pub extra_data: Vec<ArchiveExtraData>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub note_revisions: Vec<ArchiveNoteRevision>,
+    #[serde(default)]
+    pub tags: Vec<ArchiveTag>,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchivePlant {
+    pub id: Uuid,
+    pub date_created: NaiveDateTime,
+    pub species_id: Option<Uuid>,
+    /// The plant this one was propagated from
+    pub parent_id: Option<Uuid>,
+    pub cover_photo_id: Option<Uuid>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchivePhoto {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub photo_date: NaiveDateTime,
+    /// Name of the image file inside the photo folder of the archive
+    pub file_name: String,
+    pub content_hash: Option<String>,
+    pub perceptual_hash: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveSpecies {
+    pub species: Species,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveLocation {
+    pub location: Location,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveExtraDataField {
+    pub field: ExtraDataField,
+    pub date_created: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveExtraData {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    pub field_id: Uuid,
+    pub data: ExtraDataType,
+    pub date_changed: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveNoteRevision {
+    pub id: Uuid,
+    pub note_id: Uuid,
+    pub body: String,
+    pub date_replaced: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ArchiveTag {
+    pub plant_id: Uuid,
+    pub tag: String,
+}
+
+impl ArchivePhoto {
+    /// Path of the image file inside the archive
+    pub fn archive_path(&self) -> String {
+        format!("{}/{}", ARCHIVE_PHOTO_FOLDER, self.file_name)
+    }
+}
+
+/// What happened to each part of an archive when it was restored.
+///
+/// Anything that already existed is left as it is, so restoring the same archive twice adds nothing the second time
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct ImportReport {
+    pub event_types_added: usize,
+    /// Event types that already existed, either with the same id or with the same name and kind
+    pub event_types_merged: usize,
+    pub plants_added: usize,
+    pub plants_existing: usize,
+    pub events_added: usize,
+    pub events_existing: usize,
+    pub photos_added: usize,
+    pub photos_existing: usize,
+    pub species_added: usize,
+    pub locations_added: usize,
+    pub extra_data_added: usize,
+    pub notes_added: usize,
+    pub tags_added: usize,
+    pub collections_added: usize,
+    pub rejected_events: Vec<RejectedEvent>,
+    /// Everything else from the archive that couldn't be restored, and why
+    pub problems: Vec<String>,
+}
+
+/// An event from the archive that doesn't fit its event type and was left out
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RejectedEvent {
+    pub event_id: Uuid,
+    pub plant_id: Uuid,
+    pub reason: String,
+}
@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{calendar::CareSchedule, events::events_http::ActivityItem};
+
+/// HTTP request sent to the server for the events logged between two dates and the care that is scheduled
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetCalendar {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+/// HTTP response sent from the server in response to [`GetCalendar`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetCalendarResponse {
+    /// Events logged between the start and end, oldest first
+    pub events: Vec<ActivityItem>,
+    /// Every schedule, use [`CareSchedule::occurrences`] to place them in the calendar
+    pub schedules: Vec<CareSchedule>,
+}
@@ -0,0 +1,64 @@
+//! Care that is scheduled to happen along with what has already happened, shown as a calendar
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::WATERED_EVENT_ID;
+
+pub mod calendar_http;
+
+/// Care for a plant that repeats on a fixed interval. Plants are scheduled to be watered as often as their species recommends
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CareSchedule {
+    pub plant_id: Uuid,
+    pub plant_name: Option<String>,
+    /// The event type that is due
+    pub event_type_id: Uuid,
+    pub event_type_name: String,
+    /// Days between each time the care is due
+    pub interval_days: i64,
+    /// The next time the care is due in UTC. In the past if the care is overdue
+    pub next_due: NaiveDateTime,
+}
+
+impl CareSchedule {
+    /// Every time the care is due between start and end, counting on from the next due date.
+    /// Occurrences before start are skipped, so overdue care needs [`CareSchedule::is_overdue`] to stay visible
+    pub fn occurrences(&self, start: NaiveDateTime, end: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let interval = Duration::days(self.interval_days.max(1));
+        let mut due = self.next_due;
+        // Skip ahead to the first occurrence inside of the span without stepping through every one before it
+        if due < start {
+            let intervals_behind =
+                start.signed_duration_since(due).num_seconds() / interval.num_seconds();
+            due += interval * intervals_behind as i32;
+            if due < start {
+                due += interval;
+            }
+        }
+
+        let mut occurrences = vec![];
+        while due <= end {
+            occurrences.push(due);
+            due += interval;
+        }
+        occurrences
+    }
+
+    /// True if the care was due before the given time and hasn't been done since
+    pub fn is_overdue(&self, now: NaiveDateTime) -> bool {
+        self.next_due < now
+    }
+
+    /// Describes the care in a short sentence, eg `Water Monstera`
+    pub fn summary(&self) -> String {
+        let plant_name = self
+            .plant_name
+            .clone()
+            .unwrap_or("an unnamed plant".to_string());
+        match self.event_type_id.to_string() == WATERED_EVENT_ID {
+            true => format!("Water {}", plant_name),
+            false => format!("{} due for {}", self.event_type_name, plant_name),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// HTTP request sent to the server to save a new smart collection
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewCollection {
+    pub name: String,
+    /// The filter expression, see [`super::CollectionFilter`]
+    pub filter: String,
+}
@@ -0,0 +1,333 @@
+//! Smart collections, saved searches over plants defined by a filter expression such as `tag:office AND state:alive AND overdue:watered`
+use std::fmt::Display;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::plant::{PlantState, normalize_tag};
+
+pub mod collections_http;
+
+/// The longest filter expression a collection can be saved with
+pub static MAX_FILTER_LENGTH: usize = 1000;
+
+/// How deeply parentheses and `NOT` can be nested in a filter expression
+pub static MAX_FILTER_DEPTH: usize = 32;
+
+/// A saved filter that is shown as its own view of the plants
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Collection {
+    pub id: Uuid,
+    pub name: String,
+    /// The filter expression as the user wrote it
+    pub filter: String,
+    pub date_created: NaiveDateTime,
+}
+
+/// A parsed collection filter expression.
+///
+/// Terms are `tag:<tag>`, `state:<state or retired>`, `overdue:watered` and `has:photo`. Terms can be combined with `AND`, `OR`, `NOT` and parentheses,
+/// terms next to each other must both match. Values with spaces can be quoted, eg `tag:"propagation station"`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum CollectionFilter {
+    /// Plants with this tag
+    Tag(String),
+    /// Plants currently in the state with this name
+    State(String),
+    /// Plants that are gifted, dead or sold
+    Retired,
+    /// Plants whose species says they should have been watered by now
+    OverdueWatering,
+    /// Plants with at least one photo
+    HasPhoto,
+    Not(Box<CollectionFilter>),
+    And(Box<CollectionFilter>, Box<CollectionFilter>),
+    Or(Box<CollectionFilter>, Box<CollectionFilter>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(String, String),
+}
+
+impl CollectionFilter {
+    /// Parses a filter expression. Returns a description of the problem if it isn't valid
+    pub fn parse(expression: &str) -> Result<CollectionFilter, String> {
+        let tokens = tokenize(expression)?;
+        let mut position = 0;
+        let filter = parse_or(&tokens, &mut position, 0)?;
+        match tokens.get(position) {
+            None => Ok(filter),
+            Some(Token::Close) => Err("Unmatched closing parenthesis".to_string()),
+            Some(_) => Err("Unexpected text after the end of the filter".to_string()),
+        }
+    }
+}
+
+impl Display for CollectionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectionFilter::Tag(tag) => match tag.contains(' ') {
+                true => write!(f, "tag:\"{}\"", tag),
+                false => write!(f, "tag:{}", tag),
+            },
+            CollectionFilter::State(state) => write!(f, "state:{}", state.to_lowercase()),
+            CollectionFilter::Retired => write!(f, "state:retired"),
+            CollectionFilter::OverdueWatering => write!(f, "overdue:watered"),
+            CollectionFilter::HasPhoto => write!(f, "has:photo"),
+            CollectionFilter::Not(filter) => write!(f, "NOT {}", filter),
+            CollectionFilter::And(left, right) => write!(f, "({} AND {})", left, right),
+            CollectionFilter::Or(left, right) => write!(f, "({} OR {})", left, right),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.peek().copied() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => {
+                let mut word = String::new();
+                let mut quoted = false;
+                while let Some(c) = chars.peek().copied() {
+                    if !quoted && (c.is_whitespace() || c == '(' || c == ')') {
+                        break;
+                    }
+                    chars.next();
+                    match c {
+                        '"' => quoted = !quoted,
+                        _ => word.push(c),
+                    }
+                }
+                if quoted {
+                    return Err(format!("Unclosed quote in {}", word));
+                }
+                tokens.push(match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match word.split_once(':') {
+                        Some((key, value)) => Token::Term(key.to_lowercase(), value.to_string()),
+                        None => {
+                            return Err(format!(
+                                "{} is not a term, terms are written as key:value",
+                                word
+                            ));
+                        }
+                    },
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_or(
+    tokens: &[Token],
+    position: &mut usize,
+    depth: usize,
+) -> Result<CollectionFilter, String> {
+    let mut filter = parse_and(tokens, position, depth)?;
+    while tokens.get(*position) == Some(&Token::Or) {
+        *position += 1;
+        filter = CollectionFilter::Or(
+            Box::new(filter),
+            Box::new(parse_and(tokens, position, depth)?),
+        );
+    }
+    Ok(filter)
+}
+
+fn parse_and(
+    tokens: &[Token],
+    position: &mut usize,
+    depth: usize,
+) -> Result<CollectionFilter, String> {
+    let mut filter = parse_not(tokens, position, depth)?;
+    loop {
+        match tokens.get(*position) {
+            Some(Token::And) => *position += 1,
+            // Terms next to each other are joined with an implied AND
+            Some(Token::Open | Token::Not | Token::Term(_, _)) => {}
+            _ => return Ok(filter),
+        }
+        filter = CollectionFilter::And(
+            Box::new(filter),
+            Box::new(parse_not(tokens, position, depth)?),
+        );
+    }
+}
+
+/// Parses a single term, a negated filter or a filter in parentheses. The depth counts the parentheses and `NOT`s around
+/// the filter so deeply nested expressions are rejected instead of overflowing the stack
+fn parse_not(
+    tokens: &[Token],
+    position: &mut usize,
+    depth: usize,
+) -> Result<CollectionFilter, String> {
+    if depth > MAX_FILTER_DEPTH {
+        return Err("Filter is nested too deeply".to_string());
+    }
+    let token = tokens.get(*position).cloned();
+    *position += 1;
+    match token {
+        Some(Token::Not) => Ok(CollectionFilter::Not(Box::new(parse_not(
+            tokens,
+            position,
+            depth + 1,
+        )?))),
+        Some(Token::Open) => {
+            let filter = parse_or(tokens, position, depth + 1)?;
+            if tokens.get(*position) != Some(&Token::Close) {
+                return Err("Missing closing parenthesis".to_string());
+            }
+            *position += 1;
+            Ok(filter)
+        }
+        Some(Token::Term(key, value)) => parse_term(&key, &value),
+        Some(Token::Close) => Err("Unexpected closing parenthesis".to_string()),
+        Some(Token::And | Token::Or) => Err("AND and OR need a term on both sides".to_string()),
+        None => Err("The filter ends before a term".to_string()),
+    }
+}
+
+fn parse_term(key: &str, value: &str) -> Result<CollectionFilter, String> {
+    match key {
+        "tag" => normalize_tag(value)
+            .map(CollectionFilter::Tag)
+            .ok_or("Tags must not be empty".to_string()),
+        "state" if value.eq_ignore_ascii_case("retired") => Ok(CollectionFilter::Retired),
+        "state" => PlantState::NAMES
+            .into_iter()
+            .find(|name| name.eq_ignore_ascii_case(value))
+            .map(|name| CollectionFilter::State(name.to_string()))
+            .ok_or(format!(
+                "{} is not a state, states are {} or retired",
+                value,
+                PlantState::NAMES.join(", ").to_lowercase()
+            )),
+        "overdue" if value.eq_ignore_ascii_case("watered") => Ok(CollectionFilter::OverdueWatering),
+        "overdue" => Err("Only overdue:watered is supported".to_string()),
+        "has" if value.eq_ignore_ascii_case("photo") => Ok(CollectionFilter::HasPhoto),
+        "has" => Err("Only has:photo is supported".to_string()),
+        _ => Err(format!(
+            "{} is not a filter, filters are tag, state, overdue and has",
+            key
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(tag: &str) -> Box<CollectionFilter> {
+        Box::new(CollectionFilter::Tag(tag.to_string()))
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            CollectionFilter::parse("tag:a OR tag:b AND tag:c"),
+            Ok(CollectionFilter::Or(
+                tag("a"),
+                Box::new(CollectionFilter::And(tag("b"), tag("c")))
+            ))
+        );
+        assert_eq!(
+            CollectionFilter::parse("(tag:a OR tag:b) AND tag:c"),
+            Ok(CollectionFilter::And(
+                Box::new(CollectionFilter::Or(tag("a"), tag("b"))),
+                tag("c")
+            ))
+        );
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and() {
+        assert_eq!(
+            CollectionFilter::parse("NOT tag:a AND has:photo"),
+            Ok(CollectionFilter::And(
+                Box::new(CollectionFilter::Not(tag("a"))),
+                Box::new(CollectionFilter::HasPhoto)
+            ))
+        );
+    }
+
+    #[test]
+    fn terms_next_to_each_other_are_joined_with_and() {
+        assert_eq!(
+            CollectionFilter::parse("tag:a state:retired"),
+            CollectionFilter::parse("tag:a AND state:retired")
+        );
+        assert_eq!(
+            CollectionFilter::parse("tag:a NOT (tag:b)"),
+            Ok(CollectionFilter::And(
+                tag("a"),
+                Box::new(CollectionFilter::Not(tag("b")))
+            ))
+        );
+    }
+
+    #[test]
+    fn quoted_values_keep_their_spaces() {
+        let filter = CollectionFilter::parse("tag:\"propagation station\" OR has:photo").unwrap();
+        assert_eq!(
+            filter,
+            CollectionFilter::Or(
+                Box::new(CollectionFilter::Tag(
+                    normalize_tag("propagation station").unwrap()
+                )),
+                Box::new(CollectionFilter::HasPhoto)
+            )
+        );
+        assert_eq!(CollectionFilter::parse(&filter.to_string()), Ok(filter));
+        assert!(CollectionFilter::parse("tag:\"unclosed").is_err());
+    }
+
+    #[test]
+    fn invalid_filters_are_rejected() {
+        assert!(CollectionFilter::parse("").is_err());
+        assert!(CollectionFilter::parse("tag:a AND").is_err());
+        assert!(CollectionFilter::parse("(tag:a").is_err());
+        assert!(CollectionFilter::parse("tag:a)").is_err());
+        assert!(CollectionFilter::parse("plant").is_err());
+        assert!(CollectionFilter::parse("colour:green").is_err());
+    }
+
+    #[test]
+    fn nesting_is_limited() {
+        let nested = |depth: usize| format!("{}tag:a{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(CollectionFilter::parse(&nested(MAX_FILTER_DEPTH)).is_ok());
+        assert_eq!(
+            CollectionFilter::parse(&nested(MAX_FILTER_DEPTH + 1)),
+            Err("Filter is nested too deeply".to_string())
+        );
+        assert_eq!(
+            CollectionFilter::parse(&"NOT ".repeat(100_000)),
+            Err("Filter is nested too deeply".to_string())
+        );
+        assert_eq!(
+            CollectionFilter::parse(&"(".repeat(100_000)),
+            Err("Filter is nested too deeply".to_string())
+        );
+    }
+}
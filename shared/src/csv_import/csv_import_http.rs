@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{csv_import::CsvColumns, plant::plant_http::PlantCandidate};
+
+/// The most rows a single CSV import can have
+pub static MAX_CSV_ROWS: usize = 5000;
+
+/// HTTP request sent to the server to preview or commit a CSV import. Both take the same request so a preview can be committed as is
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvImport {
+    pub csv: String,
+    pub columns: CsvColumns,
+    /// A chrono layout for the date column, eg "%d/%m/%Y". When None the common layouts are tried
+    pub date_format: Option<String>,
+    /// Plant names from the CSV that should log to an existing plant instead of whatever they would match on their own
+    #[serde(default)]
+    pub plant_overrides: HashMap<String, Uuid>,
+}
+
+/// A row of the CSV that can't be imported
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvRowProblem {
+    /// The line of the file the row starts on, counting the first line of the header as line 1
+    pub row: usize,
+    pub problem: String,
+}
+
+/// A plant name from the CSV that isn't an exact match for a single plant but is close to some.
+/// Without an override a new plant is created for it, or it is skipped if it matches more than one plant exactly
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ProbableDuplicate {
+    pub name: String,
+    pub candidates: Vec<PlantCandidate>,
+}
+
+/// What a CSV import would do, without changing anything
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvPreview {
+    pub rows: usize,
+    /// Rows that passed every check that doesn't depend on the events before them
+    pub events_ready: usize,
+    /// Plant names that match an existing plant
+    pub matched_plants: Vec<String>,
+    /// Plant names that will be created as new plants
+    pub new_plants: Vec<String>,
+    pub probable_duplicates: Vec<ProbableDuplicate>,
+    /// Event type names from the CSV that no event type has
+    pub unknown_event_types: Vec<String>,
+    pub problems: Vec<CsvRowProblem>,
+}
+
+/// What a CSV import did
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvImportResult {
+    pub plants_created: Vec<Uuid>,
+    pub events_logged: usize,
+    pub problems: Vec<CsvRowProblem>,
+}
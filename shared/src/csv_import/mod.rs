@@ -0,0 +1,257 @@
+//! Importing plants and their history from CSV files made by spreadsheets and other plant apps
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+pub mod csv_import_http;
+
+/// Date and time layouts tried in order when the import doesn't name one
+pub static DATE_TIME_FORMATS: [&str; 6] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+    "%Y/%m/%d %H:%M",
+    "%m/%d/%Y %H:%M",
+];
+
+/// Date layouts tried in order when the import doesn't name one, after every date and time layout
+pub static DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%Y", "%d.%m.%Y"];
+
+/// Which column of the CSV holds each part of an event, by the name in the header row
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvColumns {
+    pub plant_name: String,
+    pub event_type: String,
+    pub date: String,
+    /// The column with the data of the event. Not needed when every event type is a plain date
+    pub value: Option<String>,
+}
+
+/// The header and rows of a CSV file
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CsvTable {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// The line of the file each row starts on, counting the first line of the header as line 1
+    pub row_lines: Vec<usize>,
+}
+
+impl CsvTable {
+    /// Reads CSV text where the first row is the header. Quoted fields may hold commas, quotes written twice and line breaks.
+    /// Empty lines are skipped, the line each row starts on is kept so problems can point at it
+    pub fn parse(text: &str) -> Result<CsvTable, String> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+        let mut rows: Vec<(usize, Vec<String>)> = vec![];
+        let mut row: Vec<String> = vec![];
+        let mut field = String::new();
+        let mut quoted = false;
+        let mut line = 1;
+        let mut row_line = 1;
+        let mut characters = text.chars().peekable();
+        while let Some(character) = characters.next() {
+            match (quoted, character) {
+                (true, '"') if characters.peek() == Some(&'"') => {
+                    characters.next();
+                    field.push('"');
+                }
+                (true, '"') => quoted = false,
+                (true, '\n') => {
+                    field.push('\n');
+                    line += 1;
+                }
+                (true, character) => field.push(character),
+                (false, '"') if field.is_empty() => quoted = true,
+                (false, ',') => row.push(std::mem::take(&mut field)),
+                (false, '\r') if characters.peek() == Some(&'\n') => {}
+                (false, '\n') => {
+                    row.push(std::mem::take(&mut field));
+                    rows.push((row_line, std::mem::take(&mut row)));
+                    line += 1;
+                    row_line = line;
+                }
+                (false, character) => field.push(character),
+            }
+        }
+        if quoted {
+            return Err("CSV ends inside of a quoted field".to_string());
+        }
+        if !field.is_empty() || !row.is_empty() {
+            row.push(field);
+            rows.push((row_line, row));
+        }
+        rows.retain(|(_, row)| row.iter().any(|field| !field.trim().is_empty()));
+
+        let mut rows = rows.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Err("CSV is empty".to_string());
+        };
+        let (row_lines, rows) = rows.unzip();
+        Ok(CsvTable {
+            header: header.iter().map(|name| name.trim().to_string()).collect(),
+            rows,
+            row_lines,
+        })
+    }
+
+    /// The position of a column by its name in the header, ignoring case
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// The line of the file a row starts on, counting the first line of the header as line 1
+    pub fn row_number(&self, index: usize) -> usize {
+        self.row_lines[index]
+    }
+}
+
+/// Writes a row of CSV ending in a line break. Fields holding commas, quotes or line breaks are quoted
+pub fn write_csv_row(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| match field.contains([',', '"', '\n', '\r']) {
+            true => format!("\"{}\"", field.replace('"', "\"\"")),
+            false => field.clone(),
+        })
+        .collect();
+    fields.join(",") + "\r\n"
+}
+
+/// Reads a date in the given layout, or else in any of the common layouts. Dates without a time are placed at noon
+/// so they stay on the same day in every timezone
+pub fn parse_date(value: &str, format: Option<&str>) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    let noon = NaiveTime::from_hms_opt(12, 0, 0)?;
+    if let Some(format) = format {
+        return NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .map(|date| date.and_time(noon))
+            });
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.naive_utc());
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS.iter().find_map(|format| {
+                NaiveDate::parse_from_str(value, format)
+                    .ok()
+                    .map(|date| date.and_time(noon))
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    fn date(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let table = CsvTable::parse("plant,note\n\"Monstera, big\",\"Said \"\"hi\"\"\"\n").unwrap();
+        assert_eq!(table.header, row(&["plant", "note"]));
+        assert_eq!(table.rows, vec![row(&["Monstera, big", "Said \"hi\""])]);
+        assert_eq!(table.row_lines, vec![2]);
+    }
+
+    #[test]
+    fn embedded_newlines_keep_line_numbers() {
+        let table =
+            CsvTable::parse("plant,note\r\nFern,\"first\r\nsecond\"\r\nIvy,third\r\n").unwrap();
+        assert_eq!(
+            table.rows,
+            vec![row(&["Fern", "first\r\nsecond"]), row(&["Ivy", "third"])]
+        );
+        assert_eq!(table.row_lines, vec![2, 4]);
+        assert_eq!(table.row_number(1), 4);
+    }
+
+    #[test]
+    fn blank_lines_are_skipped_but_counted() {
+        let table = CsvTable::parse("plant,date\n\nFern,2024-01-01\n , \nIvy,2024-01-02").unwrap();
+        assert_eq!(
+            table.rows,
+            vec![row(&["Fern", "2024-01-01"]), row(&["Ivy", "2024-01-02"])]
+        );
+        assert_eq!(table.row_lines, vec![3, 5]);
+    }
+
+    #[test]
+    fn byte_order_mark_and_missing_final_newline() {
+        let table = CsvTable::parse("\u{feff}plant,date\nFern,2024-01-01").unwrap();
+        assert_eq!(table.header, row(&["plant", "date"]));
+        assert_eq!(table.column("Plant"), Some(0));
+        assert_eq!(table.rows, vec![row(&["Fern", "2024-01-01"])]);
+    }
+
+    #[test]
+    fn invalid_csv() {
+        assert!(CsvTable::parse("").is_err());
+        assert!(CsvTable::parse("\n\n").is_err());
+        assert!(CsvTable::parse("plant\n\"Fern").is_err());
+    }
+
+    #[test]
+    fn written_rows_parse_back() {
+        let fields = row(&["Fern, big", "Said \"hi\"", "two\nlines"]);
+        let text = write_csv_row(&row(&["a", "b", "c"])) + &write_csv_row(&fields);
+        assert_eq!(CsvTable::parse(&text).unwrap().rows, vec![fields]);
+    }
+
+    #[test]
+    fn dates_in_common_layouts() {
+        assert_eq!(
+            parse_date("2024-03-05 08:30:00", None),
+            Some(date(2024, 3, 5, 8, 30))
+        );
+        assert_eq!(
+            parse_date("2024-03-05T08:30:00+02:00", None),
+            Some(date(2024, 3, 5, 6, 30))
+        );
+        assert_eq!(
+            parse_date(" 2024-03-05 ", None),
+            Some(date(2024, 3, 5, 12, 0))
+        );
+        assert_eq!(
+            parse_date("03/05/2024", None),
+            Some(date(2024, 3, 5, 12, 0))
+        );
+        assert_eq!(
+            parse_date("05.03.2024", None),
+            Some(date(2024, 3, 5, 12, 0))
+        );
+        assert_eq!(parse_date("yesterday", None), None);
+    }
+
+    #[test]
+    fn dates_in_a_given_layout() {
+        assert_eq!(
+            parse_date("05/03/2024", Some("%d/%m/%Y")),
+            Some(date(2024, 3, 5, 12, 0))
+        );
+        assert_eq!(
+            parse_date("05/03/2024 18:15", Some("%d/%m/%Y %H:%M")),
+            Some(date(2024, 3, 5, 18, 15))
+        );
+        // A given layout replaces the common ones
+        assert_eq!(parse_date("2024-03-05", Some("%d/%m/%Y")), None);
+    }
+}
@@ -2,7 +2,10 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::events::{EventData, EventDataKind, EventInstance};
+use crate::{
+    csv_import::write_csv_row,
+    events::{EventData, EventDataKind, EventInstance, PHOTO_EVENT_TYPE_ID, WATERED_EVENT_ID},
+};
 
 /// HTTP request sent to server to create a new event type
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,6 +24,33 @@ pub struct NewEvent {
     pub event_date: NaiveDateTime,
 }
 
+/// The most plants a single bulk event can be logged for
+pub static MAX_BULK_PLANTS: usize = 500;
+
+/// HTTP request sent to server to log the same event type for many plants at once
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewBulkEvent {
+    pub event_type: Uuid,
+    pub event_date: NaiveDateTime,
+    /// The data logged for every plant that doesn't have data of its own
+    pub event_data: Option<EventData>,
+    pub plants: Vec<BulkEventPlant>,
+}
+
+/// A plant in a bulk event, optionally with data that is only logged for this plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEventPlant {
+    pub plant_id: Uuid,
+    pub event_data: Option<EventData>,
+}
+
+/// HTTP response with the outcome of a bulk event for one plant. The error explains why the event wasn't logged for the plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEventResult {
+    pub plant_id: Uuid,
+    pub result: Result<EventInstance, String>,
+}
+
 /// HTTP request sent to server to request events
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GetEvent {
@@ -47,6 +77,174 @@ pub struct GetEventResponse {
     pub request_details: GetEventType,
 }
 
+/// HTTP request sent to the server to get period spans. Leaving the plant or event type empty includes every plant or event type
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPeriods {
+    pub plant_id: Option<Uuid>,
+    pub event_type: Option<Uuid>,
+    /// Only include periods that haven't ended yet
+    pub open_only: bool,
+}
+
+/// HTTP request sent to the server to summarise a numeric event type of a plant over time
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AggregateEvents {
+    pub event_type: Uuid,
+    pub plant_id: Uuid,
+    /// Only include measurements between these dates. None includes every measurement
+    pub span: Option<(NaiveDateTime, NaiveDateTime)>,
+    pub bucket: AggregateBucket,
+}
+
+/// The length of time measurements are grouped by
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum AggregateBucket {
+    Day,
+    Week,
+    Month,
+}
+
+/// HTTP response with the measurements of one bucket summarised
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AggregatePoint {
+    /// The start of the day, week or month
+    pub bucket_start: NaiveDateTime,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    /// How many measurements were taken in the bucket
+    pub count: i64,
+}
+
+/// HTTP request sent to the server for a page of the latest events across every plant, newest first
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetActivity {
+    /// Return the activity that comes after this cursor. None starts at the newest event
+    pub cursor: Option<ActivityCursor>,
+    pub limit: i64,
+}
+
+/// Position of an event in the newest first ordering of the activity feed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActivityCursor {
+    pub event_date: NaiveDateTime,
+    pub id: Uuid,
+}
+
+/// A single event in the activity feed along with what is needed to describe it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActivityItem {
+    pub event: EventInstance,
+    pub event_type_name: String,
+    /// The current name of the plant the event was logged for
+    pub plant_name: Option<String>,
+    /// Who logged the event. Always None until there are user accounts
+    pub actor: Option<String>,
+}
+
+impl ActivityItem {
+    /// Describes the event in a short sentence, eg `Watered Monstera` or `Alex watered Monstera`
+    pub fn summary(&self) -> String {
+        let plant_name = self
+            .plant_name
+            .clone()
+            .unwrap_or("an unnamed plant".to_string());
+        let event_type_id = self.event.event_type_id.to_string();
+        let action = if event_type_id == WATERED_EVENT_ID {
+            format!("watered {}", plant_name)
+        } else if event_type_id == PHOTO_EVENT_TYPE_ID {
+            format!("added a new photo of {}", plant_name)
+        } else {
+            format!(
+                "logged {} for {}",
+                self.event_type_name.to_lowercase(),
+                plant_name
+            )
+        };
+        match &self.actor {
+            Some(actor) => format!("{} {}", actor, action),
+            None => {
+                let mut chars = action.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => action,
+                }
+            }
+        }
+    }
+}
+
+/// HTTP response sent from the server in response to [`GetActivity`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetActivityResponse {
+    pub items: Vec<ActivityItem>,
+    /// Cursor to request the next page with. None if there are no more events
+    pub next_cursor: Option<ActivityCursor>,
+}
+
+/// The formats events can be exported in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+/// HTTP request sent to the server to export events for analysis, oldest first. Leaving the plants or event types empty includes every plant or event type
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportEvents {
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub plant_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub event_types: Vec<Uuid>,
+    /// Either [`GetEventType::Span`] to export the events between two dates or [`GetEventType::All`]
+    pub span: GetEventType,
+}
+
+/// An exported event, flattened so every field is a column
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExportedEvent {
+    pub id: Uuid,
+    pub event_date: NaiveDateTime,
+    pub plant_id: Uuid,
+    pub plant_name: Option<String>,
+    pub event_type_id: Uuid,
+    pub event_type_name: String,
+    /// The data as plain text, eg the measurement of a number event
+    pub value: String,
+    pub data: EventData,
+}
+
+impl ExportedEvent {
+    pub fn csv_header() -> String {
+        write_csv_row(&[
+            "id".to_string(),
+            "event_date".to_string(),
+            "plant_id".to_string(),
+            "plant_name".to_string(),
+            "event_type_id".to_string(),
+            "event_type_name".to_string(),
+            "value".to_string(),
+            "data".to_string(),
+        ])
+    }
+
+    /// The event as a row under [`ExportedEvent::csv_header`]. The data column holds the full data as JSON
+    pub fn to_csv_row(&self) -> String {
+        write_csv_row(&[
+            self.id.to_string(),
+            self.event_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+            self.plant_id.to_string(),
+            self.plant_name.clone().unwrap_or_default(),
+            self.event_type_id.to_string(),
+            self.event_type_name.clone(),
+            self.value.clone(),
+            serde_json::to_string(&self.data).unwrap_or_default(),
+        ])
+    }
+}
+
 pub enum GetEventError {
     InfallibleEventHadNoEvents,
     UniqueEventHadNoEvents,
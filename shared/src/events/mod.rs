@@ -1,7 +1,11 @@
-use chrono::{NaiveDateTime, Utc};
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::plant::StateTransition;
+
 pub mod events_http;
 
 pub static PLANT_STATE_ID: &str = "1a5c53bb-18c2-4789-8ba4-9bbfc4bc2371";
@@ -10,8 +14,19 @@ pub static BIRTHDAY_EVENT_ID: &str = "700866fd-a8b8-4cef-af5b-1752a1434129";
 pub static REPOTTED_EVENT_ID: &str = "1e7c1c14-dddd-4658-be0a-5c20726b4d16";
 pub static WATERED_EVENT_ID: &str = "9c8c6cfc-e111-44c2-9b5c-f5d84ae2da7a";
 pub static PHOTO_EVENT_TYPE_ID: &str = "77271e34-e207-47cd-b360-f1db84db4f7e";
+pub static MOVED_EVENT_TYPE_ID: &str = "cf78cef1-d41f-431c-8c8f-6d8775400d1e";
+pub static PROPAGATED_EVENT_TYPE_ID: &str = "1c650ce9-dba0-44c4-9a6b-a8881b5d7ad8";
+pub static DORMANCY_EVENT_TYPE_ID: &str = "db8efed4-ee9d-4d9d-a1bd-136b96533903";
+pub static QUARANTINE_EVENT_TYPE_ID: &str = "55703608-86fa-4808-89b7-8a9560d05430";
+pub static VACATION_CARE_EVENT_TYPE_ID: &str = "be66dbb6-5118-4314-bab7-15b9fcbd3548";
+pub static HEIGHT_EVENT_TYPE_ID: &str = "438a9f0e-5207-4f6d-8c5a-3672d8a773c3";
+pub static LEAF_COUNT_EVENT_TYPE_ID: &str = "6376a8b2-ae2d-4a59-be46-d2ed2ab1f07f";
+pub static SOIL_MOISTURE_EVENT_TYPE_ID: &str = "41a75b1d-8a7b-4a73-a11b-97d70409ecd9";
+pub static SOIL_PH_EVENT_TYPE_ID: &str = "8a68fa47-eefa-4dab-860c-535cb37050c6";
+pub static LIGHT_LEVEL_EVENT_TYPE_ID: &str = "9d8ce1c4-4c73-4e88-a9e6-3bb446beefce";
+pub static SYMPTOMS_EVENT_TYPE_ID: &str = "3e78fdcc-c44e-426d-80e7-afdbe5c43479";
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct EventType {
     pub id: Uuid,
     pub name: String,
@@ -92,15 +107,70 @@ impl EventInstance {
 }
 
 /// The different types of data that a Plant Event can contain that can exist
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub enum EventDataKind {
     /// A specific day
     DateTime,
     /// A time period between two dates
     Period,
     CustomEnum(CustomEnum),
-    Number,
+    /// A custom enum where any number of options can be picked at once
+    MultiEnum(MultiEnum),
+    /// A measurement, in the given unit and range
+    Number(NumberFormat),
     String,
+    /// A reference to a saved location
+    Location,
+    /// A reference to another plant
+    Plant,
+    /// A change in the lifecycle of the plant
+    Lifecycle,
+}
+
+impl EventDataKind {
+    /// Reads event data of this kind from text, such as a cell of a spreadsheet. Periods are written as start or end,
+    /// multi enum options are separated by semicolons. Kinds that reference something by id can't be read from text
+    pub fn parse_value(&self, value: &str) -> Result<EventData, String> {
+        let value = value.trim();
+        match self {
+            EventDataKind::DateTime => Ok(EventData::DateTime),
+            EventDataKind::Period => match value.to_lowercase().as_str() {
+                "start" | "periodstart" => Ok(EventData::Period(Period::PeriodStart)),
+                "end" | "periodend" => Ok(EventData::Period(Period::PeriodEnd)),
+                _ => Err(format!("{} is not start or end", value)),
+            },
+            EventDataKind::CustomEnum(declared) => {
+                let mut custom_enum = declared.clone();
+                custom_enum
+                    .select_by_string(value)
+                    .map_err(|err| err.to_string())?;
+                Ok(EventData::CustomEnum(custom_enum))
+            }
+            EventDataKind::MultiEnum(declared) => {
+                let mut multi_enum = declared.clone();
+                multi_enum.clear();
+                for option in value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|option| !option.is_empty())
+                {
+                    multi_enum
+                        .select_by_string(option)
+                        .map_err(|err| err.to_string())?;
+                }
+                Ok(EventData::MultiEnum(multi_enum))
+            }
+            EventDataKind::Number(_) => value
+                .parse::<f64>()
+                .map(EventData::Number)
+                .map_err(|_| format!("{} is not a number", value)),
+            EventDataKind::String if value.is_empty() => Err("Value is empty".to_string()),
+            EventDataKind::String => Ok(EventData::String(value.to_string())),
+            EventDataKind::Location | EventDataKind::Plant | EventDataKind::Lifecycle => {
+                Err("Events of this kind can't be read from text".to_string())
+            }
+        }
+    }
 }
 
 /// The different types of data that a Plant Event can contain that can exist
@@ -110,12 +180,20 @@ pub enum EventData {
     DateTime,
     /// A time period between two dates, can be either period start or end
     Period(Period),
-    /// A custom enum that provides the user with multi-select functionality
+    /// A custom enum that provides the user with a choice of one option
     CustomEnum(CustomEnum),
+    /// A custom enum that provides the user with multi-select functionality
+    MultiEnum(MultiEnum),
     /// A number
     Number(f64),
     /// A string
     String(String),
+    /// The id of a saved location
+    Location(Uuid),
+    /// The id of another plant
+    Plant(Uuid),
+    /// The plant moved from one lifecycle state to another
+    Lifecycle(StateTransition),
 }
 
 impl EventData {
@@ -133,18 +211,64 @@ impl EventData {
         return match self {
             EventData::DateTime => event_data_kind == EventDataKind::DateTime,
             EventData::Period(_) => event_data_kind == EventDataKind::Period,
-            EventData::Number(_) => event_data_kind == EventDataKind::Number,
+            EventData::Number(_) => matches!(event_data_kind, EventDataKind::Number(_)),
             EventData::String(_) => event_data_kind == EventDataKind::String,
-            _ => true,
+            EventData::Location(_) => event_data_kind == EventDataKind::Location,
+            EventData::Plant(_) => event_data_kind == EventDataKind::Plant,
+            EventData::Lifecycle(_) => event_data_kind == EventDataKind::Lifecycle,
+            EventData::CustomEnum(custom_enum) => match &event_data_kind {
+                EventDataKind::CustomEnum(declared) => custom_enum.check(declared).is_ok(),
+                _ => false,
+            },
+            EventData::MultiEnum(multi_enum) => match &event_data_kind {
+                EventDataKind::MultiEnum(declared) => multi_enum.check(declared).is_ok(),
+                _ => false,
+            },
         };
     }
 
+    /// Checks that enum data was made from the options declared by the event type and has a valid selection. Other data is always fine
+    pub fn check_options(&self, event_data_kind: &EventDataKind) -> Result<(), CustomEnumError> {
+        match (self, event_data_kind) {
+            (EventData::CustomEnum(custom_enum), EventDataKind::CustomEnum(declared)) => {
+                custom_enum.check(declared)
+            }
+            (EventData::MultiEnum(multi_enum), EventDataKind::MultiEnum(declared)) => {
+                multi_enum.check(declared)
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn expect_kind_string(&self) -> Option<String> {
         return match self {
             EventData::String(string) => Some(string.clone()),
             _ => None,
         };
     }
+
+    /// The data written as plain text, the way [`EventDataKind::parse_value`] reads it back. Ids are written out in full
+    /// and lifecycle changes as the state the plant moved to
+    pub fn value_text(&self) -> String {
+        match self {
+            EventData::DateTime => "".to_string(),
+            EventData::Period(Period::PeriodStart) => "start".to_string(),
+            EventData::Period(Period::PeriodEnd) => "end".to_string(),
+            EventData::CustomEnum(custom_enum) => {
+                custom_enum.selected().cloned().unwrap_or_default()
+            }
+            EventData::MultiEnum(multi_enum) => multi_enum
+                .selected()
+                .into_iter()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(";"),
+            EventData::Number(value) => value.to_string(),
+            EventData::String(string) => string.clone(),
+            EventData::Location(id) | EventData::Plant(id) => id.to_string(),
+            EventData::Lifecycle(transition) => transition.to.name().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
@@ -153,6 +277,164 @@ pub enum Period {
     PeriodEnd,
 }
 
+/// The unit a numeric event is measured in
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MeasurementUnit {
+    Centimeters,
+    Leaves,
+    Ph,
+    Percent,
+    Lux,
+    /// A unit not covered above, shown as written
+    Custom(String),
+    /// A plain number
+    None,
+}
+
+impl MeasurementUnit {
+    /// The short form shown after a value
+    pub fn symbol(&self) -> &str {
+        match self {
+            MeasurementUnit::Centimeters => "cm",
+            MeasurementUnit::Leaves => "leaves",
+            MeasurementUnit::Ph => "pH",
+            MeasurementUnit::Percent => "%",
+            MeasurementUnit::Lux => "lux",
+            MeasurementUnit::Custom(unit) => unit,
+            MeasurementUnit::None => "",
+        }
+    }
+
+    /// Formats a value in this unit, eg "12 cm" or "pH 6.5"
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            MeasurementUnit::Ph => format!("pH {}", value),
+            MeasurementUnit::Percent => format!("{}%", value),
+            MeasurementUnit::None => format!("{}", value),
+            unit => format!("{} {}", value, unit.symbol()),
+        }
+    }
+}
+
+/// How a numeric event type is measured. Values outside of the range are rejected
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NumberFormat {
+    pub unit: MeasurementUnit,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl NumberFormat {
+    pub fn new(unit: MeasurementUnit, min: Option<f64>, max: Option<f64>) -> NumberFormat {
+        NumberFormat { unit, min, max }
+    }
+
+    /// Checks that the value is a finite number inside of the range. Returns a description of the problem if it isn't
+    pub fn check(&self, value: f64) -> Option<String> {
+        if !value.is_finite() {
+            return Some("Measurement must be a finite number".to_string());
+        }
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => Some(format!(
+                "Measurement must be at least {}",
+                self.unit.format(min)
+            )),
+            (_, Some(max)) if value > max => Some(format!(
+                "Measurement must be at most {}",
+                self.unit.format(max)
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A span of time made from a period start event and the period end event paired with it
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PeriodSpan {
+    pub plant_id: Uuid,
+    pub event_type_id: Uuid,
+    pub start: EventInstance,
+    /// The event that ended the period. None while the period is still open
+    pub end: Option<EventInstance>,
+}
+
+impl PeriodSpan {
+    pub fn is_open(&self) -> bool {
+        self.end.is_none()
+    }
+
+    /// How long the period lasted. Open periods are measured up until now
+    pub fn duration(&self, now: NaiveDateTime) -> Duration {
+        let end = self.end.as_ref().map(|end| end.event_date).unwrap_or(now);
+        end.signed_duration_since(self.start.event_date)
+    }
+
+    /// Pairs every period start with the period end that follows it for the same plant and event type.
+    ///
+    /// Events that aren't periods and ends without a start are skipped
+    pub fn pair_periods(mut events: Vec<EventInstance>) -> Vec<PeriodSpan> {
+        events.sort_by_key(|event| event.event_date);
+
+        let mut spans = vec![];
+        let mut open: HashMap<(Uuid, Uuid), PeriodSpan> = HashMap::new();
+        for event in events {
+            let key = (event.plant_id, event.event_type_id);
+            match event.data {
+                EventData::Period(Period::PeriodStart) => {
+                    let span = PeriodSpan {
+                        plant_id: event.plant_id,
+                        event_type_id: event.event_type_id,
+                        start: event,
+                        end: None,
+                    };
+                    // A second start without an end leaves the first period open
+                    if let Some(unclosed) = open.insert(key, span) {
+                        spans.push(unclosed);
+                    }
+                }
+                EventData::Period(Period::PeriodEnd) => {
+                    if let Some(mut span) = open.remove(&key) {
+                        span.end = Some(event);
+                        spans.push(span);
+                    }
+                }
+                _ => {}
+            }
+        }
+        spans.extend(open.into_values());
+        spans.sort_by_key(|span| span.start.event_date);
+        spans
+    }
+}
+
+/// Why a custom enum selection was refused
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum CustomEnumError {
+    /// The index is past the end of the options
+    InvalidIndex(usize),
+    /// The string isn't one of the options
+    UnknownOption(String),
+    /// The options don't match the ones declared by the event type
+    OptionsChanged,
+    /// An option was picked more than once
+    DuplicateSelection(usize),
+}
+
+impl std::fmt::Display for CustomEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CustomEnumError::InvalidIndex(index) => write!(f, "There is no option {}", index),
+            CustomEnumError::UnknownOption(option) => write!(f, "{} is not an option", option),
+            CustomEnumError::OptionsChanged => {
+                write!(f, "Options do not match the options of the event type")
+            }
+            CustomEnumError::DuplicateSelection(index) => {
+                write!(f, "Option {} was selected more than once", index)
+            }
+        }
+    }
+}
+
 /// A custom enum, enables user defined multi-choice events
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct CustomEnum {
@@ -161,17 +443,6 @@ pub struct CustomEnum {
 }
 
 impl CustomEnum {
-    pub fn plant_state() -> CustomEnum {
-        CustomEnum {
-            options: vec![
-                "Alive".to_string(),
-                "Retired".to_string(),
-                "Gifted".to_string(),
-            ],
-            selected: 0,
-        }
-    }
-
     /// Create a new custom enum based on the given options
     pub fn new(options: Vec<&str>) -> Option<CustomEnum> {
         if options.len() == 0 {
@@ -183,22 +454,34 @@ impl CustomEnum {
         })
     }
 
-    /// Select an option. Silently fails if the index was invalid
-    pub fn select(&mut self, index: usize) {
-        if self.options.len() >= index {
-            self.selected = index;
-        };
+    /// Select an option. Fails if the index was invalid, leaving the selection unchanged
+    pub fn select(&mut self, index: usize) -> Result<(), CustomEnumError> {
+        if index >= self.options.len() {
+            return Err(CustomEnumError::InvalidIndex(index));
+        }
+        self.selected = index;
+        Ok(())
     }
 
-    /// Select an option based on string matching the options
-    pub fn select_by_string(&mut self, string: String) {
-        for (index, option) in self.options.iter().enumerate() {
-            if option == &string {
-                self.selected = index;
-            }
+    /// Select an option based on string matching the options. Fails if no option matched
+    pub fn select_by_string(&mut self, string: &str) -> Result<(), CustomEnumError> {
+        match self.options.iter().position(|option| option == string) {
+            Some(index) => self.select(index),
+            None => Err(CustomEnumError::UnknownOption(string.to_string())),
         }
     }
 
+    /// Checks that this enum has the declared options and a valid selection
+    pub fn check(&self, declared: &CustomEnum) -> Result<(), CustomEnumError> {
+        if self.options != declared.options {
+            return Err(CustomEnumError::OptionsChanged);
+        }
+        if self.selected >= self.options.len() {
+            return Err(CustomEnumError::InvalidIndex(self.selected));
+        }
+        Ok(())
+    }
+
     /// Returns the list of options
     pub fn options(&self) -> &Vec<String> {
         &self.options
@@ -209,3 +492,95 @@ impl CustomEnum {
         self.options.get(self.selected)
     }
 }
+
+/// A custom enum where any number of the options can be selected, eg the symptoms a plant is showing
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct MultiEnum {
+    options: Vec<String>,
+    selected: Vec<usize>,
+}
+
+impl MultiEnum {
+    pub fn symptoms() -> MultiEnum {
+        MultiEnum {
+            options: vec![
+                "Yellow Leaves".to_string(),
+                "Brown Tips".to_string(),
+                "Drooping".to_string(),
+                "Leaf Drop".to_string(),
+                "Spots".to_string(),
+                "Pests".to_string(),
+                "Root Rot".to_string(),
+            ],
+            selected: vec![],
+        }
+    }
+
+    /// Create a new multi enum based on the given options, with nothing selected
+    pub fn new(options: Vec<&str>) -> Option<MultiEnum> {
+        if options.is_empty() {
+            return None;
+        }
+        Some(Self {
+            options: options.iter().map(|e| e.to_string()).collect(),
+            selected: vec![],
+        })
+    }
+
+    /// Add an option to the selection. Selecting an option twice does nothing
+    pub fn select(&mut self, index: usize) -> Result<(), CustomEnumError> {
+        if index >= self.options.len() {
+            return Err(CustomEnumError::InvalidIndex(index));
+        }
+        if !self.selected.contains(&index) {
+            self.selected.push(index);
+        }
+        Ok(())
+    }
+
+    /// Add an option to the selection based on string matching the options. Fails if no option matched
+    pub fn select_by_string(&mut self, string: &str) -> Result<(), CustomEnumError> {
+        match self.options.iter().position(|option| option == string) {
+            Some(index) => self.select(index),
+            None => Err(CustomEnumError::UnknownOption(string.to_string())),
+        }
+    }
+
+    /// Remove an option from the selection
+    pub fn deselect(&mut self, index: usize) {
+        self.selected.retain(|selected| *selected != index);
+    }
+
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Returns the list of options
+    pub fn options(&self) -> &Vec<String> {
+        &self.options
+    }
+
+    /// Returns the selected options in the order they were picked, skipping invalid indexes
+    pub fn selected(&self) -> Vec<&String> {
+        self.selected
+            .iter()
+            .filter_map(|index| self.options.get(*index))
+            .collect()
+    }
+
+    /// Checks that this enum has the declared options and every selection is valid and picked once
+    pub fn check(&self, declared: &MultiEnum) -> Result<(), CustomEnumError> {
+        if self.options != declared.options {
+            return Err(CustomEnumError::OptionsChanged);
+        }
+        for (position, index) in self.selected.iter().enumerate() {
+            if *index >= self.options.len() {
+                return Err(CustomEnumError::InvalidIndex(*index));
+            }
+            if self.selected[..position].contains(index) {
+                return Err(CustomEnumError::DuplicateSelection(*index));
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,9 +4,17 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod archive;
+pub mod calendar;
+pub mod collections;
+pub mod csv_import;
 pub mod events;
+pub mod locations;
+pub mod notes;
 pub mod photos;
 pub mod plant;
+pub mod species;
+pub mod stats;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct DirtyCache {
@@ -19,5 +27,17 @@ pub enum CacheType {
     Plant(Uuid),
     /// Left: Plant Id - Right: Event Type ID, time of event thats dirty
     Event(Uuid, Uuid, NaiveDateTime),
+    /// Left: Event Type ID - Right: Every plant the event was logged for along with the time of its event
+    Events(Uuid, Vec<(Uuid, NaiveDateTime)>),
     EventType(Uuid),
+    /// Location Id
+    Location(Uuid),
+    /// Extra Data Field Id
+    ExtraDataField(Uuid),
+    /// Left: Plant Id - Right: Note Id
+    Note(Uuid, Uuid),
+    /// Species Id
+    Species(Uuid),
+    /// Collection Id
+    Collection(Uuid),
 }
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::locations::{Direction, LightLevel, LocationKind};
+
+/// HTTP request sent to the server to create a new location
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewLocation {
+    pub name: String,
+    pub kind: LocationKind,
+    pub parent_id: Option<Uuid>,
+    pub light_level: Option<LightLevel>,
+    pub direction: Option<Direction>,
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod locations_http;
+
+/// A place a plant can be kept. Locations nest from a home, to the rooms in that home, to the spots in those rooms
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Location {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: LocationKind,
+    /// The location this location is inside of. Homes have no parent
+    pub parent_id: Option<Uuid>,
+    /// How much light plants in this location get
+    pub light_level: Option<LightLevel>,
+    /// The direction the windows of this location face
+    pub direction: Option<Direction>,
+}
+
+impl Location {
+    /// Returns the room this location belongs to. Rooms are their own room, homes don't have a room
+    pub fn room<'a>(&'a self, locations: &'a [Location]) -> Option<&'a Location> {
+        match self.kind {
+            LocationKind::Home => None,
+            LocationKind::Room => Some(self),
+            LocationKind::Spot => locations
+                .iter()
+                .find(|location| Some(location.id) == self.parent_id)
+                .and_then(|parent| parent.room(locations)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LocationKind {
+    Home,
+    Room,
+    Spot,
+}
+
+impl LocationKind {
+    /// The kind of location that this kind of location must be inside of
+    pub fn parent_kind(&self) -> Option<LocationKind> {
+        match self {
+            LocationKind::Home => None,
+            LocationKind::Room => Some(LocationKind::Home),
+            LocationKind::Spot => Some(LocationKind::Room),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LightLevel {
+    Low,
+    Medium,
+    BrightIndirect,
+    Direct,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod notes_http;
+
+/// The scheme used to reference a photo from inside of a note, for example `![Repotted](photo:<photo id>)`
+pub static PHOTO_REFERENCE_SCHEME: &str = "photo:";
+
+/// A Markdown journal entry about a plant. Can optionally be about a specific event of that plant
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Note {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    /// The event this note was written about
+    pub event_id: Option<Uuid>,
+    /// The Markdown body of the note
+    pub body: String,
+    /// The date the note was written in UTC
+    pub date_created: NaiveDateTime,
+    /// The date the note was last edited in UTC
+    pub date_edited: NaiveDateTime,
+}
+
+impl Note {
+    /// Returns the ids of every photo referenced inside of the note body
+    pub fn photo_references(body: &str) -> Vec<Uuid> {
+        body.match_indices(PHOTO_REFERENCE_SCHEME)
+            .filter_map(|(index, scheme)| {
+                let start = index + scheme.len();
+                body.get(start..start + 36)
+                    .and_then(|id| Uuid::parse_str(id).ok())
+            })
+            .collect()
+    }
+}
+
+/// A previous body of a note. One is saved every time a note is edited
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NoteRevision {
+    pub note_id: Uuid,
+    pub body: String,
+    /// The date this body was replaced in UTC
+    pub date_replaced: NaiveDateTime,
+}
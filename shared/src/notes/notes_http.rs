@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// HTTP request sent to the server to write a new note about a plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewNote {
+    pub plant_id: Uuid,
+    pub event_id: Option<Uuid>,
+    pub body: String,
+}
+
+/// HTTP request sent to the server to change the body of a note. The old body is kept as a revision
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EditNote {
+    pub note_id: Uuid,
+    pub body: String,
+}
@@ -1,4 +1,4 @@
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use image::DynamicImage;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -8,6 +8,8 @@ pub struct NewPhoto {
     pub timestamp: i64,
     pub plant_id: Uuid,
     pub photo_binary: Vec<u8>,
+    /// When the client thinks the photo was taken in UTC. Only used when the photo itself has no EXIF capture date
+    pub photo_date: Option<NaiveDateTime>,
 }
 
 impl NewPhoto {
@@ -16,6 +18,97 @@ impl NewPhoto {
             timestamp: Utc::now().timestamp(),
             plant_id,
             photo_binary: image.as_bytes().iter().cloned().collect(),
+            photo_date: None,
         }
     }
 }
+
+/// A photo of a plant whose perceptual hash is close enough to an earlier photo of the same plant that they are probably the same picture
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PossibleDuplicate {
+    pub plant_id: Uuid,
+    pub photo_id: Uuid,
+    /// The earlier photo that this photo looks like
+    pub duplicate_of: Uuid,
+    /// Number of bits that differ between the two perceptual hashes. 0 means they look identical
+    pub distance: u32,
+}
+
+/// HTTP request sent to the server to render a timelapse out of every photo of a plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewTimelapse {
+    pub plant_id: Uuid,
+    /// Width and height in pixels that every photo is cropped and scaled to so the frames line up
+    pub frame_size: u32,
+    /// How long each photo is shown for in milliseconds
+    pub frame_delay_ms: u32,
+    /// Draw the date each photo was taken in the corner of its frame
+    pub date_caption: bool,
+}
+
+/// HTTP response describing the state of the timelapse of a plant
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum TimelapseStatus {
+    /// No timelapse has been requested for this plant yet
+    NotGenerated,
+    /// The timelapse is being rendered in the background
+    Generating,
+    /// The timelapse is rendered and saved at the given file location
+    Ready(String),
+    /// Rendering the timelapse failed with the given error
+    Failed(String),
+}
+
+/// A photo of a plant that has been saved on the server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Photo {
+    pub id: Uuid,
+    pub plant_id: Uuid,
+    /// When the photo was taken in UTC
+    pub photo_date: NaiveDateTime,
+    pub file_location: String,
+    pub thumbnail_location: String,
+}
+
+impl Photo {
+    /// The location of the thumbnail the server saves for every photo
+    pub fn thumbnail_location(photo_id: Uuid) -> String {
+        format!("./assets/photos/thumbs/{}.png", photo_id)
+    }
+}
+
+/// HTTP request sent to the server to page through the photos of every plant, newest first
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetPhotos {
+    /// Only return photos of this plant
+    pub plant_id: Option<Uuid>,
+    /// Only return photos taken within this span
+    pub span: Option<(NaiveDateTime, NaiveDateTime)>,
+    /// Only return photos of plants whose current state has this name
+    pub plant_state: Option<String>,
+    /// Return the photos that come after this cursor. None starts at the newest photo
+    pub cursor: Option<PhotoCursor>,
+    pub limit: i64,
+}
+
+/// Position of a photo in the newest first ordering of photos
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PhotoCursor {
+    pub photo_date: NaiveDateTime,
+    pub id: Uuid,
+}
+
+/// HTTP response sent from the server in response to [`GetPhotos`]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPhotosResponse {
+    pub photos: Vec<Photo>,
+    /// Cursor to request the next page with. None if there are no more photos
+    pub next_cursor: Option<PhotoCursor>,
+}
+
+/// HTTP request sent to the server to use one of a plants photos as its cover image
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetCoverPhoto {
+    pub plant_id: Uuid,
+    pub photo_id: Uuid,
+}
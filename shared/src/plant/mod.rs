@@ -1,10 +1,10 @@
 use std::hash::{DefaultHasher, Hash, Hasher};
 
-use chrono::{NaiveDateTime, NaiveTime, Utc};
+use chrono::{NaiveDate, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::events::CustomEnum;
+use crate::{locations::Location, photos::Photo};
 
 pub mod plant_http;
 
@@ -16,13 +16,15 @@ pub struct Plant {
     pub date_created: NaiveDateTime,
     /// The date that the plant was last modified in UTC
     pub event_modified: NaiveDateTime,
+    /// The values of the user defined fields that have been set on this plant
+    pub extra_data: Vec<ExtraData>,
 }
 
 impl Plant {
     pub fn new(name: String, location: Location) -> Plant {
         Plant {
             id: Uuid::new_v4(),
-            //extra_data: vec![],
+            extra_data: vec![],
             //location: HistoryItem::new(location),
             //notes: vec![],
             date_created: Utc::now().naive_utc(),
@@ -50,52 +52,278 @@ pub struct PlantDemographic {
     //pub extra_data: Vec<(String, HistoryItem<ExtraData>)>,
     //pub location: HistoryItem<Location>,
     //pub notes: Vec<String>,
-    pub plant_state: CustomEnum,
+    pub plant_state: PlantState,
+    /// The photo that represents this plant. The photo the user chose as the cover or else the latest photo of the plant
+    pub cover_photo: Option<Photo>,
+    /// The id of the location the plant was last moved to
+    pub current_location: Option<Uuid>,
+    /// The id of the species the plant is linked to
+    pub species_id: Option<Uuid>,
+    /// The tags the plant has been given, in alphabetical order
+    pub tags: Vec<String>,
 }
 
 impl PlantDemographic {
     pub fn from_plant(
         plant: Plant,
         name: Option<String>,
-        plant_state: CustomEnum,
+        plant_state: PlantState,
+        cover_photo: Option<Photo>,
+        current_location: Option<Uuid>,
+        species_id: Option<Uuid>,
+        tags: Vec<String>,
     ) -> PlantDemographic {
         PlantDemographic {
             id: plant.id,
             name: name,
             plant_state: plant_state,
+            cover_photo,
+            current_location,
+            species_id,
+            tags,
             date_created: plant.date_created,
             event_modified: plant.event_modified,
         }
     }
 }
 
-#[derive(Debug, Hash, Serialize, Deserialize, Clone, PartialEq, Default)]
+/// The longest a tag can be
+pub static MAX_TAG_LENGTH: usize = 100;
+
+/// Puts a tag in the form it is saved in, lowercase with single spaces. None if the tag is empty or too long
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    match tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+        true => None,
+        false => Some(tag),
+    }
+}
+
+/// The family tree of a plant
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PlantLineage {
+    pub plant_id: Uuid,
+    /// The plants this plant was propagated from, starting with its parent
+    pub ancestors: Vec<Uuid>,
+    /// Every plant propagated from this plant
+    pub descendants: Vec<LineageNode>,
+}
+
+/// A plant in a family tree along with the plants propagated from it
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LineageNode {
+    pub plant_id: Uuid,
+    pub children: Vec<LineageNode>,
+}
+
+/// Where a plant is in its life. Gifted, dead and sold plants are retired and kept in the archive
+#[derive(Debug, Hash, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
 pub enum PlantState {
     #[default]
     Alive,
-    Retired,
-    Gifted,
+    /// Resting, usually over winter
+    Dormant,
+    /// Unwell, with what is wrong if known
+    Sick {
+        symptoms: Option<String>,
+    },
+    /// Given away, with who it was given to if known
+    Gifted {
+        to: Option<String>,
+    },
+    Died {
+        cause: Option<String>,
+    },
+    /// Sold, with who bought it if known
+    Sold {
+        to: Option<String>,
+    },
+}
+
+impl PlantState {
+    /// The name of every state, in the order they are offered to the user
+    pub const NAMES: [&'static str; 6] = ["Alive", "Dormant", "Sick", "Gifted", "Died", "Sold"];
+
+    /// The names of the states of plants that are no longer in the users care
+    pub const RETIRED_NAMES: [&'static str; 3] = ["Gifted", "Died", "Sold"];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlantState::Alive => "Alive",
+            PlantState::Dormant => "Dormant",
+            PlantState::Sick { .. } => "Sick",
+            PlantState::Gifted { .. } => "Gifted",
+            PlantState::Died { .. } => "Died",
+            PlantState::Sold { .. } => "Sold",
+        }
+    }
+
+    /// Creates a state from its name along with its details, such as who a plant was gifted to. Empty details are left out
+    pub fn from_name(name: &str, details: String) -> Option<PlantState> {
+        let details = match details.trim().is_empty() {
+            true => None,
+            false => Some(details.trim().to_string()),
+        };
+        Some(match name {
+            "Alive" => PlantState::Alive,
+            "Dormant" => PlantState::Dormant,
+            "Sick" => PlantState::Sick { symptoms: details },
+            "Gifted" => PlantState::Gifted { to: details },
+            "Died" => PlantState::Died { cause: details },
+            "Sold" => PlantState::Sold { to: details },
+            _ => return None,
+        })
+    }
+
+    /// The details saved alongside the state, such as the cause of death
+    pub fn details(&self) -> Option<&String> {
+        match self {
+            PlantState::Alive | PlantState::Dormant => None,
+            PlantState::Sick { symptoms } => symptoms.as_ref(),
+            PlantState::Gifted { to } | PlantState::Sold { to } => to.as_ref(),
+            PlantState::Died { cause } => cause.as_ref(),
+        }
+    }
+
+    /// Retired plants are no longer in the users care
+    pub fn is_retired(&self) -> bool {
+        PlantState::RETIRED_NAMES.contains(&self.name())
+    }
+
+    /// Plants in care can move to any other state, retired plants stay retired
+    pub fn can_become(&self, next: &PlantState) -> bool {
+        !self.is_retired() && self.name() != next.name()
+    }
+
+    /// The names of the states a plant in this state can move to
+    pub fn next_states(&self) -> Vec<&'static str> {
+        match self.is_retired() {
+            true => vec![],
+            false => PlantState::NAMES
+                .into_iter()
+                .filter(|name| *name != self.name())
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for PlantState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self, self.details()) {
+            (PlantState::Gifted { .. } | PlantState::Sold { .. }, Some(to)) => {
+                write!(f, "{} to {}", self.name(), to)
+            }
+            (_, Some(details)) => write!(f, "{} ({})", self.name(), details),
+            (_, None) => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// A change in the lifecycle of a plant, saved as the data of its state events
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StateTransition {
+    /// The state the plant was in. None for the state a plant starts out in
+    pub from: Option<PlantState>,
+    pub to: PlantState,
+    /// Anything else worth remembering about the change
+    pub note: Option<String>,
+}
+
+impl StateTransition {
+    /// The transition every new plant starts with
+    pub fn initial() -> StateTransition {
+        StateTransition {
+            from: None,
+            to: PlantState::Alive,
+            note: None,
+        }
+    }
+
+    /// Checks that the transition starts from the plants current state and is allowed. Returns a description of the problem if it isn't
+    pub fn check(&self, current: Option<&PlantState>) -> Option<String> {
+        if self.from.as_ref() != current {
+            return Some("The plants state has changed since this transition was made".to_string());
+        }
+        match current {
+            Some(current) if !current.can_become(&self.to) => Some(format!(
+                "A plant that is {} can't become {}",
+                current.name(),
+                self.to.name()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A user defined field that every plant can have a value for, such as pot size or soil mix
+#[derive(Debug, Hash, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ExtraDataField {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: ExtraDataKind,
+    /// The unit numbers are measured in, such as cm or $
+    pub unit: Option<String>,
+}
+
+/// The kinds of values a user defined field can hold
+#[derive(Debug, Hash, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ExtraDataKind {
+    Text,
+    Date,
+    Number,
 }
 
 /// Extra data that every plant can have, these can be user defined
-#[derive(Hash)]
+#[derive(Debug, Hash, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ExtraData {
-    pub name: String,
+    pub field: ExtraDataField,
+    /// The current value of the field
     pub data: ExtraDataType,
+    /// The date the current value was set in UTC
+    pub date_changed: NaiveDateTime,
+    /// Every previous value of the field, newest first
+    pub history: Vec<(NaiveDateTime, ExtraDataType)>,
 }
 
 /// The different types of extra data that can be used
-#[derive(Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum ExtraDataType {
     Text(String),
-    Date(NaiveTime),
-    Number(u32),
+    Date(NaiveDate),
+    Number(f64),
 }
 
-#[derive(Hash)]
-pub struct Location {
-    pub name: String,
+impl ExtraDataType {
+    pub fn kind(&self) -> ExtraDataKind {
+        match self {
+            ExtraDataType::Text(_) => ExtraDataKind::Text,
+            ExtraDataType::Date(_) => ExtraDataKind::Date,
+            ExtraDataType::Number(_) => ExtraDataKind::Number,
+        }
+    }
 }
 
-#[derive(Hash)]
-pub struct Photo {}
+impl Hash for ExtraDataType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ExtraDataType::Text(text) => text.hash(state),
+            ExtraDataType::Date(date) => date.hash(state),
+            // Floats can't be hashed directly so their bits are hashed instead
+            ExtraDataType::Number(number) => number.to_bits().hash(state),
+        }
+    }
+}
+
+impl std::fmt::Display for ExtraDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtraDataType::Text(text) => write!(f, "{}", text),
+            ExtraDataType::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            ExtraDataType::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
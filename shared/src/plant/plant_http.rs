@@ -2,21 +2,49 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{events::events_http::NewEvent, plant::PlantState};
+use crate::{
+    events::{events_http::NewEvent, EventInstance},
+    notes::Note,
+    photos::Photo,
+    plant::{ExtraDataKind, ExtraDataType, PlantState},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NewPlant {
     pub name: String,
     pub timestamp: i64,
     pub starting_events: Vec<NewEvent>,
+    /// The plant this plant was propagated from
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// HTTP request sent to the server to find a plant. Names are matched ignoring case and small typos
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum RequestPlant {
     ByName(String),
     ByUuid(Uuid),
 }
 
+/// HTTP response sent by the server for [`RequestPlant`] requests
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum RequestPlantResponse {
+    /// Exactly one plant matched
+    Found(PlantCandidate),
+    /// No single plant matched, these are the closest plants with the best match first
+    Candidates(Vec<PlantCandidate>),
+    NotFound,
+}
+
+/// A plant that matched a [`RequestPlant`] request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PlantCandidate {
+    pub plant_id: Uuid,
+    pub name: Option<String>,
+    /// How closely the name matched, 1 is an exact match
+    pub score: f64,
+}
+
 /// A request sent by the client for the server to verify the status of its plant list. Should include all the plants the server has
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VerifyClientPlantList {
@@ -35,8 +63,153 @@ pub struct VerifyClientPlantListResponse {
     pub events_modified: Vec<Uuid>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A change to a plant. A batch of these is applied together, each one saved as an event
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ModifyPlant {
     ChangeName(String),
     ChangeState(PlantState),
 }
+
+/// HTTP request sent to the server to define a new field that plants can have
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewExtraDataField {
+    pub name: String,
+    pub kind: ExtraDataKind,
+    pub unit: Option<String>,
+}
+
+/// HTTP request sent to the server to set the value of a field on a plant. Previous values are kept as history
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetExtraData {
+    pub plant_id: Uuid,
+    pub field_id: Uuid,
+    pub data: ExtraDataType,
+}
+
+/// HTTP request sent to the server to search for plants. Every filter that is set must match
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchPlants {
+    /// Words to look for in the plants name, notes and species. Words are matched by prefix so partial words match while typing
+    pub query: Option<String>,
+    /// Only plants whose current state has this name
+    pub state: Option<String>,
+    /// Only plants that are or aren't retired
+    pub retired: Option<bool>,
+    /// Only plants currently in this location
+    pub location_id: Option<Uuid>,
+    /// Only plants last watered before this date, including plants that have never been watered
+    pub last_watered_before: Option<NaiveDateTime>,
+    /// Only plants that do or don't have photos
+    pub has_photo: Option<bool>,
+    /// Only plants with every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Only plants matching the filter of this collection
+    #[serde(default)]
+    pub collection_id: Option<Uuid>,
+    pub sort: PlantSort,
+    /// Return the plants that come after this cursor. None starts at the beginning
+    pub cursor: Option<PlantCursor>,
+    pub limit: i64,
+}
+
+/// The order plants are searched in
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum PlantSort {
+    /// Alphabetically by name
+    #[default]
+    Name,
+    /// Newest plants first
+    Created,
+    /// Plants with the most recent events first
+    LastEvent,
+}
+
+/// Position of a plant in the order of a search
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum PlantCursor {
+    Name(String, Uuid),
+    Created(NaiveDateTime, Uuid),
+    LastEvent(NaiveDateTime, Uuid),
+}
+
+/// HTTP response sent by the server for [`SearchPlants`] requests
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchPlantsResponse {
+    /// The ids of the matching plants in order
+    pub plants: Vec<Uuid>,
+    /// The cursor to request the next page with. None when there are no more plants
+    pub next_cursor: Option<PlantCursor>,
+}
+
+/// HTTP request sent to the server to replace the tags of a plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetPlantTags {
+    pub plant_id: Uuid,
+    pub tags: Vec<String>,
+}
+
+/// HTTP request sent to the server for a page of everything that has happened to a plant, newest first
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetTimeline {
+    /// Only include these kinds of entries. Empty includes every kind
+    #[serde(default)]
+    pub kinds: Vec<TimelineKind>,
+    /// Only include events of these types. Empty includes every event type. Notes and photos are not affected
+    #[serde(default)]
+    pub event_types: Vec<Uuid>,
+    /// Return the entries that come after this cursor. None starts at the newest entry
+    pub cursor: Option<TimelineCursor>,
+    pub limit: i64,
+}
+
+/// The different things that show up on a plants timeline
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TimelineKind {
+    Event,
+    Note,
+    Photo,
+}
+
+/// Position of an entry in the newest first ordering of a timeline
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimelineCursor {
+    pub date: NaiveDateTime,
+    pub id: Uuid,
+}
+
+/// A single entry on a plants timeline
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum TimelineEntry {
+    /// An event of any type other than photos, which are shown as [`TimelineEntry::Photo`]
+    Event(EventInstance),
+    Note(Note),
+    Photo(Photo),
+}
+
+impl TimelineEntry {
+    pub fn id(&self) -> Uuid {
+        match self {
+            TimelineEntry::Event(event) => event.id,
+            TimelineEntry::Note(note) => note.id,
+            TimelineEntry::Photo(photo) => photo.id,
+        }
+    }
+
+    /// The date the entry is ordered by in UTC
+    pub fn date(&self) -> NaiveDateTime {
+        match self {
+            TimelineEntry::Event(event) => event.event_date,
+            TimelineEntry::Note(note) => note.date_created,
+            TimelineEntry::Photo(photo) => photo.photo_date,
+        }
+    }
+}
+
+/// HTTP response sent from the server in response to [`GetTimeline`]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetTimelineResponse {
+    pub entries: Vec<TimelineEntry>,
+    /// Cursor to request the next page with. None if there are no more entries
+    pub next_cursor: Option<TimelineCursor>,
+}
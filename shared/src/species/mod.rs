@@ -0,0 +1,72 @@
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::locations::LightLevel;
+
+pub mod species_http;
+
+/// A species of plant from the catalog that plants can be linked to
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Species {
+    pub id: Uuid,
+    pub scientific_name: String,
+    pub common_names: Vec<String>,
+    pub family: String,
+    /// How to care for plants of this species
+    pub care: CareProfile,
+    /// True if the species was added by the user instead of coming with the bundled catalog
+    pub user_defined: bool,
+}
+
+impl Species {
+    /// The name to show users. The first common name if there is one, else the scientific name
+    pub fn display_name(&self) -> &str {
+        self.common_names
+            .first()
+            .unwrap_or(&self.scientific_name)
+    }
+}
+
+/// How to care for a species. Every part is optional as not every species has been fully documented
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct CareProfile {
+    pub light: Option<LightLevel>,
+    pub watering_interval: Option<WateringInterval>,
+    pub humidity: Option<Humidity>,
+    pub temperature: Option<TemperatureRange>,
+    pub toxic_to_pets: Option<bool>,
+}
+
+impl CareProfile {
+    /// The window of time the next watering is due in, starting from the last time the plant was watered
+    pub fn next_watering(&self, last_watered: NaiveDateTime) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        self.watering_interval.as_ref().map(|interval| {
+            (
+                last_watered + Duration::days(interval.min_days as i64),
+                last_watered + Duration::days(interval.max_days as i64),
+            )
+        })
+    }
+}
+
+/// The range of days between waterings
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct WateringInterval {
+    pub min_days: u32,
+    pub max_days: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Humidity {
+    Low,
+    Medium,
+    High,
+}
+
+/// The range of temperatures a species is comfortable in
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TemperatureRange {
+    pub min_celsius: f32,
+    pub max_celsius: f32,
+}
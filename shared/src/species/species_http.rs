@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::species::CareProfile;
+
+/// HTTP request sent to the server to add a species to the catalog
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NewSpecies {
+    pub scientific_name: String,
+    pub common_names: Vec<String>,
+    pub family: String,
+    pub care: CareProfile,
+}
+
+/// HTTP request sent to the server to link a plant to a species. A species id of None unlinks the plant
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetPlantSpecies {
+    pub plant_id: Uuid,
+    pub species_id: Option<Uuid>,
+}
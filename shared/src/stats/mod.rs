@@ -0,0 +1,56 @@
+//! Care statistics computed from the event history of plants, for a single plant or the whole household
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod stats_http;
+
+/// How many days back the calendar heatmap goes
+pub static HEATMAP_DAYS: i64 = 365;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CareStats {
+    /// The plant the statistics are for. None when they cover every plant
+    pub plant_id: Option<Uuid>,
+    /// Every month from the first watering to the last, including months without any
+    pub waterings_per_month: Vec<MonthCount>,
+    /// Average number of days between two waterings of the same plant
+    pub average_watering_interval: Option<f64>,
+    /// Share between 0 and 1 of the waterings that came within the interval the plants species recommends.
+    /// None if no plant with a known watering interval has been watered twice
+    pub interval_adherence: Option<f64>,
+    /// The plant still in the users care that has gone the longest without water
+    pub longest_neglected: Option<NeglectedPlant>,
+    pub plants_added: Vec<MonthCount>,
+    /// Plants that were gifted, died or were sold
+    pub plants_retired: Vec<MonthCount>,
+    /// Days in a row, up to today, that care was logged. Name, state and propagation events don't count as care
+    pub current_streak: i64,
+    /// The most days in a row that care was logged
+    pub longest_streak: i64,
+    /// How many care events were logged on each day of the last [`HEATMAP_DAYS`] days. Days without care are left out
+    pub heatmap: Vec<DayCount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct MonthCount {
+    /// The first day of the month
+    pub month: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct DayCount {
+    pub day: NaiveDate,
+    pub count: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NeglectedPlant {
+    pub plant_id: Uuid,
+    pub name: Option<String>,
+    /// None if the plant has never been watered
+    pub last_watered: Option<NaiveDateTime>,
+    /// Days since the last watering, or since the plant was added if it has never been watered
+    pub days_without_water: i64,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// HTTP request sent to the server for care statistics. Leaving the plant empty includes every plant
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GetCareStats {
+    pub plant_id: Option<Uuid>,
+}